@@ -235,10 +235,7 @@ impl AggrSignature {
 mod tests {
     use std::collections::HashMap;
 
-    use dusk_bytes::DeserializableSlice;
-    use dusk_core::signatures::bls::{
-        PublicKey as BlsPublicKey, SecretKey as BlsSecretKey,
-    };
+    use dusk_core::signatures::bls::PublicKey as BlsPublicKey;
     use hex::FromHex;
     use node_data::ledger::{Header, Seed};
     use node_data::message::StepMessage;
@@ -246,6 +243,7 @@ mod tests {
     use super::*;
     use crate::aggregator::Aggregator;
     use crate::commons::RoundUpdate;
+    use crate::tests::secret_keys;
     use crate::user::committee::Committee;
     use crate::user::provisioners::{Provisioners, DUSK};
     use crate::user::sortition::Config;
@@ -260,27 +258,61 @@ mod tests {
     }
 
     #[test]
-    fn test_collect_votes() {
-        let sks = [
-            "7f6f2ccdb23f2abb7b69278e947c01c6160a31cf02c19d06d0f6e5ab1d768b15",
-            "611830d3641a68f94a690dcc25d1f4b0dac948325ac18f6dd32564371735f32c",
-            "1fbec814b18b1d4c3eaa7cec41007e04bf0a98453b06ec7582aa29882c52eb3e",
-            "ecd9c4a53ea15f18447b08fb96a13c5ab7dc7d24067b102fcbaaf7b39ca52e2d",
-            "e463bcb1a6e57288ffd4671503082fa8656e3eacb78fb1925f8a7c76400e8e15",
-            "7a19fb2d099a9557f7c10c2efbb8b101d9e0ec85610d5c74a887d1d4fb8d2827",
-            "4dbad51eb408af559dd91bbbed8dbeae0a2c89e0e05f0cce87c98652a8437f1f",
-            "befba86ae9e0c207865f7e24e8349d4ecdbc8b0f4632842499a0dfa60568e20a",
-            "b260b8a10343bf5a5dacb4f1d32d06c4fdddc9981a3619fbc0a5cd9eb30f3334",
-            "87a9779748888da5d96bbbce041b5109c6ffc0c4f30561c0170384a5922d9e21",
-        ];
-        let sks: Vec<_> = sks
+    fn test_collect_late_vote() {
+        let iteration = 1;
+        let vote = Vote::Valid([1u8; 32]);
+        let tip_header = Header::default();
+
+        let mut p = Provisioners::empty();
+        let mut msgs = vec![];
+        for sk in secret_keys() {
+            let pk = node_data::bls::PublicKey::new(BlsPublicKey::from(&sk));
+            p.add_member_with_value(pk.clone(), 1000 * DUSK);
+
+            let ru =
+                RoundUpdate::new(pk, sk, &tip_header, HashMap::new(), vec![]);
+            msgs.push(crate::build_validation_payload(vote, &ru, iteration));
+        }
+
+        let cfg = Config::raw(Seed::from([4u8; 48]), 1, 1, 10, vec![]);
+        let c = Committee::new(&p, &cfg);
+        let members: Vec<_> = msgs
             .iter()
-            .map(|hex| hex::decode(hex).expect("valid hex"))
-            .map(|data| {
-                BlsSecretKey::from_slice(&data[..]).expect("valid secret key")
-            })
+            .filter(|m| c.is_member(&m.sign_info().signer))
             .collect();
 
+        // Tally the step until the quorum is reached
+        let mut a = Aggregator::default();
+        let mut tallied = None;
+        let mut late_votes = vec![];
+        for msg in members {
+            if tallied.is_some() {
+                late_votes.push(msg);
+                continue;
+            }
+            let (sv, quorum_reached) =
+                a.collect_vote(&c, msg).expect("vote to be collected");
+            if quorum_reached {
+                tallied = Some(sv);
+            }
+        }
+        let tallied = tallied.expect("quorum to be reached");
+        let late = late_votes.first().expect("a member to vote late");
+
+        // A vote cast after the tally strengthens the step votes
+        let (sv, quorum_reached) =
+            a.collect_vote(&c, late).expect("late vote to be collected");
+        assert!(quorum_reached);
+        assert_eq!(sv.bitset & tallied.bitset, tallied.bitset);
+
+        let weight = |bitset| c.total_occurrences(&c.intersect(bitset));
+        assert!(weight(sv.bitset) > weight(tallied.bitset));
+    }
+
+    #[test]
+    fn test_collect_votes() {
+        let sks = secret_keys();
+
         let round = 1;
         let iteration = 1;
 
@@ -36,7 +36,10 @@ pub struct RoundUpdate {
     att_voters: Vec<Voter>,
     timestamp: u64,
 
-    pub base_timeouts: TimeoutSet,
+    base_timeouts: TimeoutSet,
+
+    /// Skip waiting for the next block slot before broadcasting a candidate
+    skip_slot_delay: bool,
 }
 
 impl RoundUpdate {
@@ -59,9 +62,21 @@ impl RoundUpdate {
             base_timeouts,
             att_voters,
             state_root: tip_header.state_hash,
+            skip_slot_delay: false,
         }
     }
 
+    /// Sets whether the wait for the next block slot is skipped in this
+    /// round
+    pub fn with_skip_slot_delay(mut self, skip_slot_delay: bool) -> Self {
+        self.skip_slot_delay = skip_slot_delay;
+        self
+    }
+
+    pub fn skip_slot_delay(&self) -> bool {
+        self.skip_slot_delay
+    }
+
     pub fn seed(&self) -> Seed {
         self.seed
     }
@@ -85,6 +100,11 @@ impl RoundUpdate {
     pub fn state_root(&self) -> [u8; 32] {
         self.state_root
     }
+
+    /// Returns the base timeouts of each step, as adjusted for this round
+    pub fn base_timeouts(&self) -> &TimeoutSet {
+        &self.base_timeouts
+    }
 }
 
 #[async_trait::async_trait]
@@ -98,3 +118,44 @@ pub trait Database: Send + Sync {
     async fn get_last_iter(&self) -> (Hash, u8);
     async fn store_last_iter(&mut self, data: (Hash, u8));
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_base_timeouts() {
+        let base_timeouts = TimeoutSet::from([
+            (StepName::Proposal, Duration::from_secs(7)),
+            (StepName::Validation, Duration::from_secs(5)),
+            (StepName::Ratification, Duration::from_secs(6)),
+        ]);
+
+        let ru = RoundUpdate::new(
+            PublicKey::default(),
+            BlsSecretKey::default(),
+            &Header::default(),
+            base_timeouts.clone(),
+            vec![],
+        );
+
+        assert_eq!(ru.base_timeouts(), &base_timeouts);
+        assert_eq!(
+            ru.base_timeouts().get(&StepName::Validation),
+            Some(&Duration::from_secs(5))
+        );
+    }
+
+    #[test]
+    fn test_skip_slot_delay() {
+        let ru = RoundUpdate::new(
+            PublicKey::default(),
+            BlsSecretKey::default(),
+            &Header::default(),
+            TimeoutSet::default(),
+            vec![],
+        );
+        assert!(!ru.skip_slot_delay());
+        assert!(ru.with_skip_slot_delay(true).skip_slot_delay());
+    }
+}
@@ -9,6 +9,9 @@ use std::sync::LazyLock;
 use std::time::Duration;
 
 use node_data::message::MESSAGE_MAX_FAILED_ITERATIONS;
+use node_data::StepName;
+
+use crate::errors::{ConfigError, ConsensusError};
 
 /// Maximum number of iterations Consensus runs per a single round.
 pub const CONSENSUS_MAX_ITER: u8 = 50;
@@ -24,6 +27,9 @@ pub const MAX_NUMBER_OF_FAULTS: usize = 100;
 
 pub const MAX_BLOCK_SIZE: usize = 1_024 * 1_024;
 
+/// Maximum number of seconds a block timestamp can be ahead of the local clock
+pub const MARGIN_TIMESTAMP: u64 = 3;
+
 /// Emergency mode is enabled after 16 iterations
 pub const EMERGENCY_MODE_ITERATION_THRESHOLD: u8 = 16;
 pub const EMERGENCY_BLOCK_ITERATION: u8 = u8::MAX;
@@ -47,8 +53,51 @@ pub const MIN_EMERGENCY_BLOCK_TIME: Duration =
 
 mod default {
     pub const MINIMUM_BLOCK_TIME: u64 = 10;
+    pub const MIN_BLOCK_INTERVAL: u64 = 0;
+    pub const MAX_CANDIDATES_PER_PROPOSER: usize = 2;
+    pub const MAX_CANDIDATES_PER_ROUND: usize = 100;
+}
+
+/// Policy applied to candidates whose malleable fields (transactions and
+/// faults) do not match the signed header.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum MalleableFieldPolicy {
+    /// Discard the message (default)
+    #[default]
+    Discard,
+    /// Log the error and still propagate the message, for analysis purposes.
+    /// The message is never collected nor voted.
+    Propagate,
+}
+
+impl MalleableFieldPolicy {
+    /// Returns true if a message that failed verification with `err` should
+    /// be propagated anyway
+    pub fn propagates(&self, err: &ConsensusError) -> bool {
+        *self == Self::Propagate && err.is_malleable_field()
+    }
 }
 
+impl std::str::FromStr for MalleableFieldPolicy {
+    type Err = ConfigError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "discard" => Ok(Self::Discard),
+            "propagate" => Ok(Self::Propagate),
+            _ => Err(ConfigError::UnknownMalleableFieldPolicy),
+        }
+    }
+}
+
+pub static MALLEABLE_FIELD_POLICY: LazyLock<MalleableFieldPolicy> =
+    LazyLock::new(|| {
+        env::var("RUSK_MALLEABLE_FIELD_POLICY")
+            .unwrap_or_default()
+            .parse()
+            .unwrap_or_default()
+    });
+
 pub static MINIMUM_BLOCK_TIME: LazyLock<u64> = LazyLock::new(|| {
     env::var("RUSK_MINIMUM_BLOCK_TIME")
         .unwrap_or_default()
@@ -56,12 +105,138 @@ pub static MINIMUM_BLOCK_TIME: LazyLock<u64> = LazyLock::new(|| {
         .unwrap_or(default::MINIMUM_BLOCK_TIME)
 });
 
+/// Minimum number of seconds, since the previous block timestamp, before this
+/// node produces a candidate. Unlike `MINIMUM_BLOCK_TIME`, this is a local
+/// production policy and not a validity rule. Disabled (0) by default.
+pub static MIN_BLOCK_INTERVAL: LazyLock<u64> = LazyLock::new(|| {
+    env::var("RUSK_MIN_BLOCK_INTERVAL")
+        .unwrap_or_default()
+        .parse()
+        .unwrap_or(default::MIN_BLOCK_INTERVAL)
+});
+
+/// Maximum number of distinct candidates stored per proposer in a single
+/// iteration. Any further candidate signed by the same proposer is dropped.
+pub static MAX_CANDIDATES_PER_PROPOSER: LazyLock<usize> = LazyLock::new(|| {
+    env::var("RUSK_MAX_CANDIDATES_PER_PROPOSER")
+        .unwrap_or_default()
+        .parse()
+        .unwrap_or(default::MAX_CANDIDATES_PER_PROPOSER)
+});
+
+/// Maximum number of distinct candidates stored in a single round, across all
+/// proposers. Any further candidate is dropped until the next round.
+pub static MAX_CANDIDATES_PER_ROUND: LazyLock<usize> = LazyLock::new(|| {
+    env::var("RUSK_MAX_CANDIDATES_PER_ROUND")
+        .unwrap_or_default()
+        .parse()
+        .unwrap_or(default::MAX_CANDIDATES_PER_ROUND)
+});
+
 /// Maximum allowable round difference for message signature verification and
 /// for determining if a consensus message is close enough to the network tip
 /// for enqueuing.
 /// Controls the range of rounds considered relevant to current operations.
 pub const MAX_ROUND_DISTANCE: u64 = 10;
 
+/// Snapshot of the consensus parameters in use.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConsensusConfig {
+    pub max_iterations: u8,
+    pub proposal_committee_credits: usize,
+    pub validation_committee_credits: usize,
+    pub ratification_committee_credits: usize,
+    pub min_step_timeout: Duration,
+    pub max_step_timeout: Duration,
+    pub timeout_increase: Duration,
+    pub emergency_iteration_threshold: u8,
+    pub minimum_block_time: u64,
+    pub min_block_interval: u64,
+    pub max_candidates_per_proposer: usize,
+    pub max_candidates_per_round: usize,
+}
+
+impl Default for ConsensusConfig {
+    fn default() -> Self {
+        Self::current()
+    }
+}
+
+impl ConsensusConfig {
+    /// Returns the consensus parameters currently in use, including the ones
+    /// set through environment variables.
+    pub fn current() -> Self {
+        Self {
+            max_iterations: CONSENSUS_MAX_ITER,
+            proposal_committee_credits: PROPOSAL_COMMITTEE_CREDITS,
+            validation_committee_credits: VALIDATION_COMMITTEE_CREDITS,
+            ratification_committee_credits: RATIFICATION_COMMITTEE_CREDITS,
+            min_step_timeout: MIN_STEP_TIMEOUT,
+            max_step_timeout: MAX_STEP_TIMEOUT,
+            timeout_increase: TIMEOUT_INCREASE,
+            emergency_iteration_threshold: EMERGENCY_MODE_ITERATION_THRESHOLD,
+            minimum_block_time: *MINIMUM_BLOCK_TIME,
+            min_block_interval: *MIN_BLOCK_INTERVAL,
+            max_candidates_per_proposer: *MAX_CANDIDATES_PER_PROPOSER,
+            max_candidates_per_round: *MAX_CANDIDATES_PER_ROUND,
+        }
+    }
+
+    /// Checks that the parameters are consistent with each other.
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        let committees = [
+            (StepName::Proposal, self.proposal_committee_credits),
+            (StepName::Validation, self.validation_committee_credits),
+            (StepName::Ratification, self.ratification_committee_credits),
+        ];
+        for (step, credits) in committees {
+            if credits == 0 {
+                return Err(ConfigError::EmptyCommittee(step));
+            }
+        }
+
+        if self.min_step_timeout.is_zero() {
+            return Err(ConfigError::ZeroStepTimeout);
+        }
+        if self.min_step_timeout >= self.max_step_timeout {
+            return Err(ConfigError::InvalidTimeoutRange(
+                self.min_step_timeout,
+                self.max_step_timeout,
+            ));
+        }
+
+        // The candidate must still be produced within the Proposal step
+        let min_block_interval = Duration::from_secs(self.min_block_interval);
+        if min_block_interval >= self.min_step_timeout {
+            return Err(ConfigError::InvalidBlockInterval(
+                min_block_interval,
+                self.min_step_timeout,
+            ));
+        }
+
+        if self.emergency_iteration_threshold >= self.max_iterations {
+            return Err(ConfigError::InvalidEmergencyThreshold(
+                self.emergency_iteration_threshold,
+                self.max_iterations,
+            ));
+        }
+
+        if self.max_candidates_per_proposer == 0 {
+            return Err(ConfigError::ZeroCandidatesPerProposer);
+        }
+
+        // A round must be able to store a candidate for each iteration
+        if self.max_candidates_per_round < self.max_iterations as usize {
+            return Err(ConfigError::InsufficientCandidatesPerRound(
+                self.max_candidates_per_round,
+                self.max_iterations,
+            ));
+        }
+
+        Ok(())
+    }
+}
+
 // Returns `floor(value/2) + 1`
 pub fn majority(value: usize) -> usize {
     value / 2 + 1
@@ -93,6 +268,12 @@ pub fn ratification_extra() -> usize {
     RATIFICATION_COMMITTEE_CREDITS - ratification_quorum()
 }
 
+/// Returns true if `timestamp` is ahead of the local clock (`now`) by more
+/// than [MARGIN_TIMESTAMP]
+pub fn is_ahead_of_time(timestamp: u64, now: u64) -> bool {
+    timestamp > now.saturating_add(MARGIN_TIMESTAMP)
+}
+
 /// Returns whether the current iteration is an emergency iteration
 pub fn is_emergency_iter(iter: u8) -> bool {
     iter >= EMERGENCY_MODE_ITERATION_THRESHOLD
@@ -129,4 +310,82 @@ mod tests {
         assert_eq!(validation_extra(), 21);
         assert_eq!(ratification_extra(), 21);
     }
+
+    #[test]
+    fn test_validate_config() {
+        let valid = ConsensusConfig::current();
+        assert_eq!(valid.validate(), Ok(()));
+
+        let config = ConsensusConfig {
+            validation_committee_credits: 0,
+            ..valid.clone()
+        };
+        assert_eq!(
+            config.validate(),
+            Err(ConfigError::EmptyCommittee(StepName::Validation))
+        );
+
+        let config = ConsensusConfig {
+            min_step_timeout: Duration::from_secs(50),
+            ..valid.clone()
+        };
+        assert_eq!(
+            config.validate(),
+            Err(ConfigError::InvalidTimeoutRange(
+                Duration::from_secs(50),
+                MAX_STEP_TIMEOUT
+            ))
+        );
+
+        let config = ConsensusConfig {
+            min_step_timeout: Duration::ZERO,
+            ..valid.clone()
+        };
+        assert_eq!(config.validate(), Err(ConfigError::ZeroStepTimeout));
+
+        let config = ConsensusConfig {
+            min_block_interval: MIN_STEP_TIMEOUT.as_secs(),
+            ..valid.clone()
+        };
+        assert_eq!(
+            config.validate(),
+            Err(ConfigError::InvalidBlockInterval(
+                MIN_STEP_TIMEOUT,
+                MIN_STEP_TIMEOUT
+            ))
+        );
+
+        let config = ConsensusConfig {
+            emergency_iteration_threshold: CONSENSUS_MAX_ITER,
+            ..valid.clone()
+        };
+        assert_eq!(
+            config.validate(),
+            Err(ConfigError::InvalidEmergencyThreshold(
+                CONSENSUS_MAX_ITER,
+                CONSENSUS_MAX_ITER
+            ))
+        );
+
+        let config = ConsensusConfig {
+            max_candidates_per_proposer: 0,
+            ..valid.clone()
+        };
+        assert_eq!(
+            config.validate(),
+            Err(ConfigError::ZeroCandidatesPerProposer)
+        );
+
+        let config = ConsensusConfig {
+            max_candidates_per_round: 1,
+            ..valid
+        };
+        assert_eq!(
+            config.validate(),
+            Err(ConfigError::InsufficientCandidatesPerRound(
+                1,
+                CONSENSUS_MAX_ITER
+            ))
+        );
+    }
 }
@@ -14,11 +14,13 @@ use tracing::{debug, error, warn, Instrument};
 
 use crate::commons::{Database, RoundUpdate};
 use crate::config::{CONSENSUS_MAX_ITER, EMERGENCY_MODE_ITERATION_THRESHOLD};
+use crate::emergency::EmergencyMonitor;
 use crate::errors::ConsensusError;
 use crate::execution_ctx::ExecutionCtx;
 use crate::iteration_ctx::IterationCtx;
 use crate::operations::Operations;
 use crate::phase::Phase;
+use crate::progress::ProgressNotifier;
 use crate::queue::MsgRegistry;
 use crate::step_votes_reg::AttInfoRegistry;
 use crate::user::provisioners::Provisioners;
@@ -40,6 +42,9 @@ pub struct Consensus<T: Operations, D: Database> {
 
     // Database
     db: Arc<Mutex<D>>,
+
+    /// Publishes the progress of the consensus loop
+    progress: ProgressNotifier,
 }
 
 impl<T: Operations + 'static, D: Database + 'static> Consensus<T, D> {
@@ -65,9 +70,16 @@ impl<T: Operations + 'static, D: Database + 'static> Consensus<T, D> {
             future_msgs,
             executor,
             db,
+            progress: ProgressNotifier::default(),
         }
     }
 
+    /// Sets the notifier the progress of the consensus loop is published to.
+    pub fn with_progress(mut self, progress: ProgressNotifier) -> Self {
+        self.progress = progress;
+        self
+    }
+
     /// Spins the consensus state machine. The consensus runs for the whole
     /// round until either a new round is produced or the node needs to re-sync.
     ///
@@ -127,6 +139,7 @@ impl<T: Operations + 'static, D: Database + 'static> Consensus<T, D> {
         let future_msgs = self.future_msgs.clone();
         let executor = self.executor.clone();
         let db = self.db.clone();
+        let progress = self.progress.clone();
 
         tokio::spawn(async move {
             if ru.round > 0 {
@@ -136,9 +149,11 @@ impl<T: Operations + 'static, D: Database + 'static> Consensus<T, D> {
             let sv_registry =
                 Arc::new(Mutex::new(AttInfoRegistry::new(ru.clone())));
 
-            let proposal_handler = Arc::new(Mutex::new(
-                proposal::handler::ProposalHandler::new(db.clone()),
-            ));
+            let proposal_handler =
+                Arc::new(Mutex::new(proposal::handler::ProposalHandler::new(
+                    db.clone(),
+                    provisioners.clone(),
+                )));
 
             let validation_handler = Arc::new(Mutex::new(
                 validation::handler::ValidationHandler::new(
@@ -178,7 +193,7 @@ impl<T: Operations + 'static, D: Database + 'static> Consensus<T, D> {
                 validation_handler,
                 ratification_handler,
                 proposal_handler,
-                ru.base_timeouts.clone(),
+                ru.base_timeouts().clone(),
             );
 
             let (prev_block_hash, saved_iter) =
@@ -203,11 +218,14 @@ impl<T: Operations + 'static, D: Database + 'static> Consensus<T, D> {
                 debug!(event = "restored iteration", ru.round, iter);
             }
 
+            let mut emergency = EmergencyMonitor::new(ru.round);
+
             // Round execution loop
             loop {
                 db.lock().await.store_last_iter((ru.hash(), iter)).await;
 
                 iter_ctx.on_begin(iter);
+                emergency.on_iteration(iter);
 
                 iter_ctx.generate_iteration_committees(
                     iter,
@@ -219,6 +237,7 @@ impl<T: Operations + 'static, D: Database + 'static> Consensus<T, D> {
                 // Execute iteration steps
                 for phase in phases.iter_mut() {
                     let step_name = phase.to_step_name();
+                    progress.notify(ru.round, iter, step_name);
                     // Initialize new phase with message returned by previous
                     // phase.
                     phase.reinitialize(msg, ru.round, iter).await;
@@ -0,0 +1,82 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use metrics::counter;
+use tracing::{info, warn};
+
+use crate::config::is_emergency_iter;
+
+/// Signals the transitions of a round into and out of Emergency Mode.
+///
+/// The round enters Emergency Mode at the first iteration crossing the
+/// threshold, and exits it when its execution ends, i.e. when the monitor is
+/// dropped.
+pub(crate) struct EmergencyMonitor {
+    round: u64,
+    /// Iteration at which the round entered Emergency Mode
+    entered_at: Option<u8>,
+}
+
+impl EmergencyMonitor {
+    pub fn new(round: u64) -> Self {
+        Self {
+            round,
+            entered_at: None,
+        }
+    }
+
+    /// Returns true if the round enters Emergency Mode at `iter`.
+    pub fn on_iteration(&mut self, iter: u8) -> bool {
+        if self.entered_at.is_some() || !is_emergency_iter(iter) {
+            return false;
+        }
+
+        self.entered_at = Some(iter);
+        counter!("dusk_emergency_entered").increment(1);
+        warn!(event = "emergency mode entered", round = self.round, iter);
+        true
+    }
+}
+
+impl Drop for EmergencyMonitor {
+    fn drop(&mut self) {
+        if let Some(entered_at) = self.entered_at {
+            counter!("dusk_emergency_exited").increment(1);
+            info!(
+                event = "emergency mode exited",
+                round = self.round,
+                entered_at,
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{
+        CONSENSUS_MAX_ITER, EMERGENCY_MODE_ITERATION_THRESHOLD,
+    };
+
+    #[test]
+    fn test_emergency_entered_once() {
+        for round in 1..=2 {
+            let mut monitor = EmergencyMonitor::new(round);
+
+            let entered: Vec<_> = (0..CONSENSUS_MAX_ITER)
+                .filter(|iter| monitor.on_iteration(*iter))
+                .collect();
+            assert_eq!(entered, vec![EMERGENCY_MODE_ITERATION_THRESHOLD]);
+
+            // Repeating an emergency iteration does not fire again
+            assert!(!monitor.on_iteration(CONSENSUS_MAX_ITER - 1));
+        }
+
+        // A round restored past the threshold enters Emergency Mode at once
+        let mut monitor = EmergencyMonitor::new(3);
+        assert!(monitor.on_iteration(EMERGENCY_MODE_ITERATION_THRESHOLD + 1));
+    }
+}
@@ -5,6 +5,7 @@
 // Copyright (c) DUSK NETWORK. All rights reserved.
 
 use std::io;
+use std::time::Duration;
 
 use dusk_core::signatures::bls::Error as BlsSigError;
 use node_data::bls::PublicKeyBytes;
@@ -21,6 +22,8 @@ pub enum StepSigError {
     VerificationFailed(BlsSigError),
     #[error("Invalid Type")]
     InvalidType,
+    #[error("Signer {0} out of range, committee size: {1}")]
+    SignerOutOfRange(u32, usize),
 }
 
 impl From<BlsSigError> for StepSigError {
@@ -34,6 +37,10 @@ pub enum ConsensusError {
     InvalidBlock,
     InvalidBlockHash,
     InvalidBlockSize(usize),
+    InvalidTxRoot,
+    InvalidFaultRoot,
+    InvalidTimestamp(u64),
+    InvalidFault(PublicKeyBytes),
     InvalidSignature(BlsSigError),
     InvalidMsgType,
     InvalidValidationStepVotes(StepSigError),
@@ -54,6 +61,16 @@ pub enum ConsensusError {
     TooManyTransactions(usize),
     TooManyFaults(usize),
     UnknownBlockSize,
+    CandidateRateLimited,
+}
+
+impl ConsensusError {
+    /// Returns true if the error relates to a field not covered by the
+    /// candidate signature (e.g. the transactions or the faults), which an
+    /// adversary could have tampered with while relaying the message.
+    pub fn is_malleable_field(&self) -> bool {
+        matches!(self, Self::InvalidTxRoot | Self::InvalidFaultRoot)
+    }
 }
 
 impl From<StepSigError> for ConsensusError {
@@ -217,3 +234,25 @@ impl From<InvalidFault> for OperationError {
         Self::InvalidFaults(value)
     }
 }
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+pub enum ConfigError {
+    #[error("{0:?} committee has no credits")]
+    EmptyCommittee(StepName),
+    #[error("min step timeout {0:?} is not lower than max step timeout {1:?}")]
+    InvalidTimeoutRange(Duration, Duration),
+    #[error("min step timeout cannot be zero")]
+    ZeroStepTimeout,
+    #[error(
+        "min block interval {0:?} is not lower than min step timeout {1:?}"
+    )]
+    InvalidBlockInterval(Duration, Duration),
+    #[error("emergency threshold {0} is not lower than max iterations {1}")]
+    InvalidEmergencyThreshold(u8, u8),
+    #[error("max candidates per proposer cannot be zero")]
+    ZeroCandidatesPerProposer,
+    #[error("max candidates per round {0} is lower than max iterations {1}")]
+    InsufficientCandidatesPerRound(usize, u8),
+    #[error("unknown malleable field policy")]
+    UnknownMalleableFieldPolicy,
+}
@@ -21,7 +21,8 @@ use tracing::{debug, error, info, trace, warn};
 
 use crate::commons::{Database, RoundUpdate};
 use crate::config::{
-    is_emergency_iter, CONSENSUS_MAX_ITER, MAX_ROUND_DISTANCE,
+    is_emergency_iter, CONSENSUS_MAX_ITER, MALLEABLE_FIELD_POLICY,
+    MAX_ROUND_DISTANCE,
 };
 use crate::errors::ConsensusError;
 use crate::iteration_ctx::IterationCtx;
@@ -436,7 +437,8 @@ impl<'a, T: Operations + 'static, DB: Database> ExecutionCtx<'a, T, DB> {
     /// Process messages from past
     ///
     /// Ignores messages that do not originate from emergency iteration of
-    /// current round
+    /// current round, except late votes strengthening a quorum already
+    /// reached
     async fn handle_past_msg(&mut self, msg: Message) {
         // Discard past-round messages
         if msg.header.round != self.round_update.round {
@@ -457,6 +459,17 @@ impl<'a, T: Operations + 'static, DB: Database> ExecutionCtx<'a, T, DB> {
 
         // Past-iteration messages are only handled in emergency mode
         if !is_emergency_iter(msg_iteration) {
+            // Late votes only strengthen the attestation of a quorum already
+            // reached. No quorum is created nor broadcast for a past iteration
+            if self
+                .iter_ctx
+                .collect_late_vote(msg.clone(), &self.sv_registry)
+                .await
+            {
+                log_msg("collected late vote", "handle_past_msg", &msg);
+                return;
+            }
+
             log_msg(
                 "discarded message (past iter in normal mode)",
                 "handle_past_msg",
@@ -621,6 +634,10 @@ impl<'a, T: Operations + 'static, DB: Database> ExecutionCtx<'a, T, DB> {
             // verification.
             Err(e) => {
                 error!("phase handler err: {:?}", e);
+                if MALLEABLE_FIELD_POLICY.propagates(&e) {
+                    log_msg("send invalid message", "malleable_field", &msg);
+                    self.outbound.try_send(msg);
+                }
                 return None;
             }
         }
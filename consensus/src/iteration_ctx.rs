@@ -12,7 +12,7 @@ use std::time::Duration;
 
 use node_data::bls::PublicKeyBytes;
 use node_data::ledger::Seed;
-use node_data::message::{Message, Topics};
+use node_data::message::{Message, Payload, Topics};
 use node_data::StepName;
 use tokio::sync::Mutex;
 use tokio::task::JoinSet;
@@ -23,6 +23,7 @@ use crate::config::{
     exclude_next_generator, MAX_STEP_TIMEOUT, TIMEOUT_INCREASE,
 };
 use crate::msg_handler::{MsgHandler, StepOutcome};
+use crate::step_votes_reg::SafeAttestationInfoRegistry;
 use crate::user::committee::Committee;
 use crate::user::provisioners::Provisioners;
 use crate::user::sortition;
@@ -269,6 +270,35 @@ impl<DB: Database> IterationCtx<DB> {
             .map(|p| *p.bytes())
     }
 
+    /// Collects a late vote of a past iteration, only if the quorum of its
+    /// vote has already been reached, to strengthen the stored attestation.
+    ///
+    /// Returns false if the vote is discarded. No new quorum is ever created
+    /// for a past iteration, thus the resulting quorum is not returned.
+    pub(crate) async fn collect_late_vote(
+        &self,
+        msg: Message,
+        sv_registry: &SafeAttestationInfoRegistry,
+    ) -> bool {
+        let vote = match &msg.payload {
+            Payload::Validation(v) => v.vote,
+            Payload::Ratification(r) => r.vote,
+            _ => return false,
+        };
+
+        let attested = sv_registry
+            .lock()
+            .await
+            .attestation(msg.header.iteration, &vote)
+            .is_some();
+        if !attested {
+            return false;
+        }
+
+        let _ = self.process_past_msg(msg).await;
+        true
+    }
+
     /// Collects a message from a past iteration
     pub(crate) async fn process_past_msg(
         &self,
@@ -318,3 +348,144 @@ impl<DB: Database> Drop for IterationCtx<DB> {
         self.on_close();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use dusk_core::signatures::bls::PublicKey as BlsPublicKey;
+    use node_data::ledger::{Block, Header};
+    use node_data::message::payload::{QuorumType, ValidationResult, Vote};
+    use node_data::message::ConsensusHeader;
+
+    use super::*;
+    use crate::commons::RoundUpdate;
+    use crate::step_votes_reg::AttInfoRegistry;
+    use crate::tests::secret_keys;
+    use crate::user::provisioners::DUSK;
+    use crate::{build_ratification_payload, build_validation_payload};
+
+    struct DummyDb;
+
+    #[async_trait::async_trait]
+    impl Database for DummyDb {
+        async fn store_candidate_block(&mut self, _b: Block) {}
+        async fn store_validation_result(
+            &mut self,
+            _ch: &ConsensusHeader,
+            _vr: &ValidationResult,
+        ) {
+        }
+        async fn get_last_iter(&self) -> (node_data::ledger::Hash, u8) {
+            Default::default()
+        }
+        async fn store_last_iter(
+            &mut self,
+            _data: (node_data::ledger::Hash, u8),
+        ) {
+        }
+    }
+
+    #[tokio::test]
+    async fn test_late_votes_quorum() {
+        let tip_header = Header::default();
+        let iteration = 0;
+        let vote = Vote::Valid([1u8; 32]);
+
+        let mut provisioners = Provisioners::empty();
+        let mut round_updates = vec![];
+        for sk in secret_keys() {
+            let pk = node_data::bls::PublicKey::new(BlsPublicKey::from(&sk));
+            provisioners.add_member_with_value(pk.clone(), 1000 * DUSK);
+            round_updates.push(RoundUpdate::new(
+                pk,
+                sk,
+                &tip_header,
+                HashMap::new(),
+                vec![],
+            ));
+        }
+        let ru = round_updates[0].clone();
+
+        let db = Arc::new(Mutex::new(DummyDb));
+        let sv_registry =
+            Arc::new(Mutex::new(AttInfoRegistry::new(ru.clone())));
+        let mut iter_ctx = IterationCtx::new(
+            ru.round,
+            iteration,
+            Arc::new(Mutex::new(validation::handler::ValidationHandler::new(
+                sv_registry.clone(),
+                db.clone(),
+            ))),
+            Arc::new(Mutex::new(
+                ratification::handler::RatificationHandler::new(
+                    sv_registry.clone(),
+                ),
+            )),
+            Arc::new(Mutex::new(proposal::handler::ProposalHandler::new(
+                db,
+                Arc::new(provisioners.clone()),
+            ))),
+            TimeoutSet::new(),
+        );
+        for step in [
+            StepName::Proposal,
+            StepName::Validation,
+            StepName::Ratification,
+        ] {
+            let cfg = sortition::Config::new(
+                tip_header.seed,
+                ru.round,
+                iteration,
+                step,
+                vec![],
+            );
+            iter_ctx.committees.insert(
+                step.to_step(iteration),
+                Committee::new(&provisioners, &cfg),
+            );
+        }
+
+        let result =
+            ValidationResult::new(Default::default(), vote, QuorumType::Valid);
+        let attested = |registry: &AttInfoRegistry| {
+            registry
+                .attestation(iteration, &vote)
+                .map(|att| att.ratification.bitset.count_ones())
+        };
+
+        // Late votes of an iteration without quorum are discarded
+        for ru in &round_updates {
+            let msg = build_validation_payload(vote, ru, iteration).into();
+            assert!(!iter_ctx.collect_late_vote(msg, &sv_registry).await);
+            let msg = build_ratification_payload(ru, iteration, &result);
+            assert!(
+                !iter_ctx.collect_late_vote(msg.into(), &sv_registry).await
+            );
+        }
+        assert_eq!(attested(&*sv_registry.lock().await), None);
+
+        // The iteration reaches its quorum
+        for ru in &round_updates {
+            let msg = build_validation_payload(vote, ru, iteration).into();
+            iter_ctx.process_past_msg(msg).await;
+        }
+        let mut late_votes = round_updates.iter();
+        for ru in late_votes.by_ref() {
+            let msg = build_ratification_payload(ru, iteration, &result);
+            if iter_ctx.process_past_msg(msg.into()).await.is_some() {
+                break;
+            }
+        }
+        let weight = attested(&*sv_registry.lock().await)
+            .expect("a quorum to be reached");
+
+        // Late votes only strengthen the stored attestation
+        let late_votes: Vec<_> = late_votes.collect();
+        assert!(!late_votes.is_empty());
+        for ru in late_votes {
+            let msg = build_ratification_payload(ru, iteration, &result);
+            assert!(iter_ctx.collect_late_vote(msg.into(), &sv_registry).await);
+        }
+        let late_weight = attested(&*sv_registry.lock().await);
+        assert!(late_weight > Some(weight));
+    }
+}
@@ -15,10 +15,12 @@ pub mod user;
 
 mod aggregator;
 pub mod config;
+mod emergency;
 mod execution_ctx;
 mod msg_handler;
 pub mod operations;
 mod phase;
+pub mod progress;
 mod proposal;
 pub mod queue;
 pub mod quorum;
@@ -37,5 +39,30 @@ mod tests {
     // Adding benchmark dependencies here to satisfy `unused_crate_dependencies`
     // lint
     use criterion as _;
+    use dusk_bytes::DeserializableSlice;
+    use dusk_core::signatures::bls::SecretKey as BlsSecretKey;
     use rand as _;
+
+    const SKS: [&str; 10] = [
+        "7f6f2ccdb23f2abb7b69278e947c01c6160a31cf02c19d06d0f6e5ab1d768b15",
+        "611830d3641a68f94a690dcc25d1f4b0dac948325ac18f6dd32564371735f32c",
+        "1fbec814b18b1d4c3eaa7cec41007e04bf0a98453b06ec7582aa29882c52eb3e",
+        "ecd9c4a53ea15f18447b08fb96a13c5ab7dc7d24067b102fcbaaf7b39ca52e2d",
+        "e463bcb1a6e57288ffd4671503082fa8656e3eacb78fb1925f8a7c76400e8e15",
+        "7a19fb2d099a9557f7c10c2efbb8b101d9e0ec85610d5c74a887d1d4fb8d2827",
+        "4dbad51eb408af559dd91bbbed8dbeae0a2c89e0e05f0cce87c98652a8437f1f",
+        "befba86ae9e0c207865f7e24e8349d4ecdbc8b0f4632842499a0dfa60568e20a",
+        "b260b8a10343bf5a5dacb4f1d32d06c4fdddc9981a3619fbc0a5cd9eb30f3334",
+        "87a9779748888da5d96bbbce041b5109c6ffc0c4f30561c0170384a5922d9e21",
+    ];
+
+    /// Returns the secret keys of the provisioners shared by the tests
+    pub(crate) fn secret_keys() -> Vec<BlsSecretKey> {
+        SKS.iter()
+            .map(|hex| hex::decode(hex).expect("valid hex"))
+            .map(|data| {
+                BlsSecretKey::from_slice(&data[..]).expect("valid secret key")
+            })
+            .collect()
+    }
 }
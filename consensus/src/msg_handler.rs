@@ -75,7 +75,7 @@ pub trait MsgHandler {
                 // Delegate message final verification to the phase instance.
                 // It is the phase that knows what message type to expect and if
                 // it is valid or not.
-                self.verify(msg, round_committees)
+                self.verify(msg, ru, round_committees)
             }
             Status::Future => {
                 Self::verify_message(
@@ -132,6 +132,7 @@ pub trait MsgHandler {
                     node_data::message::Payload::Candidate(c) => {
                         proposal::handler::verify_stateless(
                             c,
+                            ru,
                             round_committees,
                         )?;
                     }
@@ -154,6 +155,7 @@ pub trait MsgHandler {
     fn verify(
         &self,
         msg: &Message,
+        ru: &RoundUpdate,
         round_committees: &RoundCommittees,
     ) -> Result<(), ConsensusError>;
 
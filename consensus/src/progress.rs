@@ -0,0 +1,107 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use std::sync::Arc;
+
+use node_data::StepName;
+use tokio::sync::watch;
+
+/// Position of the consensus loop
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ConsensusProgress {
+    pub round: u64,
+    pub iteration: u8,
+    pub step: StepName,
+}
+
+impl Default for ConsensusProgress {
+    fn default() -> Self {
+        Self {
+            round: 0,
+            iteration: 0,
+            step: StepName::Proposal,
+        }
+    }
+}
+
+/// Publishes the progress of the consensus loop to its watchers.
+///
+/// Watchers only observe the latest progress: updates are coalesced rather
+/// than buffered, so that a slow watcher cannot make them pile up.
+#[derive(Clone)]
+pub struct ProgressNotifier(Arc<watch::Sender<ConsensusProgress>>);
+
+impl Default for ProgressNotifier {
+    fn default() -> Self {
+        let (sender, _) = watch::channel(ConsensusProgress::default());
+        Self(Arc::new(sender))
+    }
+}
+
+impl ProgressNotifier {
+    /// Subscribes to the progress updates, starting from the current one.
+    pub fn subscribe(&self) -> watch::Receiver<ConsensusProgress> {
+        self.0.subscribe()
+    }
+
+    /// Returns the current progress.
+    pub fn current(&self) -> ConsensusProgress {
+        *self.0.borrow()
+    }
+
+    pub(crate) fn notify(&self, round: u64, iteration: u8, step: StepName) {
+        self.0.send_replace(ConsensusProgress {
+            round,
+            iteration,
+            step,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const STEPS: [StepName; 3] = [
+        StepName::Proposal,
+        StepName::Validation,
+        StepName::Ratification,
+    ];
+
+    #[tokio::test]
+    async fn test_progress_updates() {
+        let notifier = ProgressNotifier::default();
+        let mut watcher = notifier.subscribe();
+
+        // A watcher keeping up receives every phase of the loop
+        for iteration in 0..2 {
+            for step in STEPS {
+                notifier.notify(10, iteration, step);
+
+                watcher.changed().await.expect("notifier to be alive");
+                let progress = *watcher.borrow_and_update();
+                assert_eq!(progress.iteration, iteration);
+                assert_eq!(progress.step, step);
+            }
+        }
+
+        // A slow watcher only receives the latest update
+        for step in STEPS {
+            notifier.notify(11, 0, step);
+        }
+        watcher.changed().await.expect("notifier to be alive");
+        assert_eq!(
+            *watcher.borrow_and_update(),
+            ConsensusProgress {
+                round: 11,
+                iteration: 0,
+                step: StepName::Ratification,
+            }
+        );
+        assert!(!watcher.has_changed().unwrap());
+        assert_eq!(notifier.current().round, 11);
+    }
+}
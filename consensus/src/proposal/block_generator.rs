@@ -9,7 +9,10 @@ use std::sync::Arc;
 use std::time::Instant;
 
 use dusk_bytes::Serializable;
-use node_data::ledger::{to_str, Block, Fault, IterationsInfo, Seed, Slash};
+use metrics::histogram;
+use node_data::ledger::{
+    to_str, Block, Fault, IterationsInfo, Seed, SizeBreakdown, Slash,
+};
 use node_data::message::payload::Candidate;
 use node_data::message::{Message, SignedStepMessage, BLOCK_HEADER_VERSION};
 use node_data::{get_current_timestamp, ledger};
@@ -20,6 +23,13 @@ use crate::config::{MAX_BLOCK_SIZE, MAX_NUMBER_OF_FAULTS, MINIMUM_BLOCK_TIME};
 use crate::merkle::merkle_root;
 use crate::operations::{CallParams, Operations};
 
+/// A block generated by the [Generator], along with its serialized size
+#[derive(Debug)]
+pub struct GeneratedBlock {
+    pub block: Block,
+    pub size: SizeBreakdown,
+}
+
 pub struct Generator<T: Operations> {
     executor: Arc<T>,
 }
@@ -37,7 +47,8 @@ impl<T: Operations> Generator<T> {
     ) -> Result<Message, crate::errors::OperationError> {
         let candidate = self
             .generate_block(ru, iteration, failed_iterations, &[])
-            .await?;
+            .await?
+            .block;
 
         let mut candidate_msg = Candidate { candidate };
 
@@ -54,7 +65,7 @@ impl<T: Operations> Generator<T> {
         iteration: u8,
         failed_iterations: IterationsInfo,
         faults: &[Fault],
-    ) -> Result<Block, crate::errors::OperationError> {
+    ) -> Result<GeneratedBlock, crate::errors::OperationError> {
         let start = Instant::now();
 
         // Sign seed
@@ -138,6 +149,25 @@ impl<T: Operations> Generator<T> {
 
         match Block::new(blk_header, txs, faults.to_vec()) {
             Ok(blk) => {
+                let size = blk.size_breakdown().map_err(|e| {
+                    crate::errors::OperationError::InvalidEST(anyhow::anyhow!(
+                        "Cannot get block size {e}. This should be a bug"
+                    ))
+                })?;
+                histogram!("dusk_block_size_header").record(size.header as f64);
+                histogram!("dusk_block_size_txs").record(size.txs as f64);
+                histogram!("dusk_block_size_faults").record(size.faults as f64);
+                histogram!("dusk_block_size_total").record(size.total as f64);
+
+                debug!(
+                    event = "Block size",
+                    header = size.header,
+                    txs = size.txs,
+                    faults = size.faults,
+                    total = size.total,
+                    max = MAX_BLOCK_SIZE,
+                );
+
                 info!(
                     event = "Block generated",
                     round = blk.header().height,
@@ -148,7 +178,7 @@ impl<T: Operations> Generator<T> {
                     state_hash = &to_str(&blk.header().state_hash),
                     dur = format!("{:?}ms", start.elapsed().as_millis()),
                 );
-                Ok(blk)
+                Ok(GeneratedBlock { block: blk, size })
             }
             Err(e) => Err(crate::errors::OperationError::InvalidEST(
                 anyhow::anyhow!("Cannot create new block {e}",),
@@ -156,3 +186,112 @@ impl<T: Operations> Generator<T> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::time::Duration;
+
+    use dusk_core::signatures::bls::PublicKey as BlsPublicKey;
+    use node_data::bls::{PublicKey, PublicKeyBytes};
+    use node_data::ledger::{Header, SpentTransaction};
+    use node_data::StepName;
+
+    use super::*;
+    use crate::errors::{HeaderError, OperationError, VstError};
+    use crate::operations::{Output, StateRoot, VerificationOutput, Voter};
+    use crate::tests::secret_keys;
+
+    /// Executor including a fixed number of transactions in any block
+    struct DummyExecutor(usize);
+
+    #[async_trait::async_trait]
+    impl Operations for DummyExecutor {
+        async fn verify_candidate_header(
+            &self,
+            _: &Header,
+            _: &PublicKeyBytes,
+        ) -> Result<(u8, Vec<Voter>, Vec<Voter>), HeaderError> {
+            Ok((0, vec![], vec![]))
+        }
+
+        async fn verify_faults(
+            &self,
+            _: u64,
+            _: &[Fault],
+        ) -> Result<(), OperationError> {
+            Ok(())
+        }
+
+        async fn verify_state_transition(
+            &self,
+            _: StateRoot,
+            _: &Block,
+            _: &[Voter],
+        ) -> Result<VerificationOutput, VstError> {
+            Ok(VerificationOutput::default())
+        }
+
+        async fn execute_state_transition(
+            &self,
+            params: CallParams,
+        ) -> Result<Output, OperationError> {
+            let txs = (0..self.0 as u64)
+                .map(|i| SpentTransaction {
+                    inner: ledger::faker::gen_dummy_tx(i),
+                    block_height: params.round,
+                    gas_spent: 0,
+                    err: None,
+                })
+                .collect();
+            Ok(Output {
+                txs,
+                ..Default::default()
+            })
+        }
+
+        async fn add_step_elapsed_time(
+            &self,
+            _: u64,
+            _: StepName,
+            _: Duration,
+        ) -> Result<(), OperationError> {
+            Ok(())
+        }
+
+        async fn get_block_gas_limit(&self) -> u64 {
+            0
+        }
+    }
+
+    #[tokio::test]
+    async fn test_generated_block_size() {
+        let sk = secret_keys().remove(0);
+        let pk = PublicKey::new(BlsPublicKey::from(&sk));
+        let tip = Header::default();
+        let ru = RoundUpdate::new(pk, sk, &tip, HashMap::new(), vec![]);
+
+        let mut sizes = vec![];
+        for txs in [0, 3] {
+            let generator = Generator::new(Arc::new(DummyExecutor(txs)));
+            let generated = generator
+                .generate_block(&ru, 0, Default::default(), &[])
+                .await
+                .expect("block to be generated");
+
+            let block = &generated.block;
+            let size = generated.size;
+            assert_eq!(block.txs().len(), txs);
+            assert_eq!(size.header, block.header().size().unwrap());
+            assert_eq!(size.faults, u32::SIZE);
+            assert_eq!(size.total, block.size().unwrap());
+            sizes.push(size);
+        }
+
+        // Each transaction is accounted in the size of the block
+        let (empty, full) = (sizes[0], sizes[1]);
+        assert_eq!(empty.txs, u32::SIZE);
+        assert!(full.txs > empty.txs);
+        assert_eq!(full.total - empty.total, full.txs - empty.txs);
+    }
+}
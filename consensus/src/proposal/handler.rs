@@ -4,32 +4,43 @@
 //
 // Copyright (c) DUSK NETWORK. All rights reserved.
 
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 
 use async_trait::async_trait;
+use metrics::counter;
 use node_data::bls::PublicKeyBytes;
-use node_data::ledger::to_str;
+use node_data::get_current_timestamp;
+use node_data::ledger::{to_str, Block};
 use node_data::message::payload::{Candidate, GetResource, Inv};
 use node_data::message::{
     ConsensusHeader, Message, Payload, SignedStepMessage, StepMessage,
     WireMessage,
 };
 use tokio::sync::Mutex;
-use tracing::info;
+use tracing::{info, warn};
 
 use crate::commons::{Database, RoundUpdate};
 use crate::config::{
-    is_emergency_iter, MAX_BLOCK_SIZE, MAX_NUMBER_OF_FAULTS,
-    MAX_NUMBER_OF_TRANSACTIONS,
+    is_ahead_of_time, is_emergency_iter, MAX_BLOCK_SIZE,
+    MAX_CANDIDATES_PER_PROPOSER, MAX_CANDIDATES_PER_ROUND,
+    MAX_NUMBER_OF_FAULTS, MAX_NUMBER_OF_TRANSACTIONS,
 };
 use crate::errors::ConsensusError;
 use crate::iteration_ctx::RoundCommittees;
 use crate::merkle::merkle_root;
 use crate::msg_handler::{MsgHandler, StepOutcome};
 use crate::user::committee::Committee;
+use crate::user::provisioners::{verify_fault_culprits, Provisioners};
+
+/// Minimum number of transactions and faults in a candidate for its merkle
+/// roots to be computed concurrently
+const PARALLEL_ROOTS_THRESHOLD: usize = 128;
 
 pub struct ProposalHandler<D: Database> {
     pub(crate) db: Arc<Mutex<D>>,
+    limiter: CandidateLimiter,
+    provisioners: Arc<Provisioners>,
 }
 
 #[async_trait]
@@ -38,6 +49,7 @@ impl<D: Database> MsgHandler for ProposalHandler<D> {
     fn verify(
         &self,
         msg: &Message,
+        ru: &RoundUpdate,
         round_committees: &RoundCommittees,
     ) -> Result<(), ConsensusError> {
         let p = Self::unwrap_msg(msg)?;
@@ -45,7 +57,13 @@ impl<D: Database> MsgHandler for ProposalHandler<D> {
         let generator = round_committees
             .get_generator(iteration)
             .expect("committee to be created before run");
-        super::handler::verify_candidate_msg(p, &generator)?;
+        super::handler::verify_candidate_msg(p, &generator, ru.timestamp())?;
+        // Only the provisioner set of the current round is known here, faults
+        // of past rounds are checked when validating the candidate
+        verify_fault_culprits(p.candidate.faults(), |round| {
+            (round == ru.round).then_some(self.provisioners.as_ref())
+        })
+        .map_err(ConsensusError::InvalidFault)?;
 
         Ok(())
     }
@@ -61,19 +79,7 @@ impl<D: Database> MsgHandler for ProposalHandler<D> {
     ) -> Result<StepOutcome, ConsensusError> {
         // store candidate block
         let p = Self::unwrap_msg(&msg)?;
-        self.db
-            .lock()
-            .await
-            .store_candidate_block(p.candidate.clone())
-            .await;
-
-        info!(
-            event = "New Candidate",
-            hash = &to_str(&p.candidate.header().hash),
-            round = p.candidate.header().height,
-            iter = p.candidate.header().iteration,
-            prev_block = &to_str(&p.candidate.header().prev_block_hash)
-        );
+        self.store_candidate(p).await?;
 
         Ok(StepOutcome::Ready(msg))
     }
@@ -85,20 +91,7 @@ impl<D: Database> MsgHandler for ProposalHandler<D> {
         _generator: Option<PublicKeyBytes>,
     ) -> Result<StepOutcome, ConsensusError> {
         let p = Self::unwrap_msg(&msg)?;
-
-        self.db
-            .lock()
-            .await
-            .store_candidate_block(p.candidate.clone())
-            .await;
-
-        info!(
-            event = "New Candidate",
-            hash = &to_str(&p.candidate.header().hash),
-            round = p.candidate.header().height,
-            iter = p.candidate.header().iteration,
-            prev_block = &to_str(&p.candidate.header().prev_block_hash)
-        );
+        self.store_candidate(p).await?;
 
         Ok(StepOutcome::Ready(msg))
     }
@@ -138,8 +131,62 @@ impl<D: Database> MsgHandler for ProposalHandler<D> {
 }
 
 impl<D: Database> ProposalHandler<D> {
-    pub(crate) fn new(db: Arc<Mutex<D>>) -> Self {
-        Self { db }
+    pub(crate) fn new(
+        db: Arc<Mutex<D>>,
+        provisioners: Arc<Provisioners>,
+    ) -> Self {
+        Self {
+            db,
+            limiter: CandidateLimiter::new(*MAX_CANDIDATES_PER_PROPOSER)
+                .with_round_limit(*MAX_CANDIDATES_PER_ROUND),
+            provisioners,
+        }
+    }
+
+    /// Stores the candidate block unless its signer exceeded the number of
+    /// candidates allowed for the iteration, or the round already stored the
+    /// maximum number of candidates.
+    async fn store_candidate(
+        &mut self,
+        p: &Candidate,
+    ) -> Result<(), ConsensusError> {
+        let header = p.candidate.header();
+        let signer = *p.sign_info().signer.bytes();
+
+        if !self.limiter.allow(&signer, header.iteration, header.hash) {
+            let reason = if self.limiter.is_round_full() {
+                counter!("dusk_candidate_round_cap_reached").increment(1);
+                "round cap reached"
+            } else {
+                counter!("dusk_candidate_rate_limited").increment(1);
+                "rate limited"
+            };
+            warn!(
+                event = "Candidate dropped",
+                reason,
+                hash = &to_str(&header.hash),
+                round = header.height,
+                iter = header.iteration,
+                signer = signer.to_bs58(),
+            );
+            return Err(ConsensusError::CandidateRateLimited);
+        }
+
+        self.db
+            .lock()
+            .await
+            .store_candidate_block(p.candidate.clone())
+            .await;
+
+        info!(
+            event = "New Candidate",
+            hash = &to_str(&header.hash),
+            round = header.height,
+            iter = header.iteration,
+            prev_block = &to_str(&header.prev_block_hash)
+        );
+
+        Ok(())
     }
 
     fn unwrap_msg(msg: &Message) -> Result<&Candidate, ConsensusError> {
@@ -150,14 +197,81 @@ impl<D: Database> ProposalHandler<D> {
     }
 }
 
+/// Tracks the distinct candidates received from each proposer, per iteration,
+/// and across the whole round.
+struct CandidateLimiter {
+    limit: usize,
+    round_limit: usize,
+    stored: usize,
+    seen: HashMap<(PublicKeyBytes, u8), HashSet<[u8; 32]>>,
+}
+
+impl CandidateLimiter {
+    fn new(limit: usize) -> Self {
+        Self {
+            limit,
+            round_limit: usize::MAX,
+            stored: 0,
+            seen: HashMap::new(),
+        }
+    }
+
+    fn with_round_limit(mut self, round_limit: usize) -> Self {
+        self.round_limit = round_limit;
+        self
+    }
+
+    /// Returns `true` if no further candidate can be stored in the round.
+    fn is_round_full(&self) -> bool {
+        self.stored >= self.round_limit
+    }
+
+    /// Returns `true` if the candidate `hash` signed by `signer` for
+    /// `iteration` can be stored.
+    ///
+    /// Candidates already seen are always allowed, as storing them again has
+    /// no effect.
+    fn allow(
+        &mut self,
+        signer: &PublicKeyBytes,
+        iteration: u8,
+        hash: [u8; 32],
+    ) -> bool {
+        let hashes = self.seen.entry((*signer, iteration)).or_default();
+        if hashes.contains(&hash) {
+            return true;
+        }
+
+        if hashes.len() >= self.limit || self.stored >= self.round_limit {
+            return false;
+        }
+
+        hashes.insert(hash);
+        self.stored += 1;
+        true
+    }
+}
+
 fn verify_candidate_msg(
     p: &Candidate,
     expected_generator: &PublicKeyBytes,
+    prev_timestamp: u64,
 ) -> Result<(), ConsensusError> {
     if expected_generator != p.sign_info().signer.bytes() {
         return Err(ConsensusError::NotCommitteeMember);
     }
 
+    // Check number of transactions and faults before any expensive
+    // computation over them
+    if p.candidate.txs().len() > MAX_NUMBER_OF_TRANSACTIONS {
+        return Err(ConsensusError::TooManyTransactions(
+            p.candidate.txs().len(),
+        ));
+    }
+    if p.candidate.faults().len() > MAX_NUMBER_OF_FAULTS {
+        return Err(ConsensusError::TooManyFaults(p.candidate.faults().len()));
+    }
+
     let candidate_size = p
         .candidate
         .size()
@@ -166,6 +280,12 @@ fn verify_candidate_msg(
         return Err(ConsensusError::InvalidBlockSize(candidate_size));
     }
 
+    verify_timestamp(
+        p.candidate.header().timestamp,
+        prev_timestamp,
+        get_current_timestamp(),
+    )?;
+
     // Verify msg signature
     p.verify_signature()?;
 
@@ -179,48 +299,334 @@ fn verify_candidate_msg(
     // the signature only includes the header's hash, making 'txs' and 'faults'
     // fields malleable from an adversary. We then discard blocks with errors
     // related to these fields rather than propagating the message and vote
-    // Invalid
-
-    // Check number of transactions
-    if p.candidate.txs().len() > MAX_NUMBER_OF_TRANSACTIONS {
-        return Err(ConsensusError::TooManyTransactions(
-            p.candidate.txs().len(),
-        ));
-    }
+    // Invalid (see `MalleableFieldPolicy`)
 
-    // Verify tx_root
-    let tx_digests: Vec<_> =
-        p.candidate.txs().iter().map(|t| t.digest()).collect();
-    let tx_root = merkle_root(&tx_digests[..]);
+    // Verify tx_root and fault_root
+    let (tx_root, fault_root) = candidate_roots(&p.candidate);
     if tx_root != p.candidate.header().txroot {
-        return Err(ConsensusError::InvalidBlock);
+        return Err(ConsensusError::InvalidTxRoot);
+    }
+    if fault_root != p.candidate.header().faultroot {
+        return Err(ConsensusError::InvalidFaultRoot);
     }
 
-    // Check number of faults
-    if p.candidate.faults().len() > MAX_NUMBER_OF_FAULTS {
-        return Err(ConsensusError::TooManyFaults(p.candidate.faults().len()));
+    Ok(())
+}
+
+/// Checks the candidate timestamp is after the one of its parent and not
+/// ahead of the local clock (`now`) by more than `MARGIN_TIMESTAMP`
+fn verify_timestamp(
+    timestamp: u64,
+    prev_timestamp: u64,
+    now: u64,
+) -> Result<(), ConsensusError> {
+    if timestamp <= prev_timestamp || is_ahead_of_time(timestamp, now) {
+        return Err(ConsensusError::InvalidTimestamp(timestamp));
     }
+    Ok(())
+}
 
-    // Verify fault_root
-    let fault_digests: Vec<_> =
-        p.candidate.faults().iter().map(|t| t.digest()).collect();
-    let fault_root = merkle_root(&fault_digests[..]);
-    if fault_root != p.candidate.header().faultroot {
-        return Err(ConsensusError::InvalidBlock);
+/// Computes the merkle roots of the transactions and of the faults of a
+/// candidate.
+///
+/// For large blocks, the two roots are computed concurrently.
+fn candidate_roots(candidate: &Block) -> ([u8; 32], [u8; 32]) {
+    let items = candidate.txs().len() + candidate.faults().len();
+    compute_roots(candidate, items >= PARALLEL_ROOTS_THRESHOLD)
+}
+
+fn compute_roots(candidate: &Block, parallel: bool) -> ([u8; 32], [u8; 32]) {
+    let tx_root = || {
+        let tx_digests: Vec<_> =
+            candidate.txs().iter().map(|t| t.digest()).collect();
+        merkle_root(&tx_digests[..])
+    };
+    let fault_root = || {
+        let fault_digests: Vec<_> =
+            candidate.faults().iter().map(|f| f.digest()).collect();
+        merkle_root(&fault_digests[..])
+    };
+
+    if !parallel {
+        return (tx_root(), fault_root());
     }
 
-    Ok(())
+    std::thread::scope(|s| {
+        let fault_root = s.spawn(fault_root);
+        let tx_root = tx_root();
+        (
+            tx_root,
+            fault_root.join().expect("fault_root to be computed"),
+        )
+    })
 }
 
 pub fn verify_stateless(
     c: &Candidate,
+    ru: &RoundUpdate,
     round_committees: &RoundCommittees,
 ) -> Result<(), ConsensusError> {
     let iteration = c.header().iteration;
     let generator = round_committees
         .get_generator(iteration)
         .expect("committee to be created before run");
-    verify_candidate_msg(c, &generator)?;
+    verify_candidate_msg(c, &generator, ru.timestamp())?;
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use dusk_core::signatures::bls::{
+        PublicKey as BlsPublicKey, SecretKey as BlsSecretKey,
+    };
+    use fake::{Fake, Faker};
+    use node_data::bls::PublicKey;
+    use node_data::ledger::{Fault, Header, Transaction};
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    use super::*;
+    use crate::config::{MalleableFieldPolicy, MARGIN_TIMESTAMP};
+    use crate::user::provisioners::ContextProvisioners;
+
+    const PREV_TIMESTAMP: u64 = 1_000;
+
+    fn candidate(
+        generator: &PublicKey,
+        txs: usize,
+        faults: usize,
+    ) -> Candidate {
+        candidate_at(generator, txs, faults, get_current_timestamp())
+    }
+
+    fn candidate_at(
+        generator: &PublicKey,
+        txs: usize,
+        faults: usize,
+        timestamp: u64,
+    ) -> Candidate {
+        let header = Header {
+            generator_bls_pubkey: *generator.bytes(),
+            timestamp,
+            ..Default::default()
+        };
+        let tx: Transaction = Faker.fake();
+        let fault: Fault = Faker.fake();
+        let candidate = Block::new(header, vec![tx; txs], vec![fault; faults])
+            .expect("block to be created");
+        Candidate { candidate }
+    }
+
+    #[test]
+    fn test_too_many_faults_rejected_early() {
+        let generator = PublicKey::from_sk_seed_u64(1);
+
+        // The candidate is not signed, so the count check must be performed
+        // before the signature and the fault_root are verified
+        let c = candidate(&generator, 0, MAX_NUMBER_OF_FAULTS + 1);
+        let res = verify_candidate_msg(&c, generator.bytes(), PREV_TIMESTAMP);
+        assert!(matches!(
+            res,
+            Err(ConsensusError::TooManyFaults(n)) if n == MAX_NUMBER_OF_FAULTS + 1
+        ));
+
+        let c = candidate(&generator, 0, MAX_NUMBER_OF_FAULTS);
+        let res = verify_candidate_msg(&c, generator.bytes(), PREV_TIMESTAMP);
+        assert!(matches!(res, Err(ConsensusError::InvalidSignature(_))));
+    }
+
+    #[test]
+    fn test_too_many_txs_rejected_early() {
+        let generator = PublicKey::from_sk_seed_u64(1);
+
+        // The candidate is not signed, so the count check must be performed
+        // before the signature and the tx_root are verified
+        let c = candidate(&generator, MAX_NUMBER_OF_TRANSACTIONS + 1, 0);
+        let res = verify_candidate_msg(&c, generator.bytes(), PREV_TIMESTAMP);
+        assert!(matches!(
+            res,
+            Err(ConsensusError::TooManyTransactions(n))
+                if n == MAX_NUMBER_OF_TRANSACTIONS + 1
+        ));
+    }
+
+    #[test]
+    fn test_tampered_tx_root() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let sk = BlsSecretKey::random(&mut rng);
+        let generator = PublicKey::new(BlsPublicKey::from(&sk));
+
+        // Build a valid candidate and sign it
+        let c = candidate(&generator, 2, 0);
+        let (txroot, faultroot) = candidate_roots(&c.candidate);
+        let header = Header {
+            txroot,
+            faultroot,
+            hash: Default::default(),
+            ..c.candidate.header().clone()
+        };
+        let txs = c.candidate.txs().clone();
+        let candidate = Block::new(header, txs, vec![]).expect("valid block");
+        let mut c = Candidate { candidate };
+        c.sign(&sk, generator.inner());
+        assert!(
+            verify_candidate_msg(&c, generator.bytes(), PREV_TIMESTAMP).is_ok()
+        );
+
+        // Strip the transactions, leaving the signed header untouched
+        let tampered = Block::new(c.candidate.header().clone(), vec![], vec![])
+            .expect("valid block");
+        let c = Candidate {
+            candidate: tampered,
+        };
+        let err = verify_candidate_msg(&c, generator.bytes(), PREV_TIMESTAMP)
+            .expect_err("tampered candidate to be rejected");
+        assert!(matches!(err, ConsensusError::InvalidTxRoot));
+
+        // Discarded by default, propagated in debug mode
+        assert_eq!(
+            MalleableFieldPolicy::default(),
+            MalleableFieldPolicy::Discard
+        );
+        assert!(!MalleableFieldPolicy::Discard.propagates(&err));
+        assert!(MalleableFieldPolicy::Propagate.propagates(&err));
+
+        // Other verification errors are never propagated
+        let err = ConsensusError::NotCommitteeMember;
+        assert!(!MalleableFieldPolicy::Propagate.propagates(&err));
+    }
+
+    #[test]
+    fn test_invalid_timestamp() {
+        let now = get_current_timestamp();
+        let prev = now - 10;
+
+        assert!(verify_timestamp(now, prev, now).is_ok());
+        assert!(verify_timestamp(now + MARGIN_TIMESTAMP, prev, now).is_ok());
+
+        // Too far in the future
+        let ts = now + MARGIN_TIMESTAMP + 1;
+        assert!(matches!(
+            verify_timestamp(ts, prev, now),
+            Err(ConsensusError::InvalidTimestamp(t)) if t == ts
+        ));
+
+        // Non-increasing
+        assert!(matches!(
+            verify_timestamp(prev, prev, now),
+            Err(ConsensusError::InvalidTimestamp(t)) if t == prev
+        ));
+        assert!(matches!(
+            verify_timestamp(prev - 1, prev, now),
+            Err(ConsensusError::InvalidTimestamp(_))
+        ));
+
+        // The check is performed on candidate messages
+        let generator = PublicKey::from_sk_seed_u64(1);
+        let c = candidate_at(&generator, 0, 0, now);
+        let res = verify_candidate_msg(&c, generator.bytes(), now);
+        assert!(matches!(res, Err(ConsensusError::InvalidTimestamp(_))));
+    }
+
+    #[test]
+    fn test_fault_culprits() {
+        let fault: Fault = Faker.fake();
+        let culprit = fault.to_culprit();
+        let round = fault.round();
+
+        // The culprit unstaked after the fault
+        let mut prev = Provisioners::empty();
+        prev.add_member_with_value(culprit.clone(), 1);
+        let mut current = Provisioners::empty();
+        current.add_member_with_value(PublicKey::from_sk_seed_u64(1), 1);
+
+        // A fault accusing a provisioner of its round passes
+        let res = verify_fault_culprits(&[fault.clone()], |r| {
+            (r == round).then_some(&prev)
+        });
+        assert!(res.is_ok());
+        assert!(verify_fault_culprits(&[], |_| None).is_ok());
+
+        // A fault accusing a non-provisioner of its round is rejected
+        let res = verify_fault_culprits(&[fault.clone()], |r| {
+            (r == round).then_some(&current)
+        });
+        assert!(matches!(res, Err(pk) if pk == *culprit.bytes()));
+
+        // Faults of rounds whose provisioner set is not known are not checked
+        assert!(verify_fault_culprits(&[fault], |_| None).is_ok());
+
+        // Only the sets of the current and the previous rounds are known
+        let mut provisioners = ContextProvisioners::new(current);
+        provisioners.set_previous(prev);
+        assert!(provisioners
+            .at_round(9, 10)
+            .is_some_and(|p| p.get_member(&culprit).is_some()));
+        assert!(provisioners
+            .at_round(10, 10)
+            .is_some_and(|p| p.get_member(&culprit).is_none()));
+        assert!(provisioners.at_round(8, 10).is_none());
+        assert!(provisioners.at_round(11, 10).is_none());
+    }
+
+    #[test]
+    fn test_parallel_roots() {
+        let txs: Vec<Transaction> = (0..PARALLEL_ROOTS_THRESHOLD)
+            .map(|_| Faker.fake())
+            .collect();
+        let fault: Fault = Faker.fake();
+        let blk = Block::new(Header::default(), txs, vec![fault; 4])
+            .expect("block to be created");
+
+        let sequential = compute_roots(&blk, false);
+        assert_eq!(compute_roots(&blk, true), sequential);
+        assert_eq!(candidate_roots(&blk), sequential);
+    }
+
+    #[test]
+    fn test_candidate_limiter() {
+        let mut limiter = CandidateLimiter::new(2);
+        let spammer = PublicKeyBytes([1; 96]);
+        let honest = PublicKeyBytes([2; 96]);
+
+        // Flood candidates from a single signer
+        let allowed = (0..10u8)
+            .filter(|i| limiter.allow(&spammer, 0, [*i; 32]))
+            .count();
+        assert_eq!(allowed, 2, "only 2 candidates should be stored");
+
+        // Re-sending an already stored candidate is not limited
+        assert!(limiter.allow(&spammer, 0, [0; 32]));
+
+        // Candidates for a different iteration are tracked separately
+        assert!(limiter.allow(&spammer, 1, [10; 32]));
+
+        // A different signer is not affected
+        assert!(limiter.allow(&honest, 0, [20; 32]));
+        assert!(limiter.allow(&honest, 0, [21; 32]));
+        assert!(!limiter.allow(&honest, 0, [22; 32]));
+    }
+
+    #[test]
+    fn test_candidate_round_cap() {
+        let mut limiter = CandidateLimiter::new(2).with_round_limit(5);
+
+        // Flood candidates from many proposers, each one within its own limit
+        let allowed = (0..10u8)
+            .filter(|i| {
+                let proposer = PublicKeyBytes([*i; 96]);
+                limiter.allow(&proposer, 0, [*i; 32])
+            })
+            .count();
+        assert_eq!(allowed, 5, "only 5 candidates should be stored");
+        assert!(limiter.is_round_full());
+
+        // Already stored candidates are still allowed
+        assert!(limiter.allow(&PublicKeyBytes([0; 96]), 0, [0; 32]));
+
+        // A new proposer is dropped regardless of its own limit
+        let proposer = PublicKeyBytes([20; 96]);
+        assert!(!limiter.allow(&proposer, 1, [20; 32]));
+    }
+}
@@ -16,7 +16,7 @@ use tracing::{debug, error};
 
 use crate::commons::Database;
 use crate::config;
-use crate::config::MINIMUM_BLOCK_TIME;
+use crate::config::{MINIMUM_BLOCK_TIME, MIN_BLOCK_INTERVAL};
 use crate::execution_ctx::ExecutionCtx;
 use crate::msg_handler::{MsgHandler, StepOutcome};
 use crate::operations::Operations;
@@ -58,6 +58,7 @@ impl<T: Operations + 'static, D: Database> ProposalStep<T, D> {
             .expect("committee to be created before run");
 
         let tip_timestamp = ctx.round_update.timestamp();
+        let skip_slot_delay = ctx.round_update.skip_slot_delay();
 
         if ctx.am_member(committee) {
             let iteration =
@@ -67,6 +68,8 @@ impl<T: Operations + 'static, D: Database> ProposalStep<T, D> {
             let failed_attestations =
                 ctx.sv_registry.lock().await.get_failed_atts(iteration);
 
+            Self::wait_min_block_interval(tip_timestamp).await;
+
             match self
                 .bg
                 .generate_candidate_message(
@@ -101,7 +104,11 @@ impl<T: Operations + 'static, D: Database> ProposalStep<T, D> {
                         .await
                     {
                         Ok(StepOutcome::Ready(msg)) => {
-                            Self::wait_until_next_slot(tip_timestamp).await;
+                            Self::wait_until_next_slot(
+                                tip_timestamp,
+                                skip_slot_delay,
+                            )
+                            .await;
                             return msg;
                         }
                         Err(e) => {
@@ -130,18 +137,36 @@ impl<T: Operations + 'static, D: Database> ProposalStep<T, D> {
                     .await
             }
         };
-        Self::wait_until_next_slot(tip_timestamp).await;
+        Self::wait_until_next_slot(tip_timestamp, skip_slot_delay).await;
         msg
     }
 
-    /// Waits until the next slot is reached
-    async fn wait_until_next_slot(tip_timestamp: u64) {
+    /// Waits until the next slot is reached, unless the slot delay is
+    /// skipped
+    async fn wait_until_next_slot(tip_timestamp: u64, skip_slot_delay: bool) {
+        if skip_slot_delay {
+            debug!(event = "Skip waiting next block slot");
+            return;
+        }
+
         if let Some(delay) = Self::next_slot_in(tip_timestamp) {
             debug!(event = "Wait next block slot for validation", ?delay);
             tokio::time::sleep(delay).await;
         }
     }
 
+    /// Delays the candidate production until the minimum block interval since
+    /// the previous block has elapsed
+    async fn wait_min_block_interval(tip_timestamp: u64) {
+        let now = get_current_timestamp();
+        if let Some(delay) =
+            production_delay(tip_timestamp, now, *MIN_BLOCK_INTERVAL)
+        {
+            debug!(event = "Wait min block interval", ?delay);
+            tokio::time::sleep(delay).await;
+        }
+    }
+
     /// Calculate the duration needed to the next slot
     fn next_slot_in(tip_timestamp: u64) -> Option<Duration> {
         let current_time_secs = get_current_timestamp();
@@ -159,3 +184,40 @@ impl<T: Operations + 'static, D: Database> ProposalStep<T, D> {
         "proposal"
     }
 }
+
+/// Returns the time to wait before producing a candidate on top of a block
+/// with `tip_timestamp`, if any
+fn production_delay(
+    tip_timestamp: u64,
+    now: u64,
+    min_interval: u64,
+) -> Option<Duration> {
+    let earliest = tip_timestamp.saturating_add(min_interval);
+    (now < earliest).then(|| Duration::from_secs(earliest - now))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_production_delay() {
+        let tip_timestamp = 1_000;
+
+        // Default config honors the existing timing
+        assert_eq!(production_delay(tip_timestamp, tip_timestamp, 0), None);
+
+        // A candidate is not produced before the minimum interval
+        assert_eq!(
+            production_delay(tip_timestamp, tip_timestamp + 2, 5),
+            Some(Duration::from_secs(3))
+        );
+        assert_eq!(
+            production_delay(tip_timestamp, tip_timestamp - 1, 5),
+            Some(Duration::from_secs(6))
+        );
+
+        assert_eq!(production_delay(tip_timestamp, tip_timestamp + 5, 5), None);
+        assert_eq!(production_delay(tip_timestamp, tip_timestamp + 9, 5), None);
+    }
+}
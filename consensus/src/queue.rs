@@ -7,6 +7,7 @@
 use std::collections::{BTreeMap, VecDeque};
 use std::fmt::Debug;
 
+use metrics::counter;
 use node_data::message::Message;
 use thiserror::Error;
 use tracing::warn;
@@ -16,8 +17,21 @@ type RoundMap<T> = BTreeMap<u64, StepMap<T>>;
 
 const MAX_MESSAGES_PER_QUEUE: usize = 1000;
 
-#[derive(Debug, Default)]
-pub struct MsgRegistry<T: QueueMessage>(RoundMap<T>);
+/// Default maximum number of messages stored in a registry
+pub const DEFAULT_MAX_MESSAGES: usize = 10_000;
+
+#[derive(Debug)]
+pub struct MsgRegistry<T: QueueMessage> {
+    msgs: RoundMap<T>,
+    /// Maximum number of messages stored across all rounds and steps
+    max_msgs: usize,
+}
+
+impl<T: QueueMessage> Default for MsgRegistry<T> {
+    fn default() -> Self {
+        Self::with_max_msgs(DEFAULT_MAX_MESSAGES)
+    }
+}
 
 pub trait QueueMessage: Debug + Clone {
     fn step(&self) -> u8;
@@ -49,23 +63,46 @@ pub enum MsgRegistryError<T> {
 
 /// A message registry that stores messages based on their round and step.
 impl<T: QueueMessage> MsgRegistry<T> {
+    /// Creates a registry storing up to `max_msgs` messages. Once full, the
+    /// messages of the oldest round are evicted first.
+    pub fn with_max_msgs(max_msgs: usize) -> Self {
+        Self {
+            msgs: RoundMap::new(),
+            max_msgs,
+        }
+    }
+
     /// Inserts a message into the registry based on its round and step.
     pub fn put_msg(&mut self, msg: T) -> Result<T, MsgRegistryError<T>> {
         let round = msg.round();
         let step = msg.step();
-        let vec = self
-            .0
-            .entry(round)
-            .or_default()
-            .entry(step)
-            .or_insert(VecDeque::with_capacity(MAX_MESSAGES_PER_QUEUE));
         if msg.signer().is_none() {
             return Err(MsgRegistryError::NoSigner(msg));
         }
-        if vec.iter().any(|m| m.signer() == msg.signer()) {
+        let enqueued = self
+            .msgs
+            .get(&round)
+            .and_then(|r| r.get(&step))
+            .map(|vec| vec.iter().any(|m| m.signer() == msg.signer()))
+            .unwrap_or_default();
+        if enqueued {
             return Err(MsgRegistryError::SignerAlreadyEnqueue(msg));
         }
 
+        while self.msg_count() >= self.max_msgs {
+            if self.evict_oldest().is_none() {
+                break;
+            }
+            counter!("dusk_future_msg_evicted").increment(1);
+        }
+
+        let vec = self
+            .msgs
+            .entry(round)
+            .or_default()
+            .entry(step)
+            .or_insert(VecDeque::with_capacity(MAX_MESSAGES_PER_QUEUE));
+
         if vec.len() == vec.capacity() {
             warn!("queue ({}, {}) is full, dropping", round, step);
             vec.pop_front();
@@ -76,6 +113,23 @@ impl<T: QueueMessage> MsgRegistry<T> {
         Ok(ret)
     }
 
+    /// Removes the oldest message of the lowest round stored
+    fn evict_oldest(&mut self) -> Option<T> {
+        while let Some(mut round) = self.msgs.first_entry() {
+            while let Some(mut step) = round.get_mut().first_entry() {
+                let evicted = step.get_mut().pop_front();
+                if step.get().is_empty() {
+                    step.remove();
+                }
+                if evicted.is_some() {
+                    return evicted;
+                }
+            }
+            round.remove();
+        }
+        None
+    }
+
     /// Drains and returns all messages that belong to the specified round and
     /// step.
     pub fn drain_msg_by_round_step(
@@ -83,18 +137,18 @@ impl<T: QueueMessage> MsgRegistry<T> {
         round: u64,
         step: u8,
     ) -> Option<VecDeque<T>> {
-        self.0
+        self.msgs
             .get_mut(&round)
             .and_then(|r| r.remove_entry(&step).map(|(_, v)| v))
     }
 
     /// Removes all messages that belong to the specified round.
     pub fn remove_msgs_by_round(&mut self, round: u64) {
-        if let Some(r) = self.0.get_mut(&round) {
+        if let Some(r) = self.msgs.get_mut(&round) {
             r.clear();
         };
 
-        self.0.remove(&round);
+        self.msgs.remove(&round);
     }
 
     /// Removes all messages that do not belong to the range (closed interval)
@@ -102,8 +156,8 @@ impl<T: QueueMessage> MsgRegistry<T> {
     pub fn remove_msgs_out_of_range(&mut self, start_round: u64, offset: u64) {
         let end_round = start_round + offset;
 
-        self.0 = self
-            .0
+        self.msgs = self
+            .msgs
             .split_off(&start_round)
             .into_iter()
             .filter(|(k, _)| *k <= end_round)
@@ -112,7 +166,7 @@ impl<T: QueueMessage> MsgRegistry<T> {
 
     /// Returns the total number of messages in the registry.
     pub fn msg_count(&self) -> usize {
-        self.0
+        self.msgs
             .values()
             .map(|round| round.values().map(|items| items.len()).sum::<usize>())
             .sum()
@@ -209,4 +263,27 @@ mod tests {
         assert!(reg.drain_msg_by_round_step(round + 2, 1).is_some());
         Ok(())
     }
+
+    #[test]
+    fn test_max_msgs() -> Result<(), super::MsgRegistryError<Item>> {
+        let mut reg = MsgRegistry::<Item>::with_max_msgs(10);
+
+        // Fill the registry past its cap
+        for round in 100..105 {
+            for data in 0..4 {
+                reg.put_msg(Item::new(round, 1, data))?;
+            }
+        }
+        assert_eq!(reg.msg_count(), 10);
+
+        // The oldest rounds have been evicted
+        assert!(reg.drain_msg_by_round_step(100, 1).is_none());
+        assert!(reg.drain_msg_by_round_step(101, 1).is_none());
+        assert_eq!(
+            reg.drain_msg_by_round_step(102, 1).unwrap(),
+            vec![Item::new(102, 1, 2), Item::new(102, 1, 3)],
+        );
+        assert_eq!(reg.drain_msg_by_round_step(104, 1).unwrap().len(), 4);
+        Ok(())
+    }
 }
@@ -4,13 +4,15 @@
 //
 // Copyright (c) DUSK NETWORK. All rights reserved.
 
+use std::collections::BTreeMap;
+
 use dusk_bytes::Serializable as BytesSerializable;
 use dusk_core::signatures::bls::{
     MultisigPublicKey as BlsMultisigPublicKey,
     MultisigSignature as BlsMultisigSignature,
 };
 use node_data::bls::PublicKey;
-use node_data::ledger::{to_str, Seed, StepVotes};
+use node_data::ledger::{to_str, Attestation, Seed, StepVotes};
 use node_data::message::payload::{self, Vote};
 use node_data::message::{ConsensusHeader, SignedStepMessage};
 use node_data::{Serializable, StepName};
@@ -18,12 +20,102 @@ use tokio::sync::RwLock;
 use tracing::error;
 
 use crate::config::exclude_next_generator;
-use crate::errors::StepSigError;
+use crate::errors::{AttestationError, StepSigError};
 use crate::operations::Voter;
 use crate::user::cluster::Cluster;
 use crate::user::committee::{Committee, CommitteeSet};
+use crate::user::provisioners::Provisioners;
 use crate::user::sortition;
 
+/// Verifies the validation and ratification votes of an attestation against a
+/// set of provisioners.
+///
+/// `header` is the consensus header of the attested candidate, while `seed` is
+/// the seed of its previous block.
+///
+/// Returns the quorum results of both steps, along with the voters of the
+/// attestation and their credits.
+pub async fn verify_attestation(
+    att: &Attestation,
+    header: &ConsensusHeader,
+    seed: Seed,
+    provisioners: &Provisioners,
+) -> Result<(QuorumResult, QuorumResult, Vec<Voter>), AttestationError> {
+    let committee = RwLock::new(CommitteeSet::new(provisioners));
+    let vote = att.result.vote();
+
+    // Verify validation
+    let (val_result, validation_voters) = verify_step_votes(
+        header,
+        vote,
+        &att.validation,
+        &committee,
+        seed,
+        StepName::Validation,
+    )
+    .await
+    .map_err(|s| AttestationError::InvalidVotes(StepName::Validation, s))?;
+
+    // Verify ratification
+    let (rat_result, ratification_voters) = verify_step_votes(
+        header,
+        vote,
+        &att.ratification,
+        &committee,
+        seed,
+        StepName::Ratification,
+    )
+    .await
+    .map_err(|s| AttestationError::InvalidVotes(StepName::Ratification, s))?;
+
+    let voters = merge_voters(validation_voters, ratification_voters);
+    Ok((val_result, rat_result, voters))
+}
+
+/// Returns the cumulative stake of the provisioners that signed an
+/// attestation, as a measure of its finality strength.
+///
+/// `validation` and `ratification` are the committees of the attested
+/// iteration. Provisioners voting in both steps are counted once.
+///
+/// The signatures are not verified: the attestation must be checked with
+/// [verify_attestation] beforehand.
+pub fn attestation_weight(
+    att: &Attestation,
+    validation: &Committee,
+    ratification: &Committee,
+    provisioners: &Provisioners,
+) -> u64 {
+    let mut signers = Cluster::new();
+    for (sv, committee) in [
+        (&att.validation, validation),
+        (&att.ratification, ratification),
+    ] {
+        for (member, credits) in committee.intersect(sv.bitset).iter() {
+            signers.add(member, *credits);
+        }
+    }
+
+    signers
+        .iter()
+        .flat_map(|(signer, _)| provisioners.get_member(signer))
+        .map(|stake| stake.value())
+        .sum()
+}
+
+/// Merges two Vec<Voter>, summing up the usize values if the PublicKey is
+/// repeated
+pub fn merge_voters(v1: Vec<Voter>, v2: Vec<Voter>) -> Vec<Voter> {
+    let mut voter_map = BTreeMap::new();
+
+    for (pk, count) in v1.into_iter().chain(v2.into_iter()) {
+        let counter = voter_map.entry(pk).or_default();
+        *counter += count;
+    }
+
+    voter_map.into_iter().collect()
+}
+
 pub async fn verify_step_votes(
     header: &ConsensusHeader,
     vote: &Vote,
@@ -103,6 +195,19 @@ pub fn verify_votes(
     committee: &Committee,
 ) -> Result<(QuorumResult, Vec<Voter>), StepSigError> {
     let bitset = step_votes.bitset;
+
+    // Reject the votes claiming signers outside the committee before any
+    // expensive verification
+    if !within_committee(bitset, committee.size()) {
+        // The bitset is not empty, as it refers to members outside the
+        // committee
+        let highest_signer = u64::BITS - 1 - bitset.leading_zeros();
+        return Err(StepSigError::SignerOutOfRange(
+            highest_signer,
+            committee.size(),
+        ));
+    }
+
     let signature = step_votes.aggregate_signature().inner();
     let sub_committee = committee.intersect(bitset);
 
@@ -182,6 +287,14 @@ fn verify_step_signature(
     Ok(())
 }
 
+/// Returns true if `bitset` only refers to the first `committee_size` members
+fn within_committee(bitset: u64, committee_size: usize) -> bool {
+    u32::try_from(committee_size)
+        .ok()
+        .and_then(|size| bitset.checked_shr(size))
+        .map_or(true, |outside| outside == 0)
+}
+
 pub async fn get_step_voters(
     header: &ConsensusHeader,
     sv: &StepVotes,
@@ -242,3 +355,253 @@ async fn get_step_committee(
 
     committee.clone()
 }
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use dusk_core::signatures::bls::PublicKey as BlsPublicKey;
+    use node_data::ledger::Header;
+    use node_data::message::payload::{
+        QuorumType, RatificationResult, ValidationResult,
+    };
+
+    use super::*;
+    use crate::aggregator::{Aggregator, StepVote};
+    use crate::commons::RoundUpdate;
+    use crate::tests::secret_keys;
+    use crate::user::provisioners::DUSK;
+    use crate::{build_ratification_payload, build_validation_payload};
+
+    /// Collects the votes of the committee members until the quorum is
+    /// reached, returning the resulting StepVotes
+    fn collect_votes<V: StepVote + std::fmt::Debug>(
+        committee: &Committee,
+        msgs: &[V],
+    ) -> StepVotes {
+        let mut aggregator = Aggregator::default();
+        for msg in msgs {
+            if !committee.is_member(&msg.sign_info().signer) {
+                continue;
+            }
+            let (sv, quorum_reached) = aggregator
+                .collect_vote(committee, msg)
+                .expect("vote to be collected");
+            if quorum_reached {
+                return sv;
+            }
+        }
+        panic!("quorum should be reached");
+    }
+
+    /// Creates a provisioner set and a valid attestation for a candidate at
+    /// round 1
+    async fn attestation() -> (Attestation, ConsensusHeader, Seed, Provisioners)
+    {
+        let tip_header = Header::default();
+        let iteration = 0;
+        let vote = Vote::Valid([1u8; 32]);
+
+        let mut provisioners = Provisioners::empty();
+        let mut round_updates = vec![];
+        for sk in secret_keys() {
+            let pk = node_data::bls::PublicKey::new(BlsPublicKey::from(&sk));
+            provisioners.add_member_with_value(pk.clone(), 1000 * DUSK);
+            round_updates.push(RoundUpdate::new(
+                pk,
+                sk,
+                &tip_header,
+                HashMap::new(),
+                vec![],
+            ));
+        }
+
+        let header = ConsensusHeader {
+            prev_block_hash: tip_header.hash,
+            round: tip_header.height + 1,
+            iteration,
+        };
+        let seed = tip_header.seed;
+        let committees = RwLock::new(CommitteeSet::new(&provisioners));
+
+        let committee = get_step_committee(
+            &header,
+            &committees,
+            seed,
+            StepName::Validation,
+        )
+        .await;
+        let validations: Vec<_> = round_updates
+            .iter()
+            .map(|ru| build_validation_payload(vote, ru, iteration))
+            .collect();
+        let validation = collect_votes(&committee, &validations);
+
+        let committee = get_step_committee(
+            &header,
+            &committees,
+            seed,
+            StepName::Ratification,
+        )
+        .await;
+        let result = ValidationResult::new(validation, vote, QuorumType::Valid);
+        let ratifications: Vec<_> = round_updates
+            .iter()
+            .map(|ru| build_ratification_payload(ru, iteration, &result))
+            .collect();
+        let ratification = collect_votes(&committee, &ratifications);
+
+        let att = Attestation {
+            result: RatificationResult::Success(vote),
+            validation,
+            ratification,
+        };
+        (att, header, seed, provisioners)
+    }
+
+    #[tokio::test]
+    async fn test_verify_attestation() {
+        let (att, header, seed, provisioners) = attestation().await;
+
+        let (val_result, rat_result, voters) =
+            verify_attestation(&att, &header, seed, &provisioners)
+                .await
+                .expect("attestation to be valid");
+        assert!(val_result.quorum_reached());
+        assert!(rat_result.quorum_reached());
+        assert!(!voters.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_verify_tampered_attestation() {
+        let (att, header, seed, provisioners) = attestation().await;
+
+        // Tamper with the attested vote
+        let mut tampered = att;
+        tampered.result = RatificationResult::Success(Vote::Valid([2u8; 32]));
+        assert!(verify_attestation(&tampered, &header, seed, &provisioners)
+            .await
+            .is_err());
+
+        // Tamper with the ratification voters
+        let mut tampered = att;
+        tampered.ratification.bitset ^= 1;
+        assert!(verify_attestation(&tampered, &header, seed, &provisioners)
+            .await
+            .is_err());
+
+        // Verify against a different round
+        let header = ConsensusHeader {
+            round: header.round + 1,
+            ..header
+        };
+        assert!(verify_attestation(&att, &header, seed, &provisioners)
+            .await
+            .is_err());
+    }
+
+    /// Assigns the committee credits to all the eligible provisioners in
+    /// turn
+    struct RoundRobin;
+
+    impl sortition::CommitteeExtraction for RoundRobin {
+        fn extract(
+            &self,
+            provisioners: &Provisioners,
+            cfg: &sortition::Config,
+        ) -> Vec<PublicKey> {
+            let eligibles: Vec<_> = provisioners
+                .eligibles(cfg.round())
+                .map(|(pk, _)| pk.clone())
+                .collect();
+            eligibles
+                .iter()
+                .cycle()
+                .take(cfg.committee_credits())
+                .cloned()
+                .collect()
+        }
+    }
+
+    #[test]
+    fn test_attestation_weight() {
+        // Provisioners with distinct stakes: 1000, 2000, ..., 10000 DUSK
+        let mut provisioners = Provisioners::empty();
+        let mut pks = vec![];
+        for (i, sk) in secret_keys().iter().enumerate() {
+            let pk = node_data::bls::PublicKey::new(BlsPublicKey::from(sk));
+            let stake = (i as u64 + 1) * 1000 * DUSK;
+            provisioners.add_member_with_value(pk.clone(), stake);
+            pks.push(pk);
+        }
+
+        let committee = |step| {
+            let cfg =
+                sortition::Config::new(Seed::default(), 1, 0, step, vec![]);
+            Committee::with_extraction(&provisioners, &cfg, &RoundRobin)
+        };
+        let validation = committee(StepName::Validation);
+        let ratification = committee(StepName::Ratification);
+
+        let step_votes = |committee: &Committee, signers: &[usize]| {
+            let mut cluster = Cluster::new();
+            for i in signers {
+                cluster.add(&pks[*i], 1);
+            }
+            StepVotes::new([0; 48], committee.bits(&cluster))
+        };
+
+        // The third provisioner signs both steps, and is counted once
+        let att = Attestation {
+            validation: step_votes(&validation, &[0, 2, 5]),
+            ratification: step_votes(&ratification, &[2, 7]),
+            ..Default::default()
+        };
+        assert_eq!(
+            attestation_weight(&att, &validation, &ratification, &provisioners),
+            (1000 + 3000 + 6000 + 8000) * DUSK
+        );
+
+        // A failed attestation, with no signers, has no weight
+        let att = Attestation::default();
+        assert_eq!(
+            attestation_weight(&att, &validation, &ratification, &provisioners),
+            0
+        );
+    }
+
+    #[tokio::test]
+    async fn test_signer_out_of_range() {
+        let (att, header, seed, provisioners) = attestation().await;
+
+        // The ratification votes claim signers outside the committee
+        let mut oversized = att;
+        oversized.ratification.bitset = u64::MAX;
+        match verify_attestation(&oversized, &header, seed, &provisioners).await
+        {
+            Err(AttestationError::InvalidVotes(
+                StepName::Ratification,
+                StepSigError::SignerOutOfRange(63, size),
+            )) => assert!(size < 64),
+            res => panic!("unexpected result {:?}", res.err()),
+        }
+
+        // A single signer right after the last member is reported as such
+        let committee = Committee::default();
+        let step_votes = StepVotes::new([0; 48], 0b1);
+        match verify_votes(
+            &header,
+            StepName::Ratification,
+            &Vote::Valid([1u8; 32]),
+            &step_votes,
+            &committee,
+        ) {
+            Err(StepSigError::SignerOutOfRange(0, 0)) => {}
+            res => panic!("unexpected result {:?}", res.err()),
+        }
+
+        assert!(within_committee(0b111, 3));
+        assert!(!within_committee(0b1000, 3));
+        assert!(within_committee(u64::MAX, 64));
+    }
+}
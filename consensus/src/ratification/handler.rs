@@ -78,6 +78,7 @@ impl MsgHandler for RatificationHandler {
     fn verify(
         &self,
         msg: &Message,
+        _ru: &RoundUpdate,
         _round_committees: &RoundCommittees,
     ) -> Result<(), ConsensusError> {
         if let Payload::Ratification(p) = &msg.payload {
@@ -201,6 +201,21 @@ impl AttInfoRegistry {
         None
     }
 
+    /// Returns the attestation of `vote` for `iteration`, if its quorum has
+    /// been reached
+    pub(crate) fn attestation(
+        &self,
+        iteration: u8,
+        vote: &Vote,
+    ) -> Option<Attestation> {
+        self.att_list
+            .get(&iteration)?
+            .votes
+            .get(vote)
+            .filter(|att_info| att_info.is_ready())
+            .map(|att_info| att_info.att)
+    }
+
     fn get_iteration_atts(
         &mut self,
         iteration: u8,
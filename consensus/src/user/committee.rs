@@ -32,10 +32,20 @@ impl Committee {
     /// Generates a new committee from the given provisioners state and
     /// sortition config.
     ///
-    /// It executes deterministic sortition algorithm.
+    /// It executes the committee extraction algorithm in use, which is the
+    /// deterministic sortition unless another one has been installed (see
+    /// [sortition::set_extraction]).
     pub fn new(provisioners: &Provisioners, cfg: &sortition::Config) -> Self {
-        // Generate committee using deterministic sortition.
-        let extracted = provisioners.create_committee(cfg);
+        Self::with_extraction(provisioners, cfg, sortition::extraction())
+    }
+
+    /// Generates a new committee using the given extraction algorithm.
+    pub fn with_extraction(
+        provisioners: &Provisioners,
+        cfg: &sortition::Config,
+        extraction: &dyn sortition::CommitteeExtraction,
+    ) -> Self {
+        let extracted = extraction.extract(provisioners, cfg);
         let committee_credits = cfg.committee_credits();
 
         let majority = majority(committee_credits);
@@ -130,6 +140,53 @@ impl Committee {
             .flat_map(|(voter, _)| self.votes_for(voter))
             .sum()
     }
+
+    /// Compares the membership of this committee with `other`.
+    ///
+    /// Useful to pinpoint a sortition divergence between two nodes.
+    pub fn diff(&self, other: &Committee) -> CommitteeDiff {
+        let mut diff = CommitteeDiff::default();
+
+        for (member, weight) in &self.members {
+            match other.votes_for(member) {
+                None => diff.only_in_self.push(member.clone()),
+                Some(other_weight) if other_weight != *weight => diff
+                    .weight_changes
+                    .push((member.clone(), *weight, other_weight)),
+                Some(_) => {}
+            }
+        }
+
+        diff.only_in_other = other
+            .members
+            .keys()
+            .filter(|member| !self.is_member(member))
+            .cloned()
+            .collect();
+
+        diff
+    }
+}
+
+/// Differences between the members of two committees
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+pub struct CommitteeDiff {
+    /// Members of the first committee only
+    pub only_in_self: Vec<PublicKey>,
+    /// Members of the second committee only
+    pub only_in_other: Vec<PublicKey>,
+    /// Members of both committees with a different weight, along with their
+    /// weight in the first and in the second one
+    pub weight_changes: Vec<(PublicKey, usize, usize)>,
+}
+
+impl CommitteeDiff {
+    /// Returns true if both committees have the same members and weights
+    pub fn is_empty(&self) -> bool {
+        self.only_in_self.is_empty()
+            && self.only_in_other.is_empty()
+            && self.weight_changes.is_empty()
+    }
 }
 
 impl fmt::Display for &Committee {
@@ -173,3 +230,42 @@ impl<'p> CommitteeSet<'p> {
         self.provisioners
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn committee(members: &[(u64, usize)]) -> Committee {
+        Committee {
+            members: members
+                .iter()
+                .map(|(seed, weight)| {
+                    (PublicKey::from_sk_seed_u64(*seed), *weight)
+                })
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_committee_diff() {
+        let a = committee(&[(1, 3), (2, 2), (3, 1)]);
+        assert!(a.diff(&a).is_empty());
+
+        // The committees differ by one member
+        let b = committee(&[(1, 3), (2, 2), (4, 1)]);
+        let diff = a.diff(&b);
+        assert_eq!(diff.only_in_self, vec![PublicKey::from_sk_seed_u64(3)]);
+        assert_eq!(diff.only_in_other, vec![PublicKey::from_sk_seed_u64(4)]);
+        assert!(diff.weight_changes.is_empty());
+
+        // Same members with a different weight
+        let c = committee(&[(1, 3), (2, 1), (3, 1)]);
+        let diff = a.diff(&c);
+        assert_eq!(
+            diff.weight_changes,
+            vec![(PublicKey::from_sk_seed_u64(2), 2, 1)]
+        );
+        assert!(diff.only_in_self.is_empty() && diff.only_in_other.is_empty());
+    }
+}
@@ -10,7 +10,7 @@ use std::mem;
 use dusk_core::dusk;
 use dusk_core::stake::DEFAULT_MINIMUM_STAKE;
 use node_data::bls::{PublicKey, PublicKeyBytes};
-use node_data::ledger::Seed;
+use node_data::ledger::{Fault, Seed};
 use node_data::StepName;
 use num_bigint::BigInt;
 
@@ -53,6 +53,23 @@ impl ContextProvisioners {
     pub fn prev(&self) -> &Provisioners {
         self.prev.as_ref().unwrap_or(&self.current)
     }
+
+    /// Returns the provisioner set of `round`, where `current_round` is the
+    /// round the current set refers to.
+    ///
+    /// Only the current and the previous sets are known, [None] is returned
+    /// for any other round.
+    pub fn at_round(
+        &self,
+        round: u64,
+        current_round: u64,
+    ) -> Option<&Provisioners> {
+        match current_round.checked_sub(round)? {
+            0 => Some(self.current()),
+            1 => Some(self.prev()),
+            _ => None,
+        }
+    }
     /// Swap `self.current` and `self.prev` and update `self.current` with `new`
     pub fn update_and_swap(&mut self, mut new: Provisioners) {
         mem::swap(&mut self.current, &mut new);
@@ -103,6 +120,30 @@ impl ContextProvisioners {
     }
 }
 
+/// Checks each fault accuses a member of the provisioner set of the fault's
+/// round, as a fault accusing anyone else is meaningless.
+///
+/// `provisioners_at` returns the provisioner set of a round, if known. Faults
+/// of rounds whose set is not known are not checked, so that a culprit who
+/// unstaked after the fault cannot invalidate an honest block.
+///
+/// Returns the first culprit that is not a provisioner, if any.
+pub fn verify_fault_culprits<'a>(
+    faults: &[Fault],
+    provisioners_at: impl Fn(u64) -> Option<&'a Provisioners>,
+) -> Result<(), PublicKeyBytes> {
+    for fault in faults {
+        let Some(provisioners) = provisioners_at(fault.round()) else {
+            continue;
+        };
+        let culprit = fault.to_culprit();
+        if provisioners.get_member(&culprit).is_none() {
+            return Err(*culprit.bytes());
+        }
+    }
+    Ok(())
+}
+
 impl Provisioners {
     pub fn empty() -> Self {
         Self {
@@ -121,6 +162,10 @@ impl Provisioners {
         self.members.entry(pubkey_bls).or_insert_with(|| stake);
     }
 
+    pub fn get_member(&self, pubkey_bls: &PublicKey) -> Option<&Stake> {
+        self.members.get(pubkey_bls)
+    }
+
     pub fn get_member_mut(
         &mut self,
         pubkey_bls: &PublicKey,
@@ -205,12 +250,11 @@ impl Provisioners {
         );
 
         let mut total_weight = comm.total_weight().into();
+        let mut rng = cfg.rng();
 
         while extracted.len() != committee_credits {
-            let counter = extracted.len() as u32;
-
             // 1. Compute n ← H(seed ∣∣ step ∣∣ counter)
-            let hash = sortition::create_sortition_hash(cfg, counter);
+            let hash = rng.next_hash();
 
             // 2. Compute d ← n mod s
             let score =
@@ -4,7 +4,9 @@
 //
 // Copyright (c) DUSK NETWORK. All rights reserved.
 
-use node_data::bls::PublicKeyBytes;
+use std::sync::OnceLock;
+
+use node_data::bls::{PublicKey, PublicKeyBytes};
 use node_data::ledger::Seed;
 use node_data::StepName;
 use num_bigint::BigInt;
@@ -15,6 +17,7 @@ use crate::config::{
     PROPOSAL_COMMITTEE_CREDITS, RATIFICATION_COMMITTEE_CREDITS,
     VALIDATION_COMMITTEE_CREDITS,
 };
+use crate::user::provisioners::Provisioners;
 
 #[derive(Debug, Clone, Default, Eq, Hash, PartialEq)]
 pub struct Config {
@@ -63,6 +66,11 @@ impl Config {
     pub fn exclusion(&self) -> &Vec<PublicKeyBytes> {
         &self.exclusion
     }
+
+    /// Returns the source of randomness driving the committee extraction
+    pub fn rng(&self) -> RoundRng {
+        RoundRng::new(self.seed, self.step)
+    }
 }
 
 // The deterministic procedure requires the set of active stakes,
@@ -70,16 +78,108 @@ impl Config {
 // current consensus round and current consensus step.
 
 pub fn create_sortition_hash(cfg: &Config, counter: u32) -> [u8; 32] {
-    let mut hasher = Sha3_256::new();
+    RoundRng {
+        counter,
+        ..cfg.rng()
+    }
+    .next_hash()
+}
+
+/// Deterministic source of randomness keyed on the round seed and the
+/// consensus step.
+///
+/// The committee extraction draws its sortition hashes from it. Harnesses
+/// that need randomness (e.g. contract tests) can draw from the same source,
+/// so that it drives both the committees and any randomized behavior of the
+/// round.
+#[derive(Debug, Clone)]
+pub struct RoundRng {
+    seed: Seed,
+    step: u8,
+    counter: u32,
+}
+
+impl RoundRng {
+    pub fn new(seed: Seed, step: u8) -> Self {
+        Self {
+            seed,
+            step,
+            counter: 0,
+        }
+    }
+
+    /// Returns the next hash of the sequence, computed as
+    /// `H(seed || step || counter)`
+    pub fn next_hash(&mut self) -> [u8; 32] {
+        let mut hasher = Sha3_256::new();
+
+        // write input message
+        hasher.update(&self.seed.inner()[..]);
+        hasher.update(self.step.to_le_bytes());
+        hasher.update(self.counter.to_le_bytes());
+        self.counter += 1;
+
+        // read hash digest
+        let reader = hasher.finalize();
+        reader.as_slice().try_into().expect("Wrong length")
+    }
+}
+
+impl Iterator for RoundRng {
+    type Item = [u8; 32];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        Some(self.next_hash())
+    }
+}
+
+/// Algorithm extracting the committee members out of the provisioners set.
+pub trait CommitteeExtraction: Send + Sync {
+    /// Returns the extracted public keys, one per committee credit.
+    ///
+    /// The same public key can be extracted multiple times.
+    fn extract(
+        &self,
+        provisioners: &Provisioners,
+        cfg: &Config,
+    ) -> Vec<PublicKey>;
+}
+
+/// Deterministic stake-weighted sortition. This is the default algorithm.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct StakeWeightedSortition;
+
+impl CommitteeExtraction for StakeWeightedSortition {
+    fn extract(
+        &self,
+        provisioners: &Provisioners,
+        cfg: &Config,
+    ) -> Vec<PublicKey> {
+        provisioners.create_committee(cfg)
+    }
+}
 
-    // write input message
-    hasher.update(&cfg.seed.inner()[..]);
-    hasher.update(cfg.step.to_le_bytes());
-    hasher.update(counter.to_le_bytes());
+static EXTRACTION: OnceLock<Box<dyn CommitteeExtraction>> = OnceLock::new();
+
+/// Installs an alternative committee extraction algorithm for the whole
+/// process.
+///
+/// Intended for research networks only: all the nodes of a network must use
+/// the same algorithm. It can be installed only once, before any committee
+/// is generated, otherwise the algorithm is given back as error.
+pub fn set_extraction(
+    extraction: Box<dyn CommitteeExtraction>,
+) -> Result<(), Box<dyn CommitteeExtraction>> {
+    EXTRACTION.set(extraction)
+}
 
-    // read hash digest
-    let reader = hasher.finalize();
-    reader.as_slice().try_into().expect("Wrong length")
+/// Returns the committee extraction algorithm in use.
+///
+/// Defaults to [StakeWeightedSortition] if none has been installed.
+pub fn extraction() -> &'static dyn CommitteeExtraction {
+    EXTRACTION
+        .get_or_init(|| Box::new(StakeWeightedSortition))
+        .as_ref()
 }
 
 /// Generate a score from the given hash and total stake weight
@@ -94,13 +194,11 @@ pub fn generate_sortition_score(
 #[cfg(test)]
 mod tests {
 
-    use dusk_bytes::DeserializableSlice;
-    use dusk_core::signatures::bls::{
-        PublicKey as BlsPublicKey, SecretKey as BlsSecretKey,
-    };
+    use dusk_core::signatures::bls::PublicKey as BlsPublicKey;
     use node_data::ledger::Seed;
 
     use super::*;
+    use crate::tests::secret_keys;
     use crate::user::committee::Committee;
     use crate::user::provisioners::{Provisioners, DUSK};
     use crate::user::sortition::Config;
@@ -181,6 +279,39 @@ mod tests {
         assert_eq!(vec![4, 29, 9, 22], committee.get_occurrences());
     }
 
+    #[test]
+    fn test_pluggable_extraction() {
+        /// Assigns all the credits to the first eligible provisioner
+        struct FirstEligible;
+
+        impl CommitteeExtraction for FirstEligible {
+            fn extract(
+                &self,
+                provisioners: &Provisioners,
+                cfg: &Config,
+            ) -> Vec<PublicKey> {
+                let (first, _) = provisioners
+                    .eligibles(cfg.round())
+                    .next()
+                    .expect("at least one eligible provisioner");
+                vec![first.clone(); cfg.committee_credits()]
+            }
+        }
+
+        let p = generate_provisioners(5);
+        let cfg = Config::raw(Seed::default(), 1, 1, 64, vec![]);
+
+        let committee = Committee::with_extraction(&p, &cfg, &FirstEligible);
+        assert_eq!(vec![64], committee.get_occurrences());
+
+        // The default algorithm is the stake-weighted sortition
+        let committee = Committee::new(&p, &cfg);
+        let sortition =
+            Committee::with_extraction(&p, &cfg, &StakeWeightedSortition);
+        assert_eq!(vec![4, 29, 9, 22], committee.get_occurrences());
+        assert_eq!(committee.members(), sortition.members());
+    }
+
     #[test]
     fn test_deterministic_sortition_2() {
         let p = generate_provisioners(5);
@@ -246,6 +377,46 @@ mod tests {
         assert_eq!(vec![8, 13, 24], committee.get_occurrences());
     }
 
+    #[test]
+    fn test_round_rng() {
+        use rand::rngs::StdRng;
+        use rand::{RngCore, SeedableRng};
+
+        let p = generate_provisioners(5);
+        let cfg = Config::raw(Seed::default(), 1, 1, 64, vec![]);
+
+        // The sortition hashes are the sequence drawn from the round RNG
+        for (counter, hash) in cfg.rng().take(64).enumerate() {
+            assert_eq!(hash, create_sortition_hash(&cfg, counter as u32));
+        }
+
+        // A harness seeding its RNG from the same source as the extraction
+        let run = || {
+            let committee = Committee::new(&p, &cfg);
+            let mut rng = StdRng::from_seed(cfg.rng().next_hash());
+            (committee, rng.next_u64())
+        };
+
+        let (committee_1, outcome_1) = run();
+        let (committee_2, outcome_2) = run();
+        assert_eq!(committee_1.members(), committee_2.members());
+        assert_eq!(vec![4, 29, 9, 22], committee_1.get_occurrences());
+        assert_eq!(outcome_1, outcome_2);
+
+        // A different seed or step produce a different sequence
+        let mut rng = cfg.rng();
+        assert_ne!(
+            rng.next_hash(),
+            RoundRng::new(Seed::from([8u8; 48]), cfg.step()).next_hash()
+        );
+        assert_ne!(
+            rng.next_hash(),
+            RoundRng::new(Seed::default(), cfg.step() + 1)
+                .nth(1)
+                .unwrap()
+        );
+    }
+
     #[test]
     fn test_quorum() {
         let p = generate_provisioners(5);
@@ -282,26 +453,7 @@ mod tests {
     }
 
     fn generate_provisioners(n: usize) -> Provisioners {
-        let sks = [
-            "7f6f2ccdb23f2abb7b69278e947c01c6160a31cf02c19d06d0f6e5ab1d768b15",
-            "611830d3641a68f94a690dcc25d1f4b0dac948325ac18f6dd32564371735f32c",
-            "1fbec814b18b1d4c3eaa7cec41007e04bf0a98453b06ec7582aa29882c52eb3e",
-            "ecd9c4a53ea15f18447b08fb96a13c5ab7dc7d24067b102fcbaaf7b39ca52e2d",
-            "e463bcb1a6e57288ffd4671503082fa8656e3eacb78fb1925f8a7c76400e8e15",
-            "7a19fb2d099a9557f7c10c2efbb8b101d9e0ec85610d5c74a887d1d4fb8d2827",
-            "4dbad51eb408af559dd91bbbed8dbeae0a2c89e0e05f0cce87c98652a8437f1f",
-            "befba86ae9e0c207865f7e24e8349d4ecdbc8b0f4632842499a0dfa60568e20a",
-            "b260b8a10343bf5a5dacb4f1d32d06c4fdddc9981a3619fbc0a5cd9eb30f3334",
-            "87a9779748888da5d96bbbce041b5109c6ffc0c4f30561c0170384a5922d9e21",
-        ];
-        let sks: Vec<_> = sks
-            .iter()
-            .take(n)
-            .map(|hex| hex::decode(hex).expect("valid hex"))
-            .map(|data| {
-                BlsSecretKey::from_slice(&data[..]).expect("valid secret key")
-            })
-            .collect();
+        let sks: Vec<_> = secret_keys().into_iter().take(n).collect();
 
         let mut p = Provisioners::empty();
         for (i, sk) in sks.iter().enumerate().skip(1) {
@@ -131,6 +131,7 @@ impl<D: Database> MsgHandler for ValidationHandler<D> {
     fn verify(
         &self,
         msg: &Message,
+        _ru: &RoundUpdate,
         _round_committees: &RoundCommittees,
     ) -> Result<(), ConsensusError> {
         match &msg.payload {
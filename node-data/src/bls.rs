@@ -224,6 +224,10 @@ fn decrypt(data: &[u8], pwd: &[u8]) -> Result<Vec<u8>, BlockModeError> {
 /// consensus keys.
 ///
 /// It reads $DUSK_CONSENSUS_KEYS_PASS var to unlock wallet files.
+///
+/// Wallet files are looked up by name (`node_0.keys` to `node_{n-1}.keys`)
+/// rather than by enumerating the directory, so the returned keys are always
+/// in the same order, regardless of the filesystem.
 pub fn load_provisioners_keys(n: usize) -> Vec<(BlsSecretKey, PublicKey)> {
     let mut keys = vec![];
 
@@ -518,4 +518,65 @@ mod tests {
     fn test_encoding_fault() {
         assert_serializable::<Fault>();
     }
+
+    /// Asserts that `obj` encodes to `expected` and decodes back to itself.
+    fn assert_stable_encoding<S: Eq + Serializable + std::fmt::Debug>(
+        obj: S,
+        expected: &[u8],
+    ) {
+        let mut buf = vec![];
+        obj.write(&mut buf).expect("should be writable");
+        assert_eq!(buf, expected);
+
+        let decoded = S::read(&mut &buf[..]).expect("should be readable");
+        assert_eq!(decoded, obj);
+    }
+
+    #[test]
+    fn test_encoding_vote_variants() {
+        let hash = [7u8; 32];
+
+        assert_stable_encoding(Vote::NoCandidate, &[0]);
+        assert_stable_encoding(Vote::Valid(hash), &[&[1], &hash[..]].concat());
+        assert_stable_encoding(
+            Vote::Invalid(hash),
+            &[&[2], &hash[..]].concat(),
+        );
+        assert_stable_encoding(Vote::NoQuorum, &[3]);
+    }
+
+    #[test]
+    fn test_encoding_ratification_result_variants() {
+        let hash = [7u8; 32];
+
+        assert_stable_encoding(
+            RatificationResult::Success(Vote::Valid(hash)),
+            &[&[1, 1], &hash[..]].concat(),
+        );
+        assert_stable_encoding(
+            RatificationResult::Fail(Vote::Invalid(hash)),
+            &[&[0, 2], &hash[..]].concat(),
+        );
+        assert_stable_encoding(
+            RatificationResult::Fail(Vote::NoQuorum),
+            &[0, 3],
+        );
+    }
+
+    #[test]
+    fn test_decoding_invalid_vote() {
+        // Valid vote with a truncated hash
+        let buf = [&[1], &[7u8; 31][..]].concat();
+        let err = Vote::read(&mut &buf[..]).expect_err("should fail");
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+
+        // Unknown vote discriminant
+        let err = Vote::read(&mut &[4][..]).expect_err("should fail");
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+
+        // Unknown ratification result discriminant
+        let err = RatificationResult::read(&mut &[2, 3][..])
+            .expect_err("should fail");
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
 }
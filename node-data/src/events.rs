@@ -5,11 +5,13 @@
 // Copyright (c) DUSK NETWORK. All rights reserved.
 
 mod blocks;
+mod consensus;
 mod transactions;
 
 pub mod contract;
 
 pub use blocks::{BlockEvent, BlockState};
+pub use consensus::ConsensusEvent;
 pub use transactions::TransactionEvent;
 
 /// Represents an event in the system, including its source (`component`),
@@ -0,0 +1,64 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use super::*;
+use crate::StepName;
+
+/// Represents events related to the consensus loop.
+///
+/// - `Progress`
+///
+///     The consensus loop entered a new step. Progress events are coalesced,
+///     so that subscribers only observe the latest step when they fall
+///     behind.
+///
+///     - `round: u64` The round being executed, also used as entity.
+///     - `iteration: u8` The iteration within the round.
+///     - `step: StepName` The step entered.
+#[derive(Clone, Debug)]
+pub enum ConsensusEvent {
+    Progress {
+        round: u64,
+        iteration: u8,
+        step: StepName,
+    },
+}
+
+impl EventSource for ConsensusEvent {
+    const COMPONENT: &'static str = "consensus";
+
+    fn topic(&self) -> &'static str {
+        match self {
+            Self::Progress { .. } => "progress",
+        }
+    }
+    fn data(&self) -> Option<serde_json::Value> {
+        let data = match self {
+            Self::Progress {
+                round,
+                iteration,
+                step,
+            } => {
+                let step = match step {
+                    StepName::Proposal => "proposal",
+                    StepName::Validation => "validation",
+                    StepName::Ratification => "ratification",
+                };
+                serde_json::json!({
+                    "round": round,
+                    "iteration": iteration,
+                    "step": step,
+                })
+            }
+        };
+        Some(data)
+    }
+    fn entity(&self) -> String {
+        match self {
+            Self::Progress { round, .. } => round.to_string(),
+        }
+    }
+}
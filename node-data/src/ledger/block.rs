@@ -4,7 +4,10 @@
 //
 // Copyright (c) DUSK NETWORK. All rights reserved.
 
+use std::mem;
+
 use super::*;
+use crate::message::payload::{RatificationResult, Vote};
 
 pub type Hash = [u8; 32];
 pub type Bloom = [u8; 256];
@@ -67,17 +70,74 @@ impl Block {
         self.header.att = att;
     }
 
+    /// Builds the full block by attaching the attestation to the candidate.
+    ///
+    /// Fails if the attestation is not a successful one for the candidate.
+    pub fn from_candidate_and_attestation(
+        mut candidate: Block,
+        att: Attestation,
+    ) -> io::Result<Self> {
+        match att.result {
+            RatificationResult::Success(Vote::Valid(hash))
+                if hash == candidate.header.hash =>
+            {
+                candidate.set_attestation(att);
+                Ok(candidate)
+            }
+            _ => Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "attestation does not match the candidate",
+            )),
+        }
+    }
+
     pub fn size(&self) -> io::Result<usize> {
         let mut buf = vec![];
         self.write(&mut buf)?;
         Ok(buf.len())
     }
 
+    /// Returns the serialized size of each component of the block
+    pub fn size_breakdown(&self) -> io::Result<SizeBreakdown> {
+        let header = self.header.size()?;
+
+        // Both transactions and faults are prefixed by their count as u32
+        let mut txs = mem::size_of::<u32>();
+        for tx in &self.txs {
+            let mut buf = vec![];
+            tx.write(&mut buf)?;
+            txs += buf.len();
+        }
+
+        let mut faults = mem::size_of::<u32>();
+        for fault in &self.faults {
+            let mut buf = vec![];
+            fault.write(&mut buf)?;
+            faults += buf.len();
+        }
+
+        Ok(SizeBreakdown {
+            header,
+            txs,
+            faults,
+            total: header + txs + faults,
+        })
+    }
+
     pub fn set_signature(&mut self, signature: Signature) {
         self.header.signature = signature;
     }
 }
 
+/// Serialized size of the components of a block, in bytes
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SizeBreakdown {
+    pub header: usize,
+    pub txs: usize,
+    pub faults: usize,
+    pub total: usize,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum Label {
     Accepted(u64),
@@ -130,3 +190,59 @@ pub mod faker {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_candidate_and_attestation() {
+        let candidate = Block::new(Header::default(), vec![], vec![])
+            .expect("block to be created");
+        let hash = candidate.header().hash;
+
+        let att = Attestation {
+            result: RatificationResult::Success(Vote::Valid(hash)),
+            ..Default::default()
+        };
+        let blk = Block::from_candidate_and_attestation(candidate.clone(), att)
+            .expect("attestation to match");
+        assert_eq!(blk.header().att, att);
+
+        // An attestation for a different candidate is rejected
+        let att = Attestation {
+            result: RatificationResult::Success(Vote::Valid([1; 32])),
+            ..Default::default()
+        };
+        assert!(
+            Block::from_candidate_and_attestation(candidate.clone(), att)
+                .is_err()
+        );
+
+        // A failed attestation is rejected
+        let att = Attestation {
+            result: RatificationResult::Fail(Vote::Invalid(hash)),
+            ..Default::default()
+        };
+        assert!(Block::from_candidate_and_attestation(candidate, att).is_err());
+    }
+
+    #[test]
+    fn test_size_breakdown() {
+        let candidate: Block = Faker.fake();
+        let breakdown = candidate.size_breakdown().expect("valid block");
+
+        assert_eq!(
+            breakdown.header + breakdown.txs + breakdown.faults,
+            breakdown.total
+        );
+        assert_eq!(breakdown.total, candidate.size().expect("valid block"));
+
+        let empty = Block::new(Header::default(), vec![], vec![])
+            .expect("block to be created");
+        let breakdown = empty.size_breakdown().expect("valid block");
+        assert_eq!(breakdown.txs, mem::size_of::<u32>());
+        assert_eq!(breakdown.faults, mem::size_of::<u32>());
+        assert_eq!(breakdown.total, empty.size().expect("valid block"));
+    }
+}
@@ -68,6 +68,8 @@ pub enum InvalidFault {
     EmergencyIteration,
     #[error("Round mismatch")]
     RoundMismatch,
+    #[error("Culprit is not a provisioner")]
+    NotProvisioner,
     #[error("Invalid Signature {0}")]
     InvalidSignature(BlsSigError),
     #[error("Generic error {0}")]
@@ -128,7 +130,8 @@ impl Fault {
         }
     }
 
-    fn to_culprit(&self) -> PublicKey {
+    /// Returns the provisioner accused by the fault
+    pub fn to_culprit(&self) -> PublicKey {
         match self {
             Fault::DoubleRatificationVote(a, _)
             | Fault::DoubleValidationVote(a, _) => a.sig.signer.clone(),
@@ -136,6 +139,11 @@ impl Fault {
         }
     }
 
+    /// Returns the round the fault refers to
+    pub fn round(&self) -> u64 {
+        self.consensus_header().0.round
+    }
+
     /// Get the ConsensusHeader related to the inner FaultDatas
     fn consensus_header(&self) -> (&ConsensusHeader, &ConsensusHeader) {
         match self {
@@ -34,7 +34,17 @@ pub const BLOCK_HEADER_VERSION: u8 = 1;
 /// Max value for failed iterations.
 pub const MESSAGE_MAX_FAILED_ITERATIONS: u8 = 8;
 
-#[derive(Debug, Clone)]
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    serde::Serialize,
+    serde::Deserialize,
+)]
 /// Represent version (major, minor, patch)
 pub struct Version(pub u8, pub u16, pub u16);
 
@@ -185,6 +195,8 @@ pub struct Metadata {
     pub height: u8,
     pub src_addr: SocketAddr,
     pub ray_id: String,
+    /// Protocol version advertised by the sender
+    pub version: Version,
 }
 
 impl Serializable for Message {
@@ -333,6 +345,19 @@ impl Message {
     pub fn topic(&self) -> Topics {
         self.topic
     }
+
+    /// Decodes a message received from the wire.
+    ///
+    /// Blobs larger than `max_size` are rejected before any parsing.
+    pub fn from_wire(blob: &[u8], max_size: usize) -> io::Result<Self> {
+        if blob.len() > max_size {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("message too large: {} > {max_size}", blob.len()),
+            ));
+        }
+        Self::read(&mut &blob[..])
+    }
 }
 
 #[derive(Default, Clone, PartialEq, Eq, Copy)]
@@ -564,6 +589,18 @@ pub mod payload {
             };
             ENUM_BYTE + data_size
         }
+
+        /// Reads the candidate hash of a vote, failing if fewer than 32 bytes
+        /// are available.
+        fn read_hash<R: Read>(r: &mut R) -> io::Result<Hash> {
+            Self::read_bytes(r).map_err(|e| match e.kind() {
+                io::ErrorKind::UnexpectedEof => io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "Invalid vote hash length",
+                ),
+                _ => e,
+            })
+        }
     }
 
     impl fmt::Debug for Vote {
@@ -602,8 +639,8 @@ pub mod payload {
         {
             Ok(match Self::read_u8(r)? {
                 0 => Self::NoCandidate,
-                1 => Self::Valid(Self::read_bytes(r)?),
-                2 => Self::Invalid(Self::read_bytes(r)?),
+                1 => Self::Valid(Self::read_hash(r)?),
+                2 => Self::Invalid(Self::read_hash(r)?),
                 3 => Self::NoQuorum,
                 _ => Err(io::Error::new(
                     io::ErrorKind::InvalidData,
@@ -1444,6 +1481,70 @@ impl<M: Clone> AsyncQueue<M> {
     pub fn recv(&self) -> async_channel::Recv<'_, M> {
         self.receiver.recv()
     }
+
+    /// Removes and returns all the messages currently in the queue, without
+    /// waiting for new ones.
+    pub fn drain(&self) -> Vec<M> {
+        let mut msgs = vec![];
+        while let Ok(msg) = self.receiver.try_recv() {
+            msgs.push(msg);
+        }
+        msgs
+    }
+}
+
+impl AsyncQueue<Message> {
+    /// Drains the queue and serializes all pending wire messages.
+    ///
+    /// Internal messages, that are never sent on the wire, are discarded.
+    pub fn drain_to_bytes(&self) -> io::Result<Vec<u8>> {
+        let msgs: Vec<_> = self
+            .drain()
+            .into_iter()
+            .filter(|m| {
+                !matches!(
+                    m.payload,
+                    Payload::Empty | Payload::ValidationResult(_)
+                )
+            })
+            .collect();
+
+        let mut buf = vec![];
+        buf.write_all(&(msgs.len() as u32).to_le_bytes())?;
+        for msg in msgs {
+            let mut msg_buf = vec![];
+            msg.write(&mut msg_buf)?;
+            Message::write_var_le_bytes32(&mut buf, &msg_buf)?;
+        }
+        Ok(buf)
+    }
+
+    /// Enqueues the messages previously serialized with
+    /// [`AsyncQueue::drain_to_bytes`].
+    ///
+    /// Returns the number of restored messages. The messages exceeding the
+    /// queue capacity are dropped.
+    pub fn restore_from_bytes(&self, mut bytes: &[u8]) -> io::Result<usize> {
+        let r = &mut bytes;
+        let len = Message::read_u32_le(r)?;
+        let mut restored = 0;
+        for _ in 0..len {
+            let msg_buf = Message::read_var_le_bytes32(r)?;
+            let msg = Message::read(&mut &msg_buf[..])?;
+            if self.sender.try_send(msg).is_ok() {
+                restored += 1;
+            }
+        }
+
+        let dropped = len as usize - restored;
+        if dropped > 0 {
+            warn!(
+                "queue ({}) cannot restore {dropped} messages, cap: {}",
+                self.label, self.cap
+            );
+        }
+        Ok(restored)
+    }
 }
 
 pub trait StepMessage {
@@ -1707,6 +1808,100 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_from_wire_max_size() {
+        let msg: Message = payload::Validation {
+            header: ConsensusHeader::default(),
+            vote: payload::Vote::Valid([5; 32]),
+            sign_info: SignInfo {
+                signer: bls::PublicKey::from_sk_seed_u64(1),
+                signature: [4; 48].into(),
+            },
+        }
+        .into();
+        let mut blob = vec![];
+        msg.write(&mut blob).expect("serialize should succeed");
+
+        assert!(Message::from_wire(&blob, blob.len()).is_ok());
+
+        let err = Message::from_wire(&blob, blob.len() - 1)
+            .expect_err("oversized message should be rejected");
+        assert!(err.to_string().starts_with("message too large"));
+
+        // An oversized blob is rejected without being parsed
+        let garbage = vec![0xff; 1024];
+        let err = Message::from_wire(&garbage, 512)
+            .expect_err("oversized message should be rejected");
+        assert!(err.to_string().starts_with("message too large"));
+    }
+
+    #[test]
+    fn test_drain_and_restore_queue() {
+        let queue = AsyncQueue::bounded(10, "test_inbound");
+
+        let header = ConsensusHeader {
+            iteration: 2,
+            prev_block_hash: [3; 32],
+            round: 7,
+        };
+        let sign_info = SignInfo {
+            signer: bls::PublicKey::from_sk_seed_u64(1),
+            signature: [4; 48].into(),
+        };
+
+        let msgs: Vec<Message> = vec![
+            payload::Validation {
+                header,
+                vote: payload::Vote::Valid([5; 32]),
+                sign_info: sign_info.clone(),
+            }
+            .into(),
+            payload::Ratification {
+                header,
+                vote: payload::Vote::NoCandidate,
+                sign_info,
+                validation_result: Default::default(),
+                timestamp: 0,
+            }
+            .into(),
+        ];
+        for msg in &msgs {
+            queue.try_send(msg.clone());
+        }
+        // Internal messages are not persisted
+        queue.try_send(Message::default());
+
+        let bytes = queue.drain_to_bytes().expect("drain should succeed");
+        assert!(queue.drain().is_empty());
+
+        let restored = AsyncQueue::bounded(10, "test_inbound");
+        let count = restored
+            .restore_from_bytes(&bytes)
+            .expect("restore should succeed");
+        assert_eq!(count, msgs.len());
+
+        let to_bytes = |m: &Message| {
+            let mut buf = vec![];
+            m.write(&mut buf).expect("serialize should succeed");
+            buf
+        };
+        let restored = restored.drain();
+        assert_eq!(restored.len(), msgs.len());
+        for (orig, restored) in msgs.iter().zip(restored.iter()) {
+            assert_eq!(to_bytes(orig), to_bytes(restored));
+        }
+
+        // Only the messages fitting in the queue are restored
+        let restored = AsyncQueue::bounded(1, "test_inbound");
+        let count = restored
+            .restore_from_bytes(&bytes)
+            .expect("restore should succeed");
+        assert_eq!(count, 1);
+        let restored = restored.drain();
+        assert_eq!(restored.len(), 1);
+        assert_eq!(to_bytes(&msgs[0]), to_bytes(&restored[0]));
+    }
+
     fn assert_serialize<S: Serializable + PartialEq + core::fmt::Debug>(v: S) {
         let mut buf = vec![];
         assert!(v.write(&mut buf).is_ok());
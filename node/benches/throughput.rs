@@ -0,0 +1,305 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! Measures the number of blocks per second that can go through the
+//! acceptance pipeline of the node: wire decoding, header and attestation
+//! verification, rolling finality and persistence of the block in the ledger.
+//!
+//! The state transition is not executed, as it is measured by the VM
+//! benchmarks.
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use criterion::{
+    criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion,
+    Throughput,
+};
+use dusk_bytes::Serializable as DuskSerializable;
+use dusk_consensus::commons::RoundUpdate;
+use dusk_consensus::config::MINIMUM_BLOCK_TIME;
+use dusk_consensus::merkle::merkle_root;
+use dusk_consensus::user::cluster::Cluster;
+use dusk_consensus::user::committee::Committee;
+use dusk_consensus::user::provisioners::Provisioners;
+use dusk_consensus::user::sortition::Config as SortitionConfig;
+use dusk_core::signatures::bls::{
+    MultisigSignature as BlsMultisigSignature, PublicKey as BlsPublicKey,
+    SecretKey as BlsSecretKey,
+};
+use node::chain::bench::BenchAcceptor;
+use node::database::rocksdb::Backend;
+use node::database::{DatabaseOptions, Ledger, DB};
+use node_data::bls::PublicKey;
+use node_data::ledger::{
+    self, Attestation, Block, BlockWithLabel, Header, Label, Seed, StepVotes,
+};
+use node_data::message::payload::{
+    QuorumType, RatificationResult, ValidationResult, Vote,
+};
+use node_data::message::BLOCK_HEADER_VERSION;
+use node_data::{get_current_timestamp, Serializable, StepName};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use tempdir::TempDir;
+
+/// Number of blocks of the pre-built chain
+const CHAIN_LEN: u64 = 50;
+
+/// Number of provisioners attesting the blocks
+const PROVISIONERS: usize = 64;
+
+struct Input {
+    txs: usize,
+    measurement_time: u64, // secs
+}
+
+const INPUTS: &[Input] = &[
+    Input {
+        txs: 1,
+        measurement_time: 10,
+    },
+    Input {
+        txs: 500,
+        measurement_time: 30,
+    },
+];
+
+type Keys = Vec<(PublicKey, BlsSecretKey)>;
+
+fn provisioners() -> (Provisioners, Keys) {
+    let rng = &mut StdRng::seed_from_u64(0xbeef);
+    let mut provisioners = Provisioners::empty();
+    let keys = (0..PROVISIONERS)
+        .map(|_| {
+            let sk = BlsSecretKey::random(rng);
+            let pk = PublicKey::new(BlsPublicKey::from(&sk));
+            provisioners.add_member_with_value(pk.clone(), 1_000_000_000_000);
+            (pk, sk)
+        })
+        .collect();
+    (provisioners, keys)
+}
+
+/// Aggregates the votes cast by the `keys` in the committee of `step`
+fn step_votes(
+    prev: &Header,
+    vote: Vote,
+    step: StepName,
+    provisioners: &Provisioners,
+    keys: &Keys,
+) -> StepVotes {
+    let round = prev.height + 1;
+    let generator = provisioners.get_generator(0, prev.seed, round);
+    let next_generator = provisioners.get_generator(1, prev.seed, round);
+    let cfg = SortitionConfig::new(
+        prev.seed,
+        round,
+        0,
+        step,
+        vec![generator, next_generator],
+    );
+    let committee = Committee::new(provisioners, &cfg);
+
+    let mut signatures = vec![];
+    let mut cluster = Cluster::<PublicKey>::default();
+    for (pk, sk) in keys {
+        let Some(weight) = committee.votes_for(pk) else {
+            continue;
+        };
+        let ru = RoundUpdate::new(
+            pk.clone(),
+            sk.clone(),
+            prev,
+            HashMap::default(),
+            vec![],
+        );
+        let signature = match step {
+            StepName::Validation => {
+                dusk_consensus::build_validation_payload(vote, &ru, 0)
+                    .sign_info
+                    .signature
+            }
+            _ => {
+                let result = ValidationResult::new(
+                    StepVotes::default(),
+                    vote,
+                    QuorumType::Valid,
+                );
+                dusk_consensus::build_ratification_payload(&ru, 0, &result)
+                    .sign_info
+                    .signature
+            }
+        };
+        signatures.push(
+            BlsMultisigSignature::from_bytes(signature.inner())
+                .expect("signature to be valid"),
+        );
+        cluster.add(pk, weight);
+    }
+
+    let (first, rest) = signatures.split_first().expect("committee to vote");
+    StepVotes::new(first.aggregate(rest).to_bytes(), committee.bits(&cluster))
+}
+
+/// Builds a block on top of `prev` with the given `txs`, attested by all the
+/// provisioners at the first iteration
+fn attested_block(
+    prev: &Block,
+    txs: usize,
+    provisioners: &Provisioners,
+    keys: &Keys,
+) -> Block {
+    let prev_header = prev.header();
+    let height = prev_header.height + 1;
+    let generator = provisioners.get_generator(0, prev_header.seed, height);
+    let (pk, sk) = keys
+        .iter()
+        .find(|(pk, _)| pk.bytes() == &generator)
+        .expect("generator to be known");
+
+    let txs: Vec<_> = (0..txs)
+        .map(|i| ledger::faker::gen_dummy_tx(height + i as u64))
+        .collect();
+    let tx_digests: Vec<_> = txs.iter().map(|t| t.digest()).collect();
+
+    let seed = sk.sign_multisig(pk.inner(), prev_header.seed.inner());
+    let header = Header {
+        version: BLOCK_HEADER_VERSION,
+        height,
+        timestamp: prev_header.timestamp + *MINIMUM_BLOCK_TIME,
+        prev_block_hash: prev_header.hash,
+        seed: Seed::from(seed.to_bytes()),
+        generator_bls_pubkey: generator,
+        txroot: merkle_root(&tx_digests[..]),
+        faultroot: merkle_root::<[u8; 32]>(&[]),
+        prev_block_cert: prev_header.att,
+        ..Default::default()
+    };
+    let mut blk = Block::new(header, txs, vec![]).expect("valid block");
+    let hash = blk.header().hash;
+    let signature = sk.sign_multisig(pk.inner(), &hash);
+    blk.set_signature(signature.to_bytes().into());
+
+    let vote = Vote::Valid(hash);
+    let validation =
+        step_votes(prev_header, vote, StepName::Validation, provisioners, keys);
+    let ratification = step_votes(
+        prev_header,
+        vote,
+        StepName::Ratification,
+        provisioners,
+        keys,
+    );
+    blk.set_attestation(Attestation {
+        result: RatificationResult::Success(vote),
+        validation,
+        ratification,
+    });
+    blk
+}
+
+/// Builds a chain of `CHAIN_LEN` blocks with `txs` transactions each on top
+/// of `genesis`, encoded as they are received from the wire.
+fn build_chain(
+    genesis: &Block,
+    txs: usize,
+    provisioners: &Provisioners,
+    keys: &Keys,
+) -> Vec<Vec<u8>> {
+    let mut prev = genesis.clone();
+    (1..=CHAIN_LEN)
+        .map(|_| {
+            let blk = attested_block(&prev, txs, provisioners, keys);
+            let mut buf = vec![];
+            blk.write(&mut buf).expect("block to be encoded");
+            prev = blk;
+            buf
+        })
+        .collect()
+}
+
+/// Creates a genesis block produced early enough for the whole chain to be
+/// in the past
+fn genesis() -> Block {
+    let header = Header {
+        timestamp: get_current_timestamp()
+            - (CHAIN_LEN + 1) * *MINIMUM_BLOCK_TIME,
+        seed: [5; 48].into(),
+        ..Default::default()
+    };
+    Block::new(header, vec![], vec![]).expect("valid block")
+}
+
+async fn accept(acceptor: &mut BenchAcceptor<Backend>, chain: &[Vec<u8>]) {
+    for bytes in chain {
+        let blk = Block::read(&mut &bytes[..]).expect("block to be decoded");
+        acceptor.accept(&blk).await.expect("block to be accepted");
+    }
+}
+
+fn accept_blocks(c: &mut Criterion) {
+    let mut group = c.benchmark_group("accept_blocks");
+    group.sample_size(10);
+    group.throughput(Throughput::Elements(CHAIN_LEN));
+
+    let rt = tokio::runtime::Runtime::new().expect("runtime to be created");
+    let (provisioners, keys) = provisioners();
+    let genesis = genesis();
+
+    for input in INPUTS {
+        group.measurement_time(Duration::from_secs(input.measurement_time));
+        let chain = build_chain(&genesis, input.txs, &provisioners, &keys);
+
+        group.bench_with_input(
+            BenchmarkId::new("accept_blocks", format!("{} txs", input.txs)),
+            &chain,
+            |b, chain| {
+                b.iter_batched(
+                    || {
+                        let dir = TempDir::new("accept_blocks")
+                            .expect("temp dir to be created");
+                        let db = Backend::create_or_open(
+                            dir.path(),
+                            DatabaseOptions::default(),
+                        );
+                        db.update(|t| {
+                            t.store_block(
+                                genesis.header(),
+                                &[],
+                                &[],
+                                Label::Final(0),
+                            )
+                        })
+                        .expect("genesis to be stored");
+
+                        let tip = BlockWithLabel::new_with_label(
+                            genesis.clone(),
+                            Label::Final(0),
+                        );
+                        let node_keys = keys[0].clone();
+                        let acceptor = BenchAcceptor::new(
+                            db,
+                            tip,
+                            provisioners.clone(),
+                            (node_keys.1, node_keys.0),
+                        );
+                        (dir, acceptor)
+                    },
+                    |(_dir, mut acceptor)| {
+                        rt.block_on(accept(&mut acceptor, chain))
+                    },
+                    BatchSize::PerIteration,
+                )
+            },
+        );
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, accept_blocks);
+criterion_main!(benches);
@@ -5,6 +5,9 @@
 // Copyright (c) DUSK NETWORK. All rights reserved.
 
 mod acceptor;
+#[cfg(feature = "bench")]
+pub mod bench;
+pub mod conf;
 mod consensus;
 mod fallback;
 mod fsm;
@@ -12,27 +15,41 @@ mod genesis;
 
 mod header_validation;
 mod metrics;
+mod skew;
+mod timing;
 
+use std::net::SocketAddr;
 use std::ops::Deref;
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, SystemTime};
 
 use anyhow::Result;
 use async_trait::async_trait;
-use dusk_consensus::config::is_emergency_block;
+use dusk_consensus::config::{
+    is_emergency_block, ConsensusConfig, MINIMUM_BLOCK_TIME,
+};
 use dusk_consensus::errors::ConsensusError;
+use dusk_consensus::progress::{ConsensusProgress, ProgressNotifier};
 pub use header_validation::verify_att;
-use node_data::events::Event;
-use node_data::ledger::{to_str, BlockWithLabel, Label};
-use node_data::message::payload::RatificationResult;
+use node_data::events::{ConsensusEvent, Event};
+use node_data::ledger::{to_str, BlockWithLabel, Header, Label};
+use node_data::message::payload::{
+    GetResource, Inv, InvType, RatificationResult,
+};
 use node_data::message::{AsyncQueue, Payload, Topics};
+use tokio::signal::unix::{signal, SignalKind};
 use tokio::sync::mpsc::Sender;
-use tokio::sync::RwLock;
+use tokio::sync::{broadcast, watch, RwLock};
 use tokio::time::{sleep_until, Instant};
-use tracing::{debug, error, info, warn};
+use tracing::{debug, error, info, trace, warn};
 
-use self::acceptor::Acceptor;
+pub use self::acceptor::AcceptError;
+use self::acceptor::{Acceptor, AcceptorNotifiers, RevertNotifier};
 use self::fsm::SimpleFSM;
+pub use self::metrics::ConsensusMetrics;
+use self::metrics::ConsensusMetricsTracker;
+use self::skew::ClockSkewDetector;
+pub use self::timing::{BlockForecast, HeightClock};
 use crate::database::rocksdb::MD_HASH_KEY;
 use crate::database::{Ledger, Metadata};
 use crate::{database, vm, LongLivedService, Message, Network};
@@ -46,8 +63,6 @@ const TOPICS: &[u8] = &[
     Topics::ValidationQuorum as u8,
 ];
 
-const HEARTBEAT_SEC: Duration = Duration::from_secs(3);
-
 pub struct ChainSrv<N: Network, DB: database::DB, VM: vm::VMExecution> {
     /// Inbound wire messages queue
     inbound: AsyncQueue<Message>,
@@ -57,6 +72,13 @@ pub struct ChainSrv<N: Network, DB: database::DB, VM: vm::VMExecution> {
     /// Sender channel for sending out RUES events
     event_sender: Sender<Event>,
     genesis_timestamp: u64,
+    conf: conf::Params,
+    revert_notifier: RevertNotifier,
+    consensus_metrics: ConsensusMetricsTracker,
+    consensus_progress: ProgressNotifier,
+    /// Trusted finalized checkpoint to bootstrap from, as its state root and
+    /// header
+    checkpoint: Option<([u8; 32], Header)>,
 }
 
 #[async_trait]
@@ -69,28 +91,56 @@ impl<N: Network, DB: database::DB, VM: vm::VMExecution>
         db: Arc<RwLock<DB>>,
         vm: Arc<RwLock<VM>>,
     ) -> anyhow::Result<()> {
-        let tip = Self::load_tip(
-            db.read().await.deref(),
-            vm.read().await.deref(),
-            self.genesis_timestamp,
-        )
-        .await?;
-
-        let state_hash = tip.inner().header().state_hash;
-        let provisioners_list = vm.read().await.get_provisioners(state_hash)?;
+        ConsensusConfig::current().validate()?;
 
         // Initialize Acceptor
-        let acc = Acceptor::init_consensus(
-            &self.keys_path,
-            tip,
-            provisioners_list,
-            db,
-            network,
-            vm,
-            self.max_consensus_queue_size,
-            self.event_sender.clone(),
-        )
-        .await?;
+        let acc = match self.checkpoint.take() {
+            Some((state_root, header)) => {
+                ClockSkewDetector::new(self.conf.clock_skew_threshold)
+                    .check_tip(header.timestamp);
+
+                Acceptor::from_checkpoint(
+                    state_root,
+                    header,
+                    &self.keys_path,
+                    db,
+                    network,
+                    vm,
+                    self.max_consensus_queue_size,
+                    self.conf,
+                    self.notifiers(),
+                )
+                .await?
+            }
+            None => {
+                let tip = Self::load_tip(
+                    db.read().await.deref(),
+                    vm.read().await.deref(),
+                    self.genesis_timestamp,
+                )
+                .await?;
+
+                ClockSkewDetector::new(self.conf.clock_skew_threshold)
+                    .check_tip(tip.inner().header().timestamp);
+
+                let state_hash = tip.inner().header().state_hash;
+                let provisioners_list =
+                    vm.read().await.get_provisioners(state_hash)?;
+
+                Acceptor::init_consensus(
+                    &self.keys_path,
+                    tip,
+                    provisioners_list,
+                    db,
+                    network,
+                    vm,
+                    self.max_consensus_queue_size,
+                    self.conf,
+                    self.notifiers(),
+                )
+                .await?
+            }
+        };
 
         self.acceptor = Some(Arc::new(RwLock::new(acc)));
 
@@ -112,21 +162,37 @@ impl<N: Network, DB: database::DB, VM: vm::VMExecution>
         )
         .await?;
 
-        let acc = self.acceptor.as_mut().expect("initialize is called");
+        let acc = self.acceptor.clone().expect("initialize is called");
         acc.write().await.spawn_task().await;
 
+        // Stream the progress of the consensus loop to the RUES subscribers
+        tokio::spawn(publish_progress(
+            self.consensus_progress.subscribe(),
+            self.event_sender.clone(),
+        ));
+
         // Start-up FSM instance
-        let mut fsm = SimpleFSM::new(acc.clone(), network.clone()).await;
+        let mut fsm =
+            SimpleFSM::new(acc.clone(), network.clone(), self.conf).await;
 
         let outbound_chan = acc.read().await.get_outbound_chan().await;
         let result_chan = acc.read().await.get_result_chan().await;
 
-        let mut heartbeat = Instant::now().checked_add(HEARTBEAT_SEC).unwrap();
+        let mut heartbeat = Instant::now()
+            .checked_add(self.conf.heartbeat_interval)
+            .unwrap();
+
+        let mut shutdown = signal(SignalKind::interrupt())?;
 
         // Message loop for Chain context
         loop {
             tokio::select! {
                 biased;
+                // Stores the pending consensus messages before shutting down
+                _ = shutdown.recv() => {
+                    acc.read().await.persist_inbound().await;
+                    return Ok(2);
+                },
                 // Receives results from the upper layer
                 recv = result_chan.recv() => {
                     match recv? {
@@ -215,7 +281,16 @@ impl<N: Network, DB: database::DB, VM: vm::VMExecution>
                     }
 
                     if let Payload::GetResource(res) = &msg.payload {
-                        if let Err(e) = network.read().await.flood_request(res.get_inv(), None, 16).await {
+                        let net = network.read().await;
+                        let inv = res.get_inv();
+                        let sent = match request_target(inv, self.conf.candidate_request_peer) {
+                            Some(peer) => {
+                                let req = GetResource::new(inv.clone(), Some(*net.public_addr()), u64::MAX, 1);
+                                net.send_to_peer(req.into(), peer).await
+                            }
+                            None => net.flood_request(inv, None, 16).await,
+                        };
+                        if let Err(e) = sent {
                             warn!("Unable to re-route message {e}");
                         }
                     } else if let Err(e) = network.read().await.broadcast(&msg).await {
@@ -229,7 +304,26 @@ impl<N: Network, DB: database::DB, VM: vm::VMExecution>
                         error!(event = "heartbeat_failed", ?err);
                     }
 
-                    heartbeat = Instant::now().checked_add(HEARTBEAT_SEC).unwrap();
+                    if let Some((present, missing)) = fsm.pool_status() {
+                        debug!(event = "sync pool status", ?present, ?missing);
+                    }
+
+                    let status = fsm.sync_status();
+                    if let Some(target) = status.target() {
+                        info!(event = "sync progress", target, remaining = status.remaining());
+                    }
+
+                    let blacklisted: Vec<_> = fsm
+                        .blacklisted_blocks()
+                        .await
+                        .into_iter()
+                        .map(|(hash, reason)| (to_str(&hash), reason))
+                        .collect();
+                    if !blacklisted.is_empty() {
+                        trace!(event = "blacklisted blocks", ?blacklisted);
+                    }
+
+                    heartbeat = Instant::now().checked_add(self.conf.heartbeat_interval).unwrap();
                 },
             }
         }
@@ -239,6 +333,12 @@ impl<N: Network, DB: database::DB, VM: vm::VMExecution>
     fn name(&self) -> &'static str {
         "chain"
     }
+
+    /// The pending consensus messages are persisted, if enabled, before
+    /// returning on SIGINT
+    fn handles_shutdown(&self) -> bool {
+        true
+    }
 }
 
 impl<N: Network, DB: database::DB, VM: vm::VMExecution> ChainSrv<N, DB, VM> {
@@ -247,6 +347,7 @@ impl<N: Network, DB: database::DB, VM: vm::VMExecution> ChainSrv<N, DB, VM> {
         max_inbound_size: usize,
         event_sender: Sender<Event>,
         genesis_timestamp: u64,
+        conf: conf::Params,
     ) -> Self {
         info!(
             "ChainSrv::new with keys_path: {}, max_inbound_size: {}",
@@ -260,9 +361,87 @@ impl<N: Network, DB: database::DB, VM: vm::VMExecution> ChainSrv<N, DB, VM> {
             max_consensus_queue_size: max_inbound_size,
             event_sender,
             genesis_timestamp,
+            conf,
+            revert_notifier: RevertNotifier::default(),
+            consensus_metrics: ConsensusMetricsTracker::default(),
+            consensus_progress: ProgressNotifier::default(),
+            checkpoint: None,
         }
     }
 
+    /// Bootstraps the chain from a trusted finalized checkpoint instead of
+    /// the tip persisted in the ledger.
+    ///
+    /// The state `state_root` of the checkpoint `header` must be present in
+    /// the VM.
+    pub fn with_checkpoint(
+        mut self,
+        state_root: [u8; 32],
+        header: Header,
+    ) -> Self {
+        self.checkpoint = Some((state_root, header));
+        self
+    }
+
+    /// Subscribes to the notifications of state reverts.
+    ///
+    /// Each notification carries the state root the VM has been reverted to.
+    pub fn subscribe_reverts(&self) -> broadcast::Receiver<[u8; 32]> {
+        self.revert_notifier.subscribe()
+    }
+
+    /// Returns the channels the acceptor notifies the rest of the node
+    /// through
+    fn notifiers(&self) -> AcceptorNotifiers {
+        AcceptorNotifiers {
+            event_sender: self.event_sender.clone(),
+            revert_notifier: self.revert_notifier.clone(),
+            consensus_metrics: self.consensus_metrics.clone(),
+            consensus_progress: self.consensus_progress.clone(),
+        }
+    }
+
+    /// Returns a snapshot of the current consensus health.
+    pub fn consensus_metrics_snapshot(&self) -> ConsensusMetrics {
+        self.consensus_metrics.snapshot()
+    }
+
+    /// Returns the clock converting heights to approximate times, assuming
+    /// blocks are produced every `MINIMUM_BLOCK_TIME` since genesis.
+    pub fn height_clock(&self) -> HeightClock {
+        HeightClock::new(
+            self.genesis_timestamp,
+            Duration::from_secs(*MINIMUM_BLOCK_TIME),
+        )
+    }
+
+    /// Returns the approximate time at which the block at `height` is
+    /// produced.
+    pub fn height_to_time(&self, height: u64) -> SystemTime {
+        self.height_clock().height_to_time(height)
+    }
+
+    /// Returns the approximate height of the block produced at `time`.
+    pub fn time_to_height(&self, time: SystemTime) -> u64 {
+        self.height_clock().time_to_height(time)
+    }
+
+    /// Forecasts the generator and the earliest production time of the
+    /// block following the current tip.
+    ///
+    /// Returns `None` if the chain has not been initialized yet.
+    pub async fn next_block_forecast(&self) -> Option<BlockForecast> {
+        let acc = self.acceptor.as_ref()?.read().await;
+        let tip = acc.tip_header().await;
+        let provisioners = acc.provisioners_list.read().await;
+
+        Some(BlockForecast::next(
+            &tip,
+            provisioners.current(),
+            Duration::from_secs(*MINIMUM_BLOCK_TIME),
+        ))
+    }
+
     /// Load both the chain tip and last finalized block from persisted ledger.
     ///
     /// Panics
@@ -328,7 +507,8 @@ impl<N: Network, DB: database::DB, VM: vm::VMExecution> ChainSrv<N, DB, VM> {
             .read()
             .await
             .try_revert(acceptor::RevertTarget::LastFinalizedState)
-            .await
+            .await?;
+        Ok(())
     }
 
     async fn reroute_acceptor(&self, msg: Message) {
@@ -346,3 +526,157 @@ impl<N: Network, DB: database::DB, VM: vm::VMExecution> ChainSrv<N, DB, VM> {
         }
     }
 }
+
+/// Returns the peer to which a resource request from Consensus is sent, or
+/// `None` if the request is flooded to the network.
+///
+/// Only requests of candidates by iteration are directed at `candidate_peer`.
+fn request_target(
+    inv: &Inv,
+    candidate_peer: Option<SocketAddr>,
+) -> Option<SocketAddr> {
+    let by_iteration = !inv.inv_list.is_empty()
+        && inv
+            .inv_list
+            .iter()
+            .all(|i| matches!(i.inv_type, InvType::CandidateFromIteration));
+
+    candidate_peer.filter(|_| by_iteration)
+}
+
+/// Publishes the progress of the consensus loop as RUES events.
+///
+/// Updates received while an event is waiting for room in the channel are
+/// coalesced, so that only the latest progress is published.
+async fn publish_progress(
+    mut progress: watch::Receiver<ConsensusProgress>,
+    event_sender: Sender<Event>,
+) {
+    while progress.changed().await.is_ok() {
+        let ConsensusProgress {
+            round,
+            iteration,
+            step,
+        } = *progress.borrow_and_update();
+        let event = ConsensusEvent::Progress {
+            round,
+            iteration,
+            step,
+        };
+        if event_sender.send(event.into()).await.is_err() {
+            break;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use node_data::message::ConsensusHeader;
+    use node_data::StepName;
+    use tokio::sync::mpsc;
+
+    use super::*;
+    use crate::chain::acceptor::tests::{
+        acceptor, genesis, provisioners, store_block, DummyNetwork, DummyVM,
+    };
+    use crate::database::rocksdb::Backend;
+    use crate::database::DatabaseOptions;
+
+    #[tokio::test]
+    async fn test_publish_progress() {
+        let (progress, watcher) = watch::channel(ConsensusProgress::default());
+        let (event_sender, mut events) = mpsc::channel(1);
+        tokio::spawn(publish_progress(watcher, event_sender));
+
+        let update = |iteration, step| {
+            progress.send_replace(ConsensusProgress {
+                round: 10,
+                iteration,
+                step,
+            });
+        };
+
+        update(0, StepName::Proposal);
+        let event = events.recv().await.expect("event to be published");
+        assert_eq!(event.component, "consensus");
+        assert_eq!(event.topic, "progress");
+        assert_eq!(event.entity, "10");
+        let data = event.data.expect("event to carry data");
+        assert_eq!(data["round"], 10);
+        assert_eq!(data["iteration"], 0);
+        assert_eq!(data["step"], "proposal");
+
+        // Updates are coalesced while the subscriber is not keeping up
+        update(0, StepName::Validation);
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        update(0, StepName::Ratification);
+        update(1, StepName::Proposal);
+
+        let event = events.recv().await.expect("event to be published");
+        assert_eq!(event.data.unwrap()["step"], "validation");
+        let event = events.recv().await.expect("event to be published");
+        let data = event.data.unwrap();
+        assert_eq!(data["iteration"], 1);
+        assert_eq!(data["step"], "proposal");
+        assert!(events.try_recv().is_err());
+
+        // Publishing stops once the consensus loop is gone
+        drop(progress);
+        assert!(events.recv().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_next_block_forecast() {
+        let dir = tempdir::TempDir::new("test_next_block_forecast")
+            .expect("Temp directory to be created");
+        let conf = conf::Params::default();
+        let (event_sender, _) = mpsc::channel(1);
+        let genesis = genesis();
+        let mut chain = ChainSrv::<DummyNetwork, Backend, DummyVM>::new(
+            String::new(),
+            16,
+            event_sender,
+            genesis.header().timestamp,
+            conf,
+        );
+
+        // No forecast before the chain is initialized
+        assert!(chain.next_block_forecast().await.is_none());
+
+        let (provisioners, _) = provisioners(1);
+        let db =
+            Backend::create_or_open(dir.path(), DatabaseOptions::default());
+        store_block(&db, &genesis, Label::Final(0));
+        let tip = BlockWithLabel::new_with_label(genesis, Label::Final(0));
+        let acc = acceptor(db, provisioners, tip, conf);
+        chain.acceptor = Some(Arc::new(RwLock::new(acc)));
+
+        let forecast = chain
+            .next_block_forecast()
+            .await
+            .expect("forecast once initialized");
+        assert_eq!(forecast.round, 1);
+    }
+
+    #[test]
+    fn test_candidate_request_target() {
+        let peer: SocketAddr = "10.0.0.1:9000".parse().unwrap();
+        let header = ConsensusHeader {
+            prev_block_hash: [1; 32],
+            round: 10,
+            iteration: 16,
+        };
+
+        let mut candidate = Inv::new(1);
+        candidate.add_candidate_from_iteration(header);
+        let mut validation = Inv::new(1);
+        validation.add_validation_result(header);
+
+        // Broadcast by default
+        assert_eq!(request_target(&candidate, None), None);
+
+        assert_eq!(request_target(&candidate, Some(peer)), Some(peer));
+        assert_eq!(request_target(&validation, Some(peer)), None);
+        assert_eq!(request_target(&Inv::new(1), Some(peer)), None);
+    }
+}
@@ -18,6 +18,7 @@ use dusk_consensus::config::{
 };
 use dusk_consensus::errors::{ConsensusError, HeaderError};
 use dusk_consensus::operations::Voter;
+use dusk_consensus::progress::ProgressNotifier;
 use dusk_consensus::user::provisioners::{ContextProvisioners, Provisioners};
 use dusk_consensus::user::stake::Stake;
 use dusk_core::signatures::bls;
@@ -33,13 +34,15 @@ use node_data::message::payload::{GetBlocks, Vote};
 use node_data::message::{AsyncQueue, Payload, Status};
 use node_data::{get_current_timestamp, Serializable, StepName};
 use rkyv::{check_archived_root, Deserialize, Infallible};
+use thiserror::Error;
 use tokio::sync::mpsc::Sender;
-use tokio::sync::{RwLock, RwLockReadGuard};
+use tokio::sync::{broadcast, RwLock, RwLockReadGuard};
 use tracing::{debug, error, info, trace, warn};
 
+use super::conf::Params;
 use super::consensus::Task;
 use crate::chain::header_validation::{verify_att, verify_faults, Validator};
-use crate::chain::metrics::AverageElapsedTime;
+use crate::chain::metrics::{AverageElapsedTime, ConsensusMetricsTracker};
 use crate::database::rocksdb::{
     MD_AVG_PROPOSAL, MD_AVG_RATIFICATION, MD_AVG_VALIDATION, MD_HASH_KEY,
     MD_STATE_ROOT_KEY,
@@ -80,6 +83,138 @@ pub(crate) struct Acceptor<N: Network, DB: database::DB, VM: vm::VMExecution> {
     pub(crate) network: Arc<RwLock<N>>,
     /// Sender channel for sending out RUES events
     event_sender: Sender<Event>,
+    /// Notifies the state roots the VM is reverted to
+    revert_notifier: RevertNotifier,
+    /// Coalesces repeated consensus restarts
+    restart_debouncer: RestartDebouncer,
+    /// Maximum time to wait for the VM before a revert, if any
+    revert_lock_timeout: Option<Duration>,
+    /// Tracks the consensus health
+    pub(crate) consensus_metrics: ConsensusMetricsTracker,
+}
+
+const REVERT_CHANNEL_CAP: usize = 16;
+
+/// Channels through which the [Acceptor] notifies the rest of the node
+#[derive(Clone)]
+pub(crate) struct AcceptorNotifiers {
+    /// Sender channel for sending out RUES events
+    pub event_sender: Sender<Event>,
+    /// Notifies the state roots the VM is reverted to
+    pub revert_notifier: RevertNotifier,
+    /// Tracks the consensus health
+    pub consensus_metrics: ConsensusMetricsTracker,
+    /// Publishes the progress of the consensus loop
+    pub consensus_progress: ProgressNotifier,
+}
+
+/// Outcome of the acceptance of a block
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub(crate) struct AcceptOutcome {
+    /// True if the accepted block triggered a rolling finality
+    pub finalized: bool,
+    /// True if previously-accepted blocks have been reverted to accept the
+    /// block
+    pub reorged: bool,
+    /// Hashes of the blocks replaced by the accepted one, from the highest
+    pub replaced: Vec<[u8; 32]>,
+}
+
+/// Coalesces the consensus restarts requested for the same tip within a
+/// short window, to prevent restart storms.
+///
+/// Restarts are coalesced only while the consensus task is running: a task
+/// that already exited is always restarted, as nothing else would respawn it
+/// until a new block is accepted.
+struct RestartDebouncer {
+    window: Duration,
+    last_restart: Option<(std::time::Instant, [u8; 32])>,
+}
+
+impl RestartDebouncer {
+    fn new(window: Duration) -> Self {
+        Self {
+            window,
+            last_restart: None,
+        }
+    }
+
+    /// Returns true if the consensus should be restarted on top of
+    /// `tip_hash`. In that case, the restart is recorded.
+    fn should_restart(
+        &mut self,
+        tip_hash: [u8; 32],
+        now: std::time::Instant,
+        running: bool,
+    ) -> bool {
+        if let Some((at, hash)) = self.last_restart {
+            if running
+                && hash == tip_hash
+                && now.duration_since(at) < self.window
+            {
+                return false;
+            }
+        }
+        self.last_restart = Some((now, tip_hash));
+        true
+    }
+}
+
+/// Errors that make a block be rejected during its acceptance.
+///
+/// A VM failing the consistency check of an accepted block is expected to
+/// report it as [`AcceptError::StateRootMismatch`].
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum AcceptError {
+    #[error(
+        "state root mismatch: expected {}, computed {}",
+        hex::encode(expected),
+        hex::encode(computed)
+    )]
+    StateRootMismatch {
+        expected: [u8; 32],
+        computed: [u8; 32],
+    },
+}
+
+/// Checks that the state root declared by the block header matches the one
+/// computed by executing the block
+fn verify_state_root(
+    header: &ledger::Header,
+    computed: [u8; 32],
+) -> Result<(), AcceptError> {
+    if header.state_hash != computed {
+        return Err(AcceptError::StateRootMismatch {
+            expected: header.state_hash,
+            computed,
+        });
+    }
+    Ok(())
+}
+
+/// Broadcasts the state root the VM is reverted to, upon every successful
+/// revert, so that components caching derived state can invalidate it.
+#[derive(Clone)]
+pub(crate) struct RevertNotifier {
+    sender: broadcast::Sender<[u8; 32]>,
+}
+
+impl Default for RevertNotifier {
+    fn default() -> Self {
+        let (sender, _) = broadcast::channel(REVERT_CHANNEL_CAP);
+        Self { sender }
+    }
+}
+
+impl RevertNotifier {
+    pub(crate) fn subscribe(&self) -> broadcast::Receiver<[u8; 32]> {
+        self.sender.subscribe()
+    }
+
+    fn notify(&self, state_root: [u8; 32]) {
+        // An error only means there are no subscribers
+        let _ = self.sender.send(state_root);
+    }
 }
 
 impl<DB: database::DB, VM: vm::VMExecution, N: Network> Drop
@@ -165,6 +300,75 @@ impl ProvisionerChange {
 }
 
 impl<DB: database::DB, VM: vm::VMExecution, N: Network> Acceptor<N, DB, VM> {
+    /// Returns true if the block has been generated by this node
+    pub(crate) async fn is_self_authored(
+        &self,
+        header: &ledger::Header,
+    ) -> bool {
+        let task = self.task.read().await;
+        header.generator_bls_pubkey.inner() == task.keys.1.bytes().inner()
+    }
+
+    /// Initializes a new `Acceptor` at a trusted finalized checkpoint, instead
+    /// of the tip persisted in the ledger.
+    ///
+    /// The state `state_root` of the checkpoint `header` must be present in
+    /// the VM. The checkpoint is persisted as the final tip of the ledger, so
+    /// that the sync-up proceeds from it.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn from_checkpoint(
+        state_root: [u8; 32],
+        header: ledger::Header,
+        keys_path: &str,
+        db: Arc<RwLock<DB>>,
+        network: Arc<RwLock<N>>,
+        vm: Arc<RwLock<VM>>,
+        max_queue_size: usize,
+        conf: Params,
+        notifiers: AcceptorNotifiers,
+    ) -> anyhow::Result<Self> {
+        if header.state_hash != state_root {
+            return Err(anyhow!(
+                "checkpoint state root mismatch: expected {}, got {}",
+                to_str(&header.state_hash),
+                to_str(&state_root)
+            ));
+        }
+
+        let provisioners_list = {
+            let vm = vm.read().await;
+            vm.move_to_commit(state_root).map_err(|e| {
+                anyhow!(
+                    "checkpoint state {} not found: {e}",
+                    to_str(&state_root)
+                )
+            })?;
+            vm.get_provisioners(state_root)?
+        };
+
+        let tip = store_checkpoint(&*db.read().await, header)?;
+
+        info!(
+            event = "checkpoint loaded",
+            height = tip.inner().header().height,
+            hash = to_str(&tip.inner().header().hash),
+            state_root = to_str(&state_root),
+        );
+
+        Self::init_consensus(
+            keys_path,
+            tip,
+            provisioners_list,
+            db,
+            network,
+            vm,
+            max_queue_size,
+            conf,
+            notifiers,
+        )
+        .await
+    }
+
     /// Initializes a new `Acceptor` struct,
     ///
     /// The method loads the VM state and verifies consistency between the VM
@@ -180,7 +384,8 @@ impl<DB: database::DB, VM: vm::VMExecution, N: Network> Acceptor<N, DB, VM> {
         network: Arc<RwLock<N>>,
         vm: Arc<RwLock<VM>>,
         max_queue_size: usize,
-        event_sender: Sender<Event>,
+        conf: Params,
+        notifiers: AcceptorNotifiers,
     ) -> anyhow::Result<Self> {
         let tip_height = tip.inner().header().height;
         let tip_state_hash = tip.inner().header().state_hash;
@@ -193,18 +398,24 @@ impl<DB: database::DB, VM: vm::VMExecution, N: Network> Acceptor<N, DB, VM> {
             provisioners_list.apply_changes(changed_provisioners);
         }
 
-        let acc = Self {
-            tip: RwLock::new(tip),
-            provisioners_list: RwLock::new(provisioners_list),
-            db: db.clone(),
-            vm: vm.clone(),
-            network: network.clone(),
-            task: RwLock::new(Task::new_with_keys(
-                keys_path.to_string(),
-                max_queue_size,
-            )?),
-            event_sender,
-        };
+        let task = Task::new_with_keys(
+            keys_path.to_string(),
+            max_queue_size,
+            conf.persist_inbound_on_shutdown,
+            conf.skip_post_sync_delay,
+            conf.max_future_messages,
+            notifiers.consensus_progress.clone(),
+        )?;
+        let acc = Self::new(
+            task,
+            tip,
+            provisioners_list,
+            db.clone(),
+            network.clone(),
+            vm.clone(),
+            conf,
+            notifiers,
+        );
 
         // NB. After restart, state_root returned by VM is always the last
         // finalized one.
@@ -234,12 +445,46 @@ impl<DB: database::DB, VM: vm::VMExecution, N: Network> Acceptor<N, DB, VM> {
             }
         }
 
+        acc.task.read().await.restore_inbound(&*db.read().await);
+
         let tip_ts = acc.tip.read().await.inner().header().timestamp;
         Self::init_delay(tip_ts).await;
 
         Ok(acc)
     }
 
+    /// Creates an `Acceptor` whose tip is `tip`, as is.
+    ///
+    /// Unlike [Self::init_consensus], the VM state is neither loaded nor
+    /// checked against the tip.
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn new(
+        task: Task,
+        tip: BlockWithLabel,
+        provisioners_list: ContextProvisioners,
+        db: Arc<RwLock<DB>>,
+        network: Arc<RwLock<N>>,
+        vm: Arc<RwLock<VM>>,
+        conf: Params,
+        notifiers: AcceptorNotifiers,
+    ) -> Self {
+        Self {
+            tip: RwLock::new(tip),
+            provisioners_list: RwLock::new(provisioners_list),
+            db,
+            vm,
+            network,
+            task: RwLock::new(task),
+            event_sender: notifiers.event_sender,
+            revert_notifier: notifiers.revert_notifier,
+            restart_debouncer: RestartDebouncer::new(
+                conf.restart_consensus_debounce,
+            ),
+            revert_lock_timeout: conf.revert_lock_timeout,
+            consensus_metrics: notifiers.consensus_metrics,
+        }
+    }
+
     pub async fn init_delay(tip_ts: u64) {
         let spin_time: u64 = env::var("RUSK_CONSENSUS_SPIN_TIME")
             .unwrap_or_default()
@@ -292,6 +537,14 @@ impl<DB: database::DB, VM: vm::VMExecution, N: Network> Acceptor<N, DB, VM> {
         env::remove_var("RUSK_CONSENSUS_SPIN_TIME");
     }
 
+    /// Stops the running consensus task and stores its unprocessed inbound
+    /// messages, so that they can be restored on the next start
+    pub(crate) async fn persist_inbound(&self) {
+        let mut task = self.task.write().await;
+        task.abort();
+        task.persist_inbound(&*self.db.read().await);
+    }
+
     pub async fn spawn_task(&self) {
         const REDUNDANCY: usize = 16;
         const WAIT_TIMEOUT: Duration = Duration::from_secs(5);
@@ -651,12 +904,13 @@ impl<DB: database::DB, VM: vm::VMExecution, N: Network> Acceptor<N, DB, VM> {
         }
     }
 
-    /// Return true if the accepted blocks triggered a rolling finality
+    /// Accepts the block as the new tip, reporting whether it triggered a
+    /// rolling finality and which blocks it replaced, if any
     pub(crate) async fn try_accept_block(
         &mut self,
         blk: &Block,
         enable_consensus: bool,
-    ) -> anyhow::Result<bool> {
+    ) -> anyhow::Result<AcceptOutcome> {
         let mut events = vec![];
         let mut task = self.task.write().await;
 
@@ -687,7 +941,13 @@ impl<DB: database::DB, VM: vm::VMExecution, N: Network> Acceptor<N, DB, VM> {
         // Persist block in consistency with the VM state update
         let (label, finalized) = {
             let header = blk.header();
-            verify_faults(self.db.clone(), header.height, blk.faults()).await?;
+            verify_faults(
+                self.db.clone(),
+                header.height,
+                blk.faults(),
+                &provisioners_list,
+            )
+            .await?;
 
             let vm = self.vm.write().await;
 
@@ -702,7 +962,7 @@ impl<DB: database::DB, VM: vm::VMExecution, N: Network> Acceptor<N, DB, VM> {
                 }
                 est_elapsed_time = start.elapsed();
 
-                assert_eq!(header.state_hash, verification_output.state_root);
+                verify_state_root(header, verification_output.state_root)?;
                 assert_eq!(header.event_bloom, verification_output.event_bloom);
 
                 let finality =
@@ -777,6 +1037,10 @@ impl<DB: database::DB, VM: vm::VMExecution, N: Network> Acceptor<N, DB, VM> {
             block_size_on_disk,
             slashed_count,
         );
+        self.consensus_metrics.on_block_accepted(
+            Duration::from_secs(block_time),
+            tip.inner().header().iteration,
+        );
 
         // Clean up the database
         let count = self
@@ -876,7 +1140,26 @@ impl<DB: database::DB, VM: vm::VMExecution, N: Network> Acceptor<N, DB, VM> {
             );
         }
 
-        Ok(finalized)
+        Ok(AcceptOutcome {
+            finalized,
+            ..Default::default()
+        })
+    }
+
+    /// Accepts the block as the new tip in place of the `replaced` blocks,
+    /// which have just been reverted to make room for it
+    pub(crate) async fn try_accept_replacing(
+        &mut self,
+        blk: &Block,
+        replaced: Vec<[u8; 32]>,
+        enable_consensus: bool,
+    ) -> anyhow::Result<AcceptOutcome> {
+        let outcome = self.try_accept_block(blk, enable_consensus).await?;
+        Ok(AcceptOutcome {
+            reorged: !replaced.is_empty(),
+            replaced,
+            ..outcome
+        })
     }
 
     /// Perform the rolling finality checks, updating the database with new
@@ -1026,12 +1309,38 @@ impl<DB: database::DB, VM: vm::VMExecution, N: Network> Acceptor<N, DB, VM> {
     /// Implements the algorithm of full revert to any of supported targets.
     ///
     /// This incorporates both VM state revert and Ledger state revert.
-    pub async fn try_revert(&self, target: RevertTarget) -> Result<()> {
-        let curr_height = self.get_curr_height().await;
+    ///
+    /// The revert is timed and, if a revert timeout is configured, aborted
+    /// if the timeout elapses before the VM revert starts. Once started, the
+    /// VM, ledger and tip reverts always run to completion, so that they
+    /// cannot be left inconsistent.
+    ///
+    /// Returns the hashes of the reverted blocks, from the highest one.
+    pub async fn try_revert(
+        &self,
+        target: RevertTarget,
+    ) -> Result<Vec<[u8; 32]>> {
+        let prepare = async {
+            let curr_height = self.get_curr_height().await;
+            let vm = self.vm.read().await;
+            Ok((curr_height, vm))
+        };
+        timed_revert(
+            prepare,
+            |(curr_height, vm)| self.revert(target, curr_height, vm),
+            self.revert_lock_timeout,
+        )
+        .await
+    }
 
+    async fn revert(
+        &self,
+        target: RevertTarget,
+        curr_height: u64,
+        vm: RwLockReadGuard<'_, VM>,
+    ) -> Result<Vec<[u8; 32]>> {
         let target_state_hash = match target {
             RevertTarget::LastFinalizedState => {
-                let vm = self.vm.read().await;
                 let state_hash = vm.revert_to_finalized()?;
 
                 info!(
@@ -1043,7 +1352,6 @@ impl<DB: database::DB, VM: vm::VMExecution, N: Network> Acceptor<N, DB, VM> {
                 anyhow::Ok(state_hash)
             }
             RevertTarget::Commit(state_hash) => {
-                let vm = self.vm.read().await;
                 let state_hash = vm.revert(state_hash)?;
                 let is_final = vm.get_finalized_state_root()? == state_hash;
 
@@ -1057,11 +1365,13 @@ impl<DB: database::DB, VM: vm::VMExecution, N: Network> Acceptor<N, DB, VM> {
             }
             RevertTarget::LastEpoch => unimplemented!(),
         }?;
+        drop(vm);
 
         // Delete any block until we reach the target_state_hash, the
         // VM was reverted to.
 
         // The blockchain tip after reverting
+        let mut reverted = vec![];
         let (blk, label) = self.db.read().await.update(|db| {
             let mut height = curr_height;
             loop {
@@ -1103,6 +1413,7 @@ impl<DB: database::DB, VM: vm::VMExecution, N: Network> Acceptor<N, DB, VM> {
 
                 // Delete any rocksdb record related to this block
                 db.delete_block(&b)?;
+                reverted.push(h.hash);
 
                 let now = get_current_timestamp();
 
@@ -1131,13 +1442,42 @@ impl<DB: database::DB, VM: vm::VMExecution, N: Network> Acceptor<N, DB, VM> {
             state_root = hex::encode(blk.header().state_hash)
         );
 
-        self.update_tip(&blk, label).await
+        self.update_tip(&blk, label).await?;
+        self.revert_notifier.notify(target_state_hash);
+
+        Ok(reverted)
+    }
+
+    /// Restarts the consensus once the sync-up target is reached
+    pub(crate) async fn restart_consensus_after_sync(&mut self) {
+        self.task.write().await.post_sync_delay.on_synced();
+        if !self.restart_consensus().await {
+            // The running round was not restarted, so no later round should
+            // skip its slot delay
+            self.task.write().await.post_sync_delay.clear();
+        }
     }
 
-    /// Spawns consensus algorithm after aborting currently running one
-    pub(crate) async fn restart_consensus(&mut self) {
+    /// Spawns consensus algorithm after aborting currently running one.
+    ///
+    /// Returns false if the restart has been coalesced with a recent one.
+    pub(crate) async fn restart_consensus(&mut self) -> bool {
         let mut task = self.task.write().await;
         let tip = self.tip.read().await.inner().clone();
+
+        if !self.restart_debouncer.should_restart(
+            tip.header().hash,
+            std::time::Instant::now(),
+            task.is_running(),
+        ) {
+            debug!(
+                event = "restart consensus skipped",
+                reason = "restarted recently",
+                height = tip.header().height,
+            );
+            return false;
+        }
+
         let provisioners_list = self.provisioners_list.read().await.clone();
 
         task.abort_with_wait().await;
@@ -1160,6 +1500,7 @@ impl<DB: database::DB, VM: vm::VMExecution, N: Network> Acceptor<N, DB, VM> {
             base_timeouts,
             tip_block_voters,
         );
+        true
     }
 
     pub(crate) async fn get_curr_height(&self) -> u64 {
@@ -1356,6 +1697,68 @@ impl<DB: database::DB, VM: vm::VMExecution, N: Network> Acceptor<N, DB, VM> {
     }
 }
 
+/// Runs a revert, recording its duration in the
+/// `dusk_revert_duration_seconds` histogram.
+///
+/// The revert is split in two phases: `prepare` acquires what the revert needs
+/// and `apply` performs it. If `timeout` is set and elapses during `prepare`,
+/// the revert is dropped before any change and an error is returned, leaving
+/// the VM and the ledger untouched. `apply` is never interrupted, as dropping
+/// it halfway would leave the node with a partially reverted state.
+async fn timed_revert<P, T, A, F, R>(
+    prepare: P,
+    apply: A,
+    timeout: Option<Duration>,
+) -> Result<R>
+where
+    P: std::future::Future<Output = Result<T>>,
+    A: FnOnce(T) -> F,
+    F: std::future::Future<Output = Result<R>>,
+{
+    let start = std::time::Instant::now();
+    let prepared = match timeout {
+        Some(timeout) => tokio::time::timeout(timeout, prepare)
+            .await
+            .unwrap_or_else(|_| {
+                Err(anyhow!(
+                    "revert aborted, VM not acquired within {timeout:?}"
+                ))
+            }),
+        None => prepare.await,
+    };
+    let res = match prepared {
+        Ok(prepared) => apply(prepared).await,
+        Err(e) => Err(e),
+    };
+
+    let elapsed = start.elapsed();
+    histogram!("dusk_revert_duration_seconds").record(elapsed.as_secs_f64());
+    if let Err(e) = &res {
+        warn!(event = "revert failed", ?elapsed, err = ?e);
+    }
+
+    res
+}
+
+/// Persists the checkpoint `header` as the final tip of the ledger.
+///
+/// As for the genesis block, only the header of the checkpoint is stored.
+fn store_checkpoint<DB: database::DB>(
+    db: &DB,
+    header: ledger::Header,
+) -> Result<BlockWithLabel> {
+    let label = Label::Final(header.height);
+    let blk = Block::new(header, vec![], vec![])?;
+
+    db.update(|t| {
+        t.store_block(blk.header(), &[], &[], label)?;
+        t.op_write(MD_HASH_KEY, blk.header().hash)?;
+        t.op_write(MD_STATE_ROOT_KEY, blk.header().state_hash)
+    })?;
+
+    Ok(BlockWithLabel::new_with_label(blk, label))
+}
+
 async fn broadcast<N: Network>(network: &Arc<RwLock<N>>, msg: &Message) {
     let _ = network.read().await.broadcast(msg).await.map_err(|err| {
         warn!("Unable to broadcast msg: {:?} {err} ", msg.topic())
@@ -1398,3 +1801,755 @@ pub(crate) async fn verify_block_header<DB: database::DB>(
         .execute_checks(header, &expected_generator, check_att)
         .await
 }
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use std::collections::HashMap;
+    use std::net::SocketAddr;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Mutex;
+
+    use async_trait::async_trait;
+    use dusk_bytes::Serializable as DuskSerializable;
+    use dusk_consensus::commons::RoundUpdate;
+    use dusk_consensus::config::MINIMUM_BLOCK_TIME;
+    use dusk_consensus::errors::VstError;
+    use dusk_consensus::operations::{CallParams, VerificationOutput};
+    use dusk_consensus::user::cluster::Cluster;
+    use dusk_consensus::user::committee::Committee;
+    use dusk_consensus::user::sortition::Config as SortitionConfig;
+    use dusk_core::signatures::bls::MultisigSignature;
+    use node_data::ledger::{
+        Attestation, SpentTransaction, StepVotes, Transaction,
+    };
+    use node_data::message::payload::{
+        Inv, QuorumType, RatificationResult, ValidationResult,
+    };
+    use node_data::message::BLOCK_HEADER_VERSION;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    use crate::chain::fallback;
+    use crate::database::rocksdb::Backend;
+    use crate::database::{Persist, DB};
+    use crate::vm::PreverificationResult;
+    use crate::BoxedFilter;
+
+    use metrics::{
+        Counter, Gauge, Histogram, HistogramFn, Key, KeyName, Metadata,
+        Recorder, SharedString, Unit,
+    };
+
+    use super::*;
+
+    /// Records the values of all the histograms
+    #[derive(Default)]
+    struct HistogramRecorder(Arc<Mutex<Vec<(String, f64)>>>);
+
+    struct RecordedHistogram {
+        name: String,
+        values: Arc<Mutex<Vec<(String, f64)>>>,
+    }
+
+    impl HistogramFn for RecordedHistogram {
+        fn record(&self, value: f64) {
+            let mut values = self.values.lock().expect("lock");
+            values.push((self.name.clone(), value));
+        }
+    }
+
+    impl Recorder for HistogramRecorder {
+        fn describe_counter(
+            &self,
+            _: KeyName,
+            _: Option<Unit>,
+            _: SharedString,
+        ) {
+        }
+        fn describe_gauge(&self, _: KeyName, _: Option<Unit>, _: SharedString) {
+        }
+        fn describe_histogram(
+            &self,
+            _: KeyName,
+            _: Option<Unit>,
+            _: SharedString,
+        ) {
+        }
+        fn register_counter(&self, _: &Key, _: &Metadata<'_>) -> Counter {
+            Counter::noop()
+        }
+        fn register_gauge(&self, _: &Key, _: &Metadata<'_>) -> Gauge {
+            Gauge::noop()
+        }
+        fn register_histogram(&self, key: &Key, _: &Metadata<'_>) -> Histogram {
+            Histogram::from_arc(Arc::new(RecordedHistogram {
+                name: key.name().to_string(),
+                values: self.0.clone(),
+            }))
+        }
+    }
+
+    #[test]
+    fn test_revert_lock_timeout() {
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_time()
+            .build()
+            .expect("runtime");
+        let recorder = HistogramRecorder::default();
+        let timeout = Some(Duration::from_millis(50));
+        let applied = Arc::new(Mutex::new(false));
+
+        // A revert that cannot start within the timeout is never applied
+        let slow_prepare = async {
+            tokio::time::sleep(Duration::from_secs(10)).await;
+            Ok(())
+        };
+        let flag = applied.clone();
+        let apply = move |_| async move {
+            *flag.lock().expect("lock") = true;
+            Ok(())
+        };
+        let res = metrics::with_local_recorder(&recorder, || {
+            rt.block_on(timed_revert(slow_prepare, apply, timeout))
+        });
+        let err = res.expect_err("revert to time out");
+        assert!(err.to_string().contains("revert aborted"));
+        assert!(!*applied.lock().expect("lock"));
+
+        // Once started, a revert runs to completion even past the timeout
+        let flag = applied.clone();
+        let apply = move |_| async move {
+            tokio::time::sleep(Duration::from_millis(100)).await;
+            *flag.lock().expect("lock") = true;
+            Ok(())
+        };
+        let res = metrics::with_local_recorder(&recorder, || {
+            rt.block_on(timed_revert(async { Ok(()) }, apply, timeout))
+        });
+        assert!(res.is_ok());
+        assert!(*applied.lock().expect("lock"));
+
+        let values = recorder.0.lock().expect("lock");
+        assert_eq!(values.len(), 2);
+        assert!(values
+            .iter()
+            .all(|(name, _)| name == "dusk_revert_duration_seconds"));
+        assert!(values[0].1 >= 0.05 && values[0].1 < 10.0);
+        assert!(values[1].1 >= 0.1);
+    }
+
+    #[tokio::test]
+    async fn test_revert_notification() {
+        let notifier = RevertNotifier::default();
+        let mut sub = notifier.subscribe();
+
+        let state_root = [7u8; 32];
+        notifier.notify(state_root);
+
+        assert_eq!(sub.recv().await.expect("notification"), state_root);
+
+        // Notifying without subscribers is not an error
+        drop(sub);
+        notifier.notify(state_root);
+    }
+
+    #[tokio::test]
+    async fn test_accept_outcome() {
+        let dir = tempdir::TempDir::new("test_accept_outcome")
+            .expect("Temp directory to be created");
+        let (provisioners, keys) = provisioners(1);
+        let genesis = genesis();
+        let local =
+            attested_block(genesis.header(), 1, [1; 32], &provisioners, &keys);
+        let remote =
+            attested_block(genesis.header(), 0, [1; 32], &provisioners, &keys);
+        let refused =
+            attested_block(genesis.header(), 0, [2; 32], &provisioners, &keys);
+
+        let db = Backend::create_or_open(dir.path(), Default::default());
+        store_block(&db, &genesis, Label::Final(0));
+        store_block(&db, &local, Label::Accepted(1));
+        let tip =
+            BlockWithLabel::new_with_label(local.clone(), Label::Accepted(1));
+        let mut acc =
+            acceptor(db, provisioners.clone(), tip, Params::default());
+        let target = || RevertTarget::Commit(genesis.header().state_hash);
+
+        // A fallback whose block is then refused reports nothing, even to
+        // the next acceptance
+        let replaced = fallback::WithContext::new(&acc)
+            .try_revert(local.header(), refused.header(), target(), false)
+            .await
+            .expect("fallback to succeed");
+        assert_eq!(replaced, vec![local.header().hash]);
+        assert!(acc.try_accept_block(&refused, false).await.is_err());
+        let outcome = acc
+            .try_accept_block(&local, false)
+            .await
+            .expect("block to be accepted");
+        assert_eq!(outcome, AcceptOutcome::default());
+
+        // The acceptance completing a fallback reports the replaced block
+        let replaced = fallback::WithContext::new(&acc)
+            .try_revert(local.header(), remote.header(), target(), false)
+            .await
+            .expect("fallback to succeed");
+        let outcome = acc
+            .try_accept_replacing(&remote, replaced, false)
+            .await
+            .expect("block to be accepted");
+        assert!(outcome.reorged);
+        assert_eq!(outcome.replaced, vec![local.header().hash]);
+
+        // The replaced blocks are not reported again
+        let next =
+            attested_block(remote.header(), 0, [2; 32], &provisioners, &keys);
+        let outcome = acc
+            .try_accept_block(&next, false)
+            .await
+            .expect("block to be accepted");
+        assert!(!outcome.reorged);
+        assert!(outcome.replaced.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_state_root_mismatch() {
+        let dir = tempdir::TempDir::new("test_state_root_mismatch")
+            .expect("Temp directory to be created");
+        let (provisioners, keys) = provisioners(1);
+        let genesis = genesis();
+        let db = Backend::create_or_open(dir.path(), Default::default());
+        store_block(&db, &genesis, Label::Final(0));
+        let tip = BlockWithLabel::new_with_label(genesis, Label::Final(0));
+        let mut acc =
+            acceptor(db, provisioners.clone(), tip, Params::default());
+        let prev = acc.tip.read().await.inner().header().clone();
+
+        // The VM refuses a block whose state root differs from the computed
+        // one, which is reported as a mismatch
+        let blk = attested_block(&prev, 0, [2; 32], &provisioners, &keys);
+        let err = acc
+            .try_accept_block(&blk, false)
+            .await
+            .expect_err("block to be rejected");
+        assert_eq!(
+            err.downcast_ref::<AcceptError>(),
+            Some(&AcceptError::StateRootMismatch {
+                expected: [2; 32],
+                computed: [1; 32],
+            })
+        );
+        assert_eq!(acc.get_curr_height().await, 0);
+
+        let blk = attested_block(&prev, 0, [1; 32], &provisioners, &keys);
+        acc.try_accept_block(&blk, false)
+            .await
+            .expect("block to be accepted");
+        assert_eq!(acc.get_curr_height().await, 1);
+
+        // Blocks are also checked against the state root returned by the VM
+        let header = ledger::Header {
+            state_hash: [2; 32],
+            ..Default::default()
+        };
+        let err: anyhow::Error =
+            verify_state_root(&header, [1; 32]).unwrap_err().into();
+        assert_eq!(
+            err.downcast_ref::<AcceptError>(),
+            Some(&AcceptError::StateRootMismatch {
+                expected: [2; 32],
+                computed: [1; 32],
+            })
+        );
+    }
+
+    #[test]
+    fn test_restart_debounce() {
+        let window = Duration::from_secs(1);
+        let mut debouncer = RestartDebouncer::new(window);
+        let now = std::time::Instant::now();
+        let tip = [1u8; 32];
+
+        let restarts = (0..5)
+            .map(|i| now + Duration::from_millis(i * 100))
+            .filter(|at| debouncer.should_restart(tip, *at, true))
+            .count();
+        assert_eq!(restarts, 1);
+
+        // A different tip is always restarted
+        assert!(debouncer.should_restart([2u8; 32], now, true));
+
+        // Once the window has elapsed, the same tip can be restarted again
+        assert!(debouncer.should_restart([2u8; 32], now + window, true));
+
+        // A consensus task that already exited is restarted even within the
+        // window
+        let at = now + window + Duration::from_millis(100);
+        assert!(!debouncer.should_restart([2u8; 32], at, true));
+        assert!(debouncer.should_restart([2u8; 32], at, false));
+    }
+
+    #[test]
+    fn test_store_checkpoint() {
+        let dir = tempdir::TempDir::new("test_store_checkpoint")
+            .expect("Temp directory to be created");
+        let db = Backend::create_or_open(dir.path(), Default::default());
+
+        let header = ledger::Header {
+            height: 1_000,
+            state_hash: [1u8; 32],
+            ..Default::default()
+        };
+        let tip =
+            store_checkpoint(&db, header).expect("checkpoint to be stored");
+        let tip_hash = tip.inner().header().hash;
+        assert_eq!(tip.label(), Label::Final(1_000));
+
+        // The checkpoint is loaded as the final tip of the ledger
+        db.view(|t| {
+            let stored = t.tip_header().unwrap().expect("tip to be set");
+            assert_eq!(stored.hash, tip_hash);
+            assert_eq!(
+                t.block_label_by_height(1_000).unwrap(),
+                Some((tip_hash, Label::Final(1_000)))
+            );
+            assert_eq!(
+                t.op_read(MD_STATE_ROOT_KEY).unwrap(),
+                Some(vec![1u8; 32])
+            );
+        });
+
+        // The next block is validated against the checkpoint
+        let next = ledger::Header {
+            height: 1_001,
+            prev_block_hash: tip_hash,
+            ..Default::default()
+        };
+        let prev = db
+            .view(|t| t.block_header(&next.prev_block_hash))
+            .unwrap()
+            .expect("checkpoint to be found");
+        assert_eq!(prev.height + 1, next.height);
+    }
+
+    /// Network dropping any message
+    pub(crate) struct DummyNetwork(SocketAddr);
+
+    impl Default for DummyNetwork {
+        fn default() -> Self {
+            Self(SocketAddr::from(([127, 0, 0, 1], 0)))
+        }
+    }
+
+    #[async_trait]
+    impl Network for DummyNetwork {
+        async fn broadcast(&self, _: &Message) -> anyhow::Result<()> {
+            Ok(())
+        }
+
+        async fn flood_request(
+            &self,
+            _: &Inv,
+            _: Option<u64>,
+            _: u16,
+        ) -> anyhow::Result<()> {
+            Ok(())
+        }
+
+        async fn send_to_peer(
+            &self,
+            _: Message,
+            _: SocketAddr,
+        ) -> anyhow::Result<()> {
+            Ok(())
+        }
+
+        async fn send_to_alive_peers(
+            &self,
+            _: Message,
+            _: usize,
+        ) -> anyhow::Result<()> {
+            Ok(())
+        }
+
+        async fn add_route(
+            &mut self,
+            _: u8,
+            _: AsyncQueue<Message>,
+        ) -> anyhow::Result<()> {
+            Ok(())
+        }
+
+        async fn add_filter(
+            &mut self,
+            _: u8,
+            _: BoxedFilter,
+        ) -> anyhow::Result<()> {
+            Ok(())
+        }
+
+        fn get_info(&self) -> anyhow::Result<String> {
+            Ok(String::new())
+        }
+
+        fn public_addr(&self) -> &SocketAddr {
+            &self.0
+        }
+
+        async fn alive_nodes_count(&self) -> usize {
+            0
+        }
+    }
+
+    /// VM with a fixed set of provisioners, which can be reverted to any
+    /// state and executes any block at height `h` to the state root `[h; 32]`
+    pub(crate) struct DummyVM {
+        provisioners: Provisioners,
+        /// Number of blocks the VM has been asked to accept
+        pub(crate) accept_calls: AtomicUsize,
+    }
+
+    impl vm::VMExecution for DummyVM {
+        fn execute_state_transition<I: Iterator<Item = Transaction>>(
+            &self,
+            _: &CallParams,
+            _: I,
+        ) -> anyhow::Result<(
+            Vec<SpentTransaction>,
+            Vec<Transaction>,
+            VerificationOutput,
+        )> {
+            Err(anyhow!("not supported"))
+        }
+
+        fn verify_state_transition(
+            &self,
+            _: [u8; 32],
+            _: &Block,
+            _: &[Voter],
+        ) -> Result<VerificationOutput, VstError> {
+            Err(VstError::Generic("not supported".into()))
+        }
+
+        fn accept(
+            &self,
+            _: [u8; 32],
+            blk: &Block,
+            _: &[Voter],
+        ) -> anyhow::Result<(
+            Vec<SpentTransaction>,
+            VerificationOutput,
+            Vec<ContractEvent>,
+        )> {
+            self.accept_calls.fetch_add(1, Ordering::Relaxed);
+            let header = blk.header();
+            let state_root = [header.height as u8; 32];
+            if header.state_hash != state_root {
+                return Err(AcceptError::StateRootMismatch {
+                    expected: header.state_hash,
+                    computed: state_root,
+                }
+                .into());
+            }
+            let verification_output = VerificationOutput {
+                state_root,
+                event_bloom: header.event_bloom,
+            };
+            Ok((vec![], verification_output, vec![]))
+        }
+
+        fn finalize_state(
+            &self,
+            _: [u8; 32],
+            _: Vec<[u8; 32]>,
+        ) -> anyhow::Result<()> {
+            Ok(())
+        }
+
+        fn preverify(
+            &self,
+            _: &Transaction,
+        ) -> anyhow::Result<PreverificationResult> {
+            Err(anyhow!("not supported"))
+        }
+
+        fn get_provisioners(
+            &self,
+            _: [u8; 32],
+        ) -> anyhow::Result<Provisioners> {
+            Ok(self.provisioners.clone())
+        }
+
+        fn get_changed_provisioners(
+            &self,
+            _: [u8; 32],
+        ) -> anyhow::Result<Vec<(PublicKey, Option<Stake>)>> {
+            Ok(vec![])
+        }
+
+        fn get_provisioner(
+            &self,
+            _: &bls::PublicKey,
+        ) -> anyhow::Result<Option<Stake>> {
+            Ok(None)
+        }
+
+        fn get_state_root(&self) -> anyhow::Result<[u8; 32]> {
+            Ok([0u8; 32])
+        }
+
+        fn move_to_commit(&self, _: [u8; 32]) -> anyhow::Result<()> {
+            Ok(())
+        }
+
+        fn get_finalized_state_root(&self) -> anyhow::Result<[u8; 32]> {
+            Ok([0u8; 32])
+        }
+
+        fn get_block_gas_limit(&self) -> u64 {
+            0
+        }
+
+        fn revert(&self, state_hash: [u8; 32]) -> anyhow::Result<[u8; 32]> {
+            Ok(state_hash)
+        }
+
+        fn revert_to_finalized(&self) -> anyhow::Result<[u8; 32]> {
+            Ok([0u8; 32])
+        }
+
+        fn gas_per_deploy_byte(&self) -> u64 {
+            0
+        }
+
+        fn min_deployment_gas_price(&self) -> u64 {
+            0
+        }
+
+        fn min_gas_limit(&self) -> u64 {
+            0
+        }
+
+        fn min_deploy_points(&self) -> u64 {
+            0
+        }
+    }
+
+    /// Creates `n` provisioners with the same stake, along with their keys
+    pub(crate) fn provisioners(
+        n: usize,
+    ) -> (Provisioners, Vec<(PublicKey, bls::SecretKey)>) {
+        let rng = &mut StdRng::seed_from_u64(0xbeef);
+        let mut provisioners = Provisioners::empty();
+        let keys = (0..n)
+            .map(|_| {
+                let sk = bls::SecretKey::random(rng);
+                let pk = PublicKey::new(bls::PublicKey::from(&sk));
+                provisioners
+                    .add_member_with_value(pk.clone(), 1_000_000_000_000);
+                (pk, sk)
+            })
+            .collect();
+        (provisioners, keys)
+    }
+
+    /// Creates an acceptor over `db`, whose tip is `tip`
+    pub(crate) fn acceptor(
+        db: Backend,
+        provisioners: Provisioners,
+        tip: BlockWithLabel,
+        conf: Params,
+    ) -> Acceptor<DummyNetwork, Backend, DummyVM> {
+        let rng = &mut StdRng::seed_from_u64(0xcafe);
+        let sk = bls::SecretKey::random(rng);
+        let pk = PublicKey::new(bls::PublicKey::from(&sk));
+        acceptor_with_keys(db, provisioners, tip, conf, (sk, pk))
+    }
+
+    /// Creates an acceptor over `db`, whose tip is `tip`, for a node with the
+    /// given consensus `keys`
+    pub(crate) fn acceptor_with_keys(
+        db: Backend,
+        provisioners: Provisioners,
+        tip: BlockWithLabel,
+        conf: Params,
+        keys: (bls::SecretKey, PublicKey),
+    ) -> Acceptor<DummyNetwork, Backend, DummyVM> {
+        let (event_sender, _) = tokio::sync::mpsc::channel(16);
+        let notifiers = AcceptorNotifiers {
+            event_sender,
+            revert_notifier: RevertNotifier::default(),
+            consensus_metrics: ConsensusMetricsTracker::default(),
+            consensus_progress: ProgressNotifier::default(),
+        };
+        let task = Task::new(
+            keys,
+            16,
+            false,
+            false,
+            16,
+            notifiers.consensus_progress.clone(),
+        );
+
+        Acceptor::new(
+            task,
+            tip,
+            ContextProvisioners::new(provisioners.clone()),
+            Arc::new(RwLock::new(db)),
+            Arc::new(RwLock::new(DummyNetwork::default())),
+            Arc::new(RwLock::new(DummyVM {
+                provisioners,
+                accept_calls: AtomicUsize::new(0),
+            })),
+            conf,
+            notifiers,
+        )
+    }
+
+    /// Stores `blk` in the ledger of `db` with the given `label`
+    pub(crate) fn store_block(db: &Backend, blk: &Block, label: Label) {
+        db.update(|t| t.store_block(blk.header(), &[], &[], label))
+            .expect("block to be stored");
+    }
+
+    /// Creates a genesis block produced an hour ago
+    pub(crate) fn genesis() -> Block {
+        let header = ledger::Header {
+            timestamp: get_current_timestamp() - 3_600,
+            seed: [5; 48].into(),
+            ..Default::default()
+        };
+        Block::new(header, vec![], vec![]).expect("block to be created")
+    }
+
+    /// Creates a block on top of `prev`, produced at `iteration` and attested
+    /// by all the `keys`.
+    ///
+    /// The keys must be the ones of all the `provisioners`.
+    pub(crate) fn attested_block(
+        prev: &ledger::Header,
+        iteration: u8,
+        state_hash: [u8; 32],
+        provisioners: &Provisioners,
+        keys: &[(PublicKey, bls::SecretKey)],
+    ) -> Block {
+        let round = prev.height + 1;
+        let generator = provisioners.get_generator(iteration, prev.seed, round);
+        let (pk, sk) = keys
+            .iter()
+            .find(|(pk, _)| pk.bytes() == &generator)
+            .expect("generator to be known");
+
+        let seed = sk.sign_multisig(pk.inner(), prev.seed.inner());
+        let header = ledger::Header {
+            version: BLOCK_HEADER_VERSION,
+            height: round,
+            timestamp: prev.timestamp + *MINIMUM_BLOCK_TIME,
+            prev_block_hash: prev.hash,
+            seed: Seed::from(seed.to_bytes()),
+            state_hash,
+            generator_bls_pubkey: generator,
+            iteration,
+            prev_block_cert: prev.att,
+            ..Default::default()
+        };
+        let mut blk =
+            Block::new(header, vec![], vec![]).expect("block to be created");
+        let hash = blk.header().hash;
+        let signature = sk.sign_multisig(pk.inner(), &hash);
+        blk.set_signature(signature.to_bytes().into());
+
+        let vote = Vote::Valid(hash);
+        blk.set_attestation(Attestation {
+            result: RatificationResult::Success(vote),
+            validation: step_votes(
+                prev,
+                iteration,
+                vote,
+                StepName::Validation,
+                provisioners,
+                keys,
+            ),
+            ratification: step_votes(
+                prev,
+                iteration,
+                vote,
+                StepName::Ratification,
+                provisioners,
+                keys,
+            ),
+        });
+        blk
+    }
+
+    /// Aggregates the votes cast by the `keys` in the committee of `step`
+    fn step_votes(
+        prev: &ledger::Header,
+        iteration: u8,
+        vote: Vote,
+        step: StepName,
+        provisioners: &Provisioners,
+        keys: &[(PublicKey, bls::SecretKey)],
+    ) -> StepVotes {
+        let round = prev.height + 1;
+        let generator = provisioners.get_generator(iteration, prev.seed, round);
+        let next_generator =
+            provisioners.get_generator(iteration + 1, prev.seed, round);
+        let cfg = SortitionConfig::new(
+            prev.seed,
+            round,
+            iteration,
+            step,
+            vec![generator, next_generator],
+        );
+        let committee = Committee::new(provisioners, &cfg);
+
+        let mut signatures = vec![];
+        let mut cluster = Cluster::<PublicKey>::default();
+        for (pk, sk) in keys {
+            let Some(weight) = committee.votes_for(pk) else {
+                continue;
+            };
+            let ru = RoundUpdate::new(
+                pk.clone(),
+                sk.clone(),
+                prev,
+                HashMap::default(),
+                vec![],
+            );
+            let signature = match step {
+                StepName::Validation => {
+                    dusk_consensus::build_validation_payload(
+                        vote, &ru, iteration,
+                    )
+                    .sign_info
+                    .signature
+                }
+                _ => {
+                    let result = ValidationResult::new(
+                        StepVotes::default(),
+                        vote,
+                        QuorumType::Valid,
+                    );
+                    dusk_consensus::build_ratification_payload(
+                        &ru, iteration, &result,
+                    )
+                    .sign_info
+                    .signature
+                }
+            };
+            signatures.push(
+                MultisigSignature::from_bytes(signature.inner())
+                    .expect("signature to be valid"),
+            );
+            cluster.add(pk, weight);
+        }
+
+        let (first, rest) =
+            signatures.split_first().expect("committee to vote");
+        StepVotes::new(
+            first.aggregate(rest).to_bytes(),
+            committee.bits(&cluster),
+        )
+    }
+}
@@ -0,0 +1,290 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+//! Drives the block acceptance for benchmarking purposes.
+//!
+//! The [Acceptor] runs over a VM that does not execute the state transition,
+//! as it is measured by the VM benchmarks, and over a network dropping any
+//! message.
+
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use anyhow::anyhow;
+use async_trait::async_trait;
+use dusk_consensus::errors::VstError;
+use dusk_consensus::operations::{CallParams, VerificationOutput, Voter};
+use dusk_consensus::progress::ProgressNotifier;
+use dusk_consensus::user::provisioners::{ContextProvisioners, Provisioners};
+use dusk_consensus::user::stake::Stake;
+use dusk_core::signatures::bls;
+use node_data::bls::PublicKey;
+use node_data::events::contract::ContractEvent;
+use node_data::ledger::{Block, BlockWithLabel, SpentTransaction, Transaction};
+use node_data::message::payload::Inv;
+use node_data::message::AsyncQueue;
+use tokio::sync::RwLock;
+
+use super::acceptor::{Acceptor, AcceptorNotifiers, RevertNotifier};
+use super::conf::Params;
+use super::consensus::Task;
+use super::metrics::ConsensusMetricsTracker;
+use crate::vm::{PreverificationResult, VMExecution};
+use crate::{database, BoxedFilter, Message, Network};
+
+/// Size of the consensus queue, never filled as the consensus is not run
+const MAX_QUEUE_SIZE: usize = 16;
+
+/// An [Acceptor] accepting blocks on top of a given tip
+pub struct BenchAcceptor<DB: database::DB> {
+    acceptor: Acceptor<NoopNetwork, DB, NoopVM>,
+}
+
+impl<DB: database::DB> BenchAcceptor<DB> {
+    /// Creates an acceptor over `db`, whose tip is `tip`, verifying blocks
+    /// against the given `provisioners`.
+    ///
+    /// The `keys` are the consensus keys of the node, which never takes part
+    /// in the consensus.
+    pub fn new(
+        db: DB,
+        tip: BlockWithLabel,
+        provisioners: Provisioners,
+        keys: (bls::SecretKey, PublicKey),
+    ) -> Self {
+        let conf = Params::default();
+        let (event_sender, _) = tokio::sync::mpsc::channel(1);
+        let notifiers = AcceptorNotifiers {
+            event_sender,
+            revert_notifier: RevertNotifier::default(),
+            consensus_metrics: ConsensusMetricsTracker::default(),
+            consensus_progress: ProgressNotifier::default(),
+        };
+        let task = Task::new(
+            keys,
+            MAX_QUEUE_SIZE,
+            false,
+            false,
+            conf.max_future_messages,
+            notifiers.consensus_progress.clone(),
+        );
+
+        let acceptor = Acceptor::new(
+            task,
+            tip,
+            ContextProvisioners::new(provisioners.clone()),
+            Arc::new(RwLock::new(db)),
+            Arc::new(RwLock::new(NoopNetwork::default())),
+            Arc::new(RwLock::new(NoopVM(provisioners))),
+            conf,
+            notifiers,
+        );
+        Self { acceptor }
+    }
+
+    /// Accepts `blk` as the new tip
+    pub async fn accept(&mut self, blk: &Block) -> anyhow::Result<()> {
+        self.acceptor.try_accept_block(blk, false).await?;
+        Ok(())
+    }
+}
+
+/// Network dropping any message
+struct NoopNetwork(SocketAddr);
+
+impl Default for NoopNetwork {
+    fn default() -> Self {
+        Self(SocketAddr::from(([127, 0, 0, 1], 0)))
+    }
+}
+
+#[async_trait]
+impl Network for NoopNetwork {
+    async fn broadcast(&self, _: &Message) -> anyhow::Result<()> {
+        Ok(())
+    }
+
+    async fn flood_request(
+        &self,
+        _: &Inv,
+        _: Option<u64>,
+        _: u16,
+    ) -> anyhow::Result<()> {
+        Ok(())
+    }
+
+    async fn send_to_peer(
+        &self,
+        _: Message,
+        _: SocketAddr,
+    ) -> anyhow::Result<()> {
+        Ok(())
+    }
+
+    async fn send_to_alive_peers(
+        &self,
+        _: Message,
+        _: usize,
+    ) -> anyhow::Result<()> {
+        Ok(())
+    }
+
+    async fn add_route(
+        &mut self,
+        _: u8,
+        _: AsyncQueue<Message>,
+    ) -> anyhow::Result<()> {
+        Ok(())
+    }
+
+    async fn add_filter(
+        &mut self,
+        _: u8,
+        _: BoxedFilter,
+    ) -> anyhow::Result<()> {
+        Ok(())
+    }
+
+    fn get_info(&self) -> anyhow::Result<String> {
+        Ok(String::new())
+    }
+
+    fn public_addr(&self) -> &SocketAddr {
+        &self.0
+    }
+
+    async fn alive_nodes_count(&self) -> usize {
+        0
+    }
+}
+
+/// VM with a fixed set of provisioners, accepting any block as executed to
+/// the state root declared by its header
+struct NoopVM(Provisioners);
+
+impl VMExecution for NoopVM {
+    fn execute_state_transition<I: Iterator<Item = Transaction>>(
+        &self,
+        _: &CallParams,
+        _: I,
+    ) -> anyhow::Result<(
+        Vec<SpentTransaction>,
+        Vec<Transaction>,
+        VerificationOutput,
+    )> {
+        Err(anyhow!("not supported"))
+    }
+
+    fn verify_state_transition(
+        &self,
+        _: [u8; 32],
+        _: &Block,
+        _: &[Voter],
+    ) -> Result<VerificationOutput, VstError> {
+        Err(VstError::Generic("not supported".into()))
+    }
+
+    fn accept(
+        &self,
+        _: [u8; 32],
+        blk: &Block,
+        _: &[Voter],
+    ) -> anyhow::Result<(
+        Vec<SpentTransaction>,
+        VerificationOutput,
+        Vec<ContractEvent>,
+    )> {
+        let header = blk.header();
+        let txs = blk
+            .txs()
+            .iter()
+            .map(|tx| SpentTransaction {
+                inner: tx.clone(),
+                block_height: header.height,
+                gas_spent: 0,
+                err: None,
+            })
+            .collect();
+        let verification_output = VerificationOutput {
+            state_root: header.state_hash,
+            event_bloom: header.event_bloom,
+        };
+        Ok((txs, verification_output, vec![]))
+    }
+
+    fn finalize_state(
+        &self,
+        _: [u8; 32],
+        _: Vec<[u8; 32]>,
+    ) -> anyhow::Result<()> {
+        Ok(())
+    }
+
+    fn preverify(
+        &self,
+        _: &Transaction,
+    ) -> anyhow::Result<PreverificationResult> {
+        Err(anyhow!("not supported"))
+    }
+
+    fn get_provisioners(&self, _: [u8; 32]) -> anyhow::Result<Provisioners> {
+        Ok(self.0.clone())
+    }
+
+    fn get_changed_provisioners(
+        &self,
+        _: [u8; 32],
+    ) -> anyhow::Result<Vec<(PublicKey, Option<Stake>)>> {
+        Ok(vec![])
+    }
+
+    fn get_provisioner(
+        &self,
+        _: &bls::PublicKey,
+    ) -> anyhow::Result<Option<Stake>> {
+        Ok(None)
+    }
+
+    fn get_state_root(&self) -> anyhow::Result<[u8; 32]> {
+        Ok([0u8; 32])
+    }
+
+    fn move_to_commit(&self, _: [u8; 32]) -> anyhow::Result<()> {
+        Ok(())
+    }
+
+    fn get_finalized_state_root(&self) -> anyhow::Result<[u8; 32]> {
+        Ok([0u8; 32])
+    }
+
+    fn get_block_gas_limit(&self) -> u64 {
+        0
+    }
+
+    fn revert(&self, state_hash: [u8; 32]) -> anyhow::Result<[u8; 32]> {
+        Ok(state_hash)
+    }
+
+    fn revert_to_finalized(&self) -> anyhow::Result<[u8; 32]> {
+        Ok([0u8; 32])
+    }
+
+    fn gas_per_deploy_byte(&self) -> u64 {
+        0
+    }
+
+    fn min_deployment_gas_price(&self) -> u64 {
+        0
+    }
+
+    fn min_gas_limit(&self) -> u64 {
+        0
+    }
+
+    fn min_deploy_points(&self) -> u64 {
+        0
+    }
+}
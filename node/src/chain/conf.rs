@@ -0,0 +1,282 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use std::net::SocketAddr;
+use std::time::Duration;
+
+use dusk_consensus::config::MAX_BLOCK_SIZE;
+use dusk_consensus::queue::DEFAULT_MAX_MESSAGES;
+use node_data::message::Version;
+use serde::{Deserialize, Serialize};
+
+/// Block kept when the local tip conflicts with a network block of the same
+/// height and iteration
+#[derive(
+    Serialize, Deserialize, Copy, Clone, Debug, Default, PartialEq, Eq,
+)]
+#[serde(rename_all = "kebab-case")]
+pub enum TieBreakPolicy {
+    PreferLocal,
+    PreferNetwork,
+    /// Keep the block with the lower hash, so that all the nodes converge to
+    /// the same block
+    #[default]
+    PreferLowerHash,
+}
+
+/// Behavior when a new attestation must be cached while the attestation cache
+/// is full
+#[derive(
+    Serialize, Deserialize, Copy, Clone, Debug, Default, PartialEq, Eq,
+)]
+#[serde(rename_all = "kebab-case")]
+pub enum AttCacheFullPolicy {
+    /// Evict the oldest attestation to make room for the new one
+    #[default]
+    EvictOldest,
+    /// Drop the new attestation and skip requesting its candidate
+    RejectNew,
+}
+
+/// Behavior when a block fails acceptance while the node is out of sync
+#[derive(
+    Serialize, Deserialize, Copy, Clone, Debug, Default, PartialEq, Eq,
+)]
+#[serde(rename_all = "kebab-case")]
+pub enum SyncFailurePolicy {
+    /// Abort the sync-up
+    #[default]
+    Abort,
+    /// Drop the block and request it again, carrying on with the sync-up
+    Skip,
+}
+
+#[derive(Serialize, Deserialize, Copy, Clone, Debug)]
+pub struct Params {
+    /// Maximum number of blocks the node can be behind the network while
+    /// still fetching the missing ones inline, without switching to
+    /// OutOfSync mode
+    #[serde(default = "default_catchup_window")]
+    pub catchup_window: u64,
+
+    /// Maximum number of blocks ahead of the tip kept while catching up
+    /// inline. Once reached, the oldest pooled block is evicted first
+    #[serde(default = "default_max_catchup_blocks")]
+    pub max_catchup_blocks: usize,
+
+    /// Persist the unprocessed inbound consensus messages on shutdown and
+    /// reload them on the next start
+    #[serde(default)]
+    pub persist_inbound_on_shutdown: bool,
+
+    /// Maximum number of blocks ahead of the local tip a Quorum can be for
+    /// its candidate to be requested from the network
+    #[serde(default = "default_max_quorum_lookahead")]
+    pub max_quorum_lookahead: u64,
+
+    /// Broadcast our block when a lower-priority block at the same height is
+    /// received without knowing the peer it comes from
+    #[serde(default = "default_broadcast_on_conflict")]
+    pub broadcast_on_conflict: bool,
+
+    /// Time window during which a candidate already requested to the network
+    /// is not requested again. It should be longer than the expiry of the
+    /// cached attestations (60s), which already prevents re-requests until
+    /// then
+    #[serde(
+        with = "humantime_serde",
+        default = "default_flood_request_window"
+    )]
+    pub flood_request_window: Duration,
+
+    /// Maximum difference tolerated between the local clock and the
+    /// timestamps of the blocks produced by the network
+    #[serde(
+        with = "humantime_serde",
+        default = "default_clock_skew_threshold"
+    )]
+    pub clock_skew_threshold: Duration,
+
+    /// Consensus restarts requested for the same tip within this window are
+    /// coalesced into one
+    #[serde(
+        with = "humantime_serde",
+        default = "default_restart_consensus_debounce"
+    )]
+    pub restart_consensus_debounce: Duration,
+
+    /// Maximum size of a message received from the network. Larger messages
+    /// are discarded before being parsed
+    #[serde(default = "default_max_wire_message_size")]
+    pub max_wire_message_size: usize,
+
+    /// Block kept when the local tip conflicts with a network block of the
+    /// same height and iteration
+    #[serde(default)]
+    pub tie_break: TieBreakPolicy,
+
+    /// Maximum number of blocks the node reverts to fallback to a
+    /// higher-priority block. Deeper forks are refused
+    #[serde(default = "default_max_fork_depth")]
+    pub max_fork_depth: u64,
+
+    /// Maximum number of attestations cached while waiting for their
+    /// candidates
+    #[serde(default = "default_att_cache_capacity")]
+    pub att_cache_capacity: usize,
+
+    /// Behavior when the attestation cache is full
+    #[serde(default)]
+    pub att_cache_full_policy: AttCacheFullPolicy,
+
+    /// Peer to which the Emergency Mode requests of candidates by iteration
+    /// are sent. If not set, the requests are flooded to the network
+    #[serde(default)]
+    pub candidate_request_peer: Option<SocketAddr>,
+
+    /// Interval of the heartbeat driving the sync state and the stalled
+    /// chain detection
+    #[serde(with = "humantime_serde", default = "default_heartbeat_interval")]
+    pub heartbeat_interval: Duration,
+
+    /// Time after which a blacklisted block can be accepted again
+    #[serde(with = "humantime_serde", default = "default_blacklist_ttl")]
+    pub blacklist_ttl: Duration,
+
+    /// Persist the blacklisted blocks so that they are still discarded after
+    /// a restart, until the blacklist is cleared
+    #[serde(default)]
+    pub persist_blacklist: bool,
+
+    /// Skip waiting for the next block slot in the first consensus round
+    /// after a sync-up, so that a freshly synced node re-engages quickly
+    #[serde(default)]
+    pub skip_post_sync_delay: bool,
+
+    /// Minimum protocol version a peer must advertise for the node to sync
+    /// from it. If not set, peers of any version are accepted
+    #[serde(default)]
+    pub min_peer_version: Option<Version>,
+
+    /// Maximum number of future consensus messages buffered. Once reached,
+    /// the messages of the oldest round are evicted first
+    #[serde(default = "default_max_future_messages")]
+    pub max_future_messages: usize,
+
+    /// Maximum time a state revert waits to acquire the VM. A revert that
+    /// cannot start in time is aborted with an error, leaving the state
+    /// untouched. Once started, a revert always runs to completion. If not
+    /// set, reverts wait indefinitely
+    #[serde(default, with = "humantime_serde")]
+    pub revert_lock_timeout: Option<Duration>,
+
+    /// Maximum number of blocks requested at once while out of sync. Bounded
+    /// between 1 and the size of the sync pool
+    #[serde(default = "default_max_blocks_to_request")]
+    pub max_blocks_to_request: u64,
+
+    /// Behavior when a block fails acceptance while out of sync
+    #[serde(default)]
+    pub sync_failure_policy: SyncFailurePolicy,
+
+    /// Maximum cumulative size, in bytes, of the blocks pooled while out of
+    /// sync. Once reached, further blocks are not pooled even if the pool is
+    /// not full
+    #[serde(default = "default_max_sync_pool_bytes")]
+    pub max_sync_pool_bytes: usize,
+}
+
+const fn default_catchup_window() -> u64 {
+    3
+}
+
+const fn default_max_catchup_blocks() -> usize {
+    16
+}
+
+const fn default_max_quorum_lookahead() -> u64 {
+    100
+}
+
+const fn default_broadcast_on_conflict() -> bool {
+    true
+}
+
+const fn default_flood_request_window() -> Duration {
+    Duration::from_secs(90)
+}
+
+const fn default_clock_skew_threshold() -> Duration {
+    Duration::from_secs(30)
+}
+
+const fn default_restart_consensus_debounce() -> Duration {
+    Duration::from_millis(500)
+}
+
+const fn default_heartbeat_interval() -> Duration {
+    Duration::from_secs(3)
+}
+
+const fn default_blacklist_ttl() -> Duration {
+    Duration::from_secs(60 * 60)
+}
+
+const fn default_max_future_messages() -> usize {
+    DEFAULT_MAX_MESSAGES
+}
+
+const fn default_max_blocks_to_request() -> u64 {
+    100
+}
+
+const fn default_max_sync_pool_bytes() -> usize {
+    512 * 1024 * 1024
+}
+
+const fn default_max_fork_depth() -> u64 {
+    50
+}
+
+const fn default_att_cache_capacity() -> usize {
+    1_000
+}
+
+const fn default_max_wire_message_size() -> usize {
+    // Leave room for the message envelope of a full block
+    2 * MAX_BLOCK_SIZE
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        Self {
+            catchup_window: default_catchup_window(),
+            max_catchup_blocks: default_max_catchup_blocks(),
+            persist_inbound_on_shutdown: false,
+            max_quorum_lookahead: default_max_quorum_lookahead(),
+            broadcast_on_conflict: default_broadcast_on_conflict(),
+            flood_request_window: default_flood_request_window(),
+            clock_skew_threshold: default_clock_skew_threshold(),
+            restart_consensus_debounce: default_restart_consensus_debounce(),
+            max_wire_message_size: default_max_wire_message_size(),
+            tie_break: TieBreakPolicy::default(),
+            max_fork_depth: default_max_fork_depth(),
+            att_cache_capacity: default_att_cache_capacity(),
+            att_cache_full_policy: AttCacheFullPolicy::default(),
+            candidate_request_peer: None,
+            heartbeat_interval: default_heartbeat_interval(),
+            blacklist_ttl: default_blacklist_ttl(),
+            persist_blacklist: false,
+            skip_post_sync_delay: false,
+            min_peer_version: None,
+            max_future_messages: default_max_future_messages(),
+            revert_lock_timeout: None,
+            max_blocks_to_request: default_max_blocks_to_request(),
+            sync_failure_policy: SyncFailurePolicy::default(),
+            max_sync_pool_bytes: default_max_sync_pool_bytes(),
+        }
+    }
+}
@@ -16,6 +16,7 @@ use dusk_consensus::errors::{
 use dusk_consensus::operations::{
     CallParams, Operations, Output, VerificationOutput, Voter,
 };
+use dusk_consensus::progress::ProgressNotifier;
 use dusk_consensus::queue::MsgRegistry;
 use dusk_consensus::user::provisioners::ContextProvisioners;
 use metrics::gauge;
@@ -31,10 +32,43 @@ use crate::chain::header_validation::Validator;
 use crate::chain::metrics::AverageElapsedTime;
 use crate::database::rocksdb::{
     MD_AVG_PROPOSAL, MD_AVG_RATIFICATION, MD_AVG_VALIDATION, MD_LAST_ITER,
+    MD_PENDING_INBOUND,
 };
 use crate::database::{self, ConsensusStorage, Mempool, Metadata};
 use crate::{vm, Message};
 
+/// Decides whether the slot delay is skipped in the first consensus round
+/// after a sync-up
+pub(crate) struct PostSyncDelay {
+    skip_enabled: bool,
+    synced: bool,
+}
+
+impl PostSyncDelay {
+    fn new(skip_enabled: bool) -> Self {
+        Self {
+            skip_enabled,
+            synced: false,
+        }
+    }
+
+    /// Records that the sync-up has been completed
+    pub(crate) fn on_synced(&mut self) {
+        self.synced = true;
+    }
+
+    /// Forgets a completed sync-up whose round has not been spawned
+    pub(crate) fn clear(&mut self) {
+        self.synced = false;
+    }
+
+    /// Returns true if the slot delay must be skipped in the round being
+    /// spawned. Only the first round after a sync-up is affected.
+    fn take_skip(&mut self) -> bool {
+        std::mem::take(&mut self.synced) && self.skip_enabled
+    }
+}
+
 /// Consensus Service Task is responsible for running the consensus layer.
 ///
 /// It manages consensus lifecycle and provides a way to interact with it.
@@ -54,6 +88,15 @@ pub(crate) struct Task {
     /// task id a counter to track consensus tasks
     task_id: u64,
 
+    /// Persist the unprocessed inbound messages on shutdown
+    persist_inbound: bool,
+
+    /// Publishes the progress of the consensus loop
+    progress: ProgressNotifier,
+
+    /// Tracks whether the next round follows a completed sync-up
+    pub(crate) post_sync_delay: PostSyncDelay,
+
     /// Loaded Consensus keys
     pub keys: (
         dusk_core::signatures::bls::SecretKey,
@@ -67,6 +110,10 @@ impl Task {
     pub(crate) fn new_with_keys(
         path: String,
         max_inbound_size: usize,
+        persist_inbound: bool,
+        skip_post_sync_delay: bool,
+        max_future_msgs: usize,
+        progress: ProgressNotifier,
     ) -> anyhow::Result<Self> {
         let pwd = std::env::var("DUSK_CONSENSUS_KEYS_PASS")
             .map_err(|_| anyhow::anyhow!("DUSK_CONSENSUS_KEYS_PASS not set"))?;
@@ -78,7 +125,29 @@ impl Task {
             pubkey = format!("{:?}", keys.1)
         );
 
-        Ok(Self {
+        Ok(Self::new(
+            keys,
+            max_inbound_size,
+            persist_inbound,
+            skip_post_sync_delay,
+            max_future_msgs,
+            progress,
+        ))
+    }
+
+    /// Creates a new consensus task with the given consensus keys
+    pub(crate) fn new(
+        keys: (
+            dusk_core::signatures::bls::SecretKey,
+            node_data::bls::PublicKey,
+        ),
+        max_inbound_size: usize,
+        persist_inbound: bool,
+        skip_post_sync_delay: bool,
+        max_future_msgs: usize,
+        progress: ProgressNotifier,
+    ) -> Self {
+        Self {
             main_inbound: AsyncQueue::bounded(
                 max_inbound_size,
                 "consensus_inbound",
@@ -87,12 +156,17 @@ impl Task {
                 max_inbound_size,
                 "consensus_outbound",
             ),
-            future_msg: Arc::new(Mutex::new(MsgRegistry::default())),
+            future_msg: Arc::new(Mutex::new(MsgRegistry::with_max_msgs(
+                max_future_msgs,
+            ))),
             result: AsyncQueue::bounded(1, "consensus_result"),
             running_task: None,
             task_id: 0,
+            persist_inbound,
+            progress,
+            post_sync_delay: PostSyncDelay::new(skip_post_sync_delay),
             keys,
-        })
+        }
     }
 
     pub(crate) fn spawn<D: database::DB, VM: vm::VMExecution>(
@@ -116,7 +190,8 @@ impl Task {
                 provisioners_list, // TODO: Avoid cloning
             )),
             Arc::new(Mutex::new(CandidateDB::new(db.clone()))),
-        );
+        )
+        .with_progress(self.progress.clone());
 
         let ru = RoundUpdate::new(
             self.keys.1.clone(),
@@ -124,7 +199,8 @@ impl Task {
             tip.header(),
             base_timeout.clone(),
             voters,
-        );
+        )
+        .with_skip_slot_delay(self.post_sync_delay.take_skip());
 
         self.task_id += 1;
 
@@ -182,8 +258,69 @@ impl Task {
         }
     }
 
+    /// Returns true if a consensus task has been spawned and has not exited
+    /// yet
     pub(crate) fn is_running(&self) -> bool {
-        self.running_task.is_some()
+        self.running_task
+            .as_ref()
+            .is_some_and(|(handle, _)| !handle.is_finished())
+    }
+
+    /// Drains the inbound queue and stores the unprocessed messages, so that
+    /// they can be restored on the next start.
+    ///
+    /// No-op if persisting the inbound messages is not enabled.
+    pub(crate) fn persist_inbound<D: database::DB>(&self, db: &D) {
+        if !self.persist_inbound {
+            return;
+        }
+
+        let bytes = match self.main_inbound.drain_to_bytes() {
+            Ok(bytes) => bytes,
+            Err(e) => {
+                warn!("Cannot serialize pending inbound messages {e:?}");
+                return;
+            }
+        };
+
+        match db.update(|t| t.op_write(MD_PENDING_INBOUND, bytes)) {
+            Ok(_) => info!(event = "pending inbound messages persisted"),
+            Err(e) => warn!("Cannot persist pending inbound messages {e:?}"),
+        }
+    }
+
+    /// Restores into the inbound queue the messages persisted on the previous
+    /// shutdown.
+    ///
+    /// Persisted messages are removed from the database in any case.
+    pub(crate) fn restore_inbound<D: database::DB>(&self, db: &D) {
+        let data = db
+            .update(|t| {
+                let data = t.op_read(MD_PENDING_INBOUND)?;
+                if data.is_some() {
+                    t.op_delete(MD_PENDING_INBOUND)?;
+                }
+                Ok(data)
+            })
+            .unwrap_or_else(|e| {
+                warn!("Cannot read pending inbound messages {e:?}");
+                None
+            });
+
+        let Some(data) = data else {
+            return;
+        };
+
+        if !self.persist_inbound {
+            return;
+        }
+
+        match self.main_inbound.restore_from_bytes(&data) {
+            Ok(count) => {
+                info!(event = "pending inbound messages restored", count)
+            }
+            Err(e) => warn!("Cannot restore pending inbound messages {e:?}"),
+        }
     }
 }
 
@@ -403,3 +540,133 @@ impl<DB: database::DB, VM: vm::VMExecution> Operations for Executor<DB, VM> {
         self.vm.read().await.get_block_gas_limit()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use dusk_core::signatures::bls::{
+        PublicKey as BlsPublicKey, SecretKey as BlsSecretKey,
+    };
+    use node_data::message::SignInfo;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    use super::*;
+    use crate::database::rocksdb::tests::TestWrapper;
+    use crate::database::rocksdb::Backend;
+    use crate::database::{DatabaseOptions, DB};
+
+    fn task(persist_inbound: bool) -> Task {
+        let rng = &mut StdRng::seed_from_u64(0xbeef);
+        let sk = BlsSecretKey::random(rng);
+        let pk = node_data::bls::PublicKey::new(BlsPublicKey::from(&sk));
+        Task::new(
+            (sk, pk),
+            16,
+            persist_inbound,
+            false,
+            16,
+            ProgressNotifier::default(),
+        )
+    }
+
+    #[test]
+    fn test_persist_and_restore_inbound() {
+        TestWrapper::new("test_persist_and_restore_inbound").run(|path| {
+            let db = Backend::create_or_open(path, DatabaseOptions::default());
+
+            let header = ConsensusHeader {
+                iteration: 2,
+                prev_block_hash: [3; 32],
+                round: 7,
+            };
+            let msg: Message = payload::Validation {
+                header,
+                vote: payload::Vote::Valid([5; 32]),
+                sign_info: SignInfo {
+                    signer: node_data::bls::PublicKey::from_sk_seed_u64(1),
+                    signature: [4; 48].into(),
+                },
+            }
+            .into();
+
+            let t = task(true);
+            t.main_inbound.try_send(msg.clone());
+            t.main_inbound.try_send(msg.clone());
+            t.persist_inbound(&db);
+            assert!(t.main_inbound.drain().is_empty());
+
+            // The pending messages are restored by a fresh task
+            let t = task(true);
+            t.restore_inbound(&db);
+            let restored = t.main_inbound.drain();
+            assert_eq!(restored.len(), 2);
+            assert_eq!(restored[0].header, msg.header);
+
+            // Persisted messages are removed once restored
+            db.view(|t| {
+                assert!(t.op_read(MD_PENDING_INBOUND).unwrap().is_none())
+            });
+            let t = task(true);
+            t.restore_inbound(&db);
+            assert!(t.main_inbound.drain().is_empty());
+
+            // Nothing is persisted when disabled
+            let t = task(false);
+            t.main_inbound.try_send(msg);
+            t.persist_inbound(&db);
+            db.view(|t| {
+                assert!(t.op_read(MD_PENDING_INBOUND).unwrap().is_none())
+            });
+        });
+    }
+
+    #[tokio::test]
+    async fn test_is_running() {
+        let mut t = task(false);
+        assert!(!t.is_running());
+
+        // A task that exited on its own is not running anymore, even if it
+        // has not been aborted
+        let (cancel_tx, _cancel_rx) = oneshot::channel::<i32>();
+        let handle = tokio::spawn(async { 0 });
+        while !handle.is_finished() {
+            tokio::task::yield_now().await;
+        }
+        t.running_task = Some((handle, cancel_tx));
+        assert!(!t.is_running());
+
+        let (cancel_tx, cancel_rx) = oneshot::channel::<i32>();
+        let handle = tokio::spawn(async move {
+            let _ = cancel_rx.await;
+            0
+        });
+        t.running_task = Some((handle, cancel_tx));
+        assert!(t.is_running());
+
+        t.abort_with_wait().await;
+        assert!(!t.is_running());
+    }
+
+    #[test]
+    fn test_post_sync_delay() {
+        let mut delay = PostSyncDelay::new(true);
+
+        // InSync rounds keep the slot delay
+        assert!(!delay.take_skip());
+
+        // OutOfSync -> InSync: only the first round skips it
+        delay.on_synced();
+        assert!(delay.take_skip());
+        assert!(!delay.take_skip());
+
+        // A sync-up not followed by a new round is forgotten
+        delay.on_synced();
+        delay.clear();
+        assert!(!delay.take_skip());
+
+        // Default behavior is preserved when disabled
+        let mut delay = PostSyncDelay::new(false);
+        delay.on_synced();
+        assert!(!delay.take_skip());
+    }
+}
@@ -32,15 +32,22 @@ impl<'a, N: Network, DB: database::DB, VM: vm::VMExecution>
     }
 
     /// Makes an attempt to revert to the specified Target, if remote header is
-    /// fully valid
+    /// fully valid.
+    ///
+    /// A remote header with the same iteration as the local one is accepted
+    /// only if `tie_break_won` is set, i.e. the tie-break policy preferred it.
+    ///
+    /// Returns the hashes of the reverted blocks, from the highest one.
     pub(crate) async fn try_revert(
         &self,
         local: &Header,
         remote: &Header,
         revert_target: RevertTarget,
-    ) -> Result<()> {
+        tie_break_won: bool,
+    ) -> Result<Vec<[u8; 32]>> {
         match (local.height, remote.iteration.cmp(&local.iteration)) {
             (0, _) => Err(anyhow!("cannot fallback over genesis block")),
+            (_, Ordering::Equal) if tie_break_won => Ok(()),
             (_, Ordering::Greater) => Err(anyhow!(
                 "iteration {:?} is higher than the current {:?}",
                 remote.iteration,
@@ -8,14 +8,15 @@ mod insync;
 mod outofsync;
 mod stalled;
 
-use std::collections::{HashMap, HashSet};
+use std::collections::HashMap;
 use std::net::SocketAddr;
 use std::sync::Arc;
 use std::time::Duration;
 
 use dusk_consensus::config::is_emergency_block;
-use metrics::counter;
-use node_data::ledger::{to_str, Attestation, Block};
+use metrics::{counter, gauge};
+use node_data::get_current_timestamp;
+use node_data::ledger::{to_str, Attestation, Block, Header};
 use node_data::message::payload::{Inv, Quorum, RatificationResult, Vote};
 use node_data::message::Metadata;
 use tokio::sync::RwLock;
@@ -26,6 +27,8 @@ use self::insync::InSyncImpl;
 use self::outofsync::OutOfSyncImpl;
 use self::stalled::StalledChainFSM;
 use super::acceptor::{Acceptor, RevertTarget};
+use super::conf::{AttCacheFullPolicy, Params};
+use super::skew::ClockSkewDetector;
 use crate::database::{ConsensusStorage, Ledger};
 use crate::{database, vm, Network};
 
@@ -37,7 +40,133 @@ const DEFAULT_ATT_CACHE_EXPIRY: Duration = Duration::from_secs(60);
 /// requested resource
 const DEFAULT_HOPS_LIMIT: u16 = 16;
 
-type SharedHashSet = Arc<RwLock<HashSet<[u8; 32]>>>;
+/// Heartbeats fired within the heartbeat interval divided by this value from
+/// the last handled one are coalesced into it
+const HEARTBEAT_COALESCING_DIVISOR: u32 = 2;
+
+/// Blacklisted block hashes, with the reason and the expiry of each entry
+type SharedBlacklist =
+    Arc<RwLock<HashMap<[u8; 32], (BlacklistReason, Instant)>>>;
+
+/// Why a block has been blacklisted
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub(crate) enum BlacklistReason {
+    /// The local block has been replaced by a higher-priority block through
+    /// fallback
+    FallbackLoser = 0,
+    /// The local block has been reverted to recover from a fork the chain
+    /// was stalled on
+    ForkRecovery = 1,
+}
+
+impl BlacklistReason {
+    fn from_u8(value: u8) -> Option<Self> {
+        match value {
+            0 => Some(Self::FallbackLoser),
+            1 => Some(Self::ForkRecovery),
+            _ => None,
+        }
+    }
+}
+
+/// Blacklists `hash` for `ttl` so that the block is discarded if received
+/// again
+async fn blacklist(
+    blacklisted_blocks: &SharedBlacklist,
+    hash: [u8; 32],
+    reason: BlacklistReason,
+    ttl: Duration,
+) {
+    debug!(event = "block blacklisted", hash = to_str(&hash), ?reason);
+    let expiry = Instant::now() + ttl;
+    blacklisted_blocks
+        .write()
+        .await
+        .insert(hash, (reason, expiry));
+}
+
+/// Persists the blacklisted `hash` so that it is restored on restart, until
+/// `ttl` has elapsed
+async fn persist_blacklisted<DB: database::DB>(
+    db: &RwLock<DB>,
+    hash: [u8; 32],
+    reason: BlacklistReason,
+    ttl: Duration,
+) {
+    let expiry = get_current_timestamp().saturating_add(ttl.as_secs());
+    let res = db
+        .read()
+        .await
+        .update(|t| t.store_blacklisted_block(&hash, reason as u8, expiry));
+    if let Err(err) = res {
+        warn!(
+            event = "blacklist not persisted",
+            hash = to_str(&hash),
+            ?err
+        );
+    }
+}
+
+/// Clears the blacklisted blocks, along with the persisted ones if `persist`
+/// is set
+async fn clear_blacklist<DB: database::DB>(
+    blacklisted_blocks: &SharedBlacklist,
+    db: &RwLock<DB>,
+    persist: bool,
+) {
+    blacklisted_blocks.write().await.clear();
+    if persist {
+        let res = db.read().await.update(|t| t.clear_blacklisted_blocks());
+        if let Err(err) = res {
+            warn!(event = "persisted blacklist not cleared", ?err);
+        }
+    }
+}
+
+/// Loads the persisted blacklisted blocks that have not expired yet.
+///
+/// Expired entries are deleted from the database.
+fn load_blacklist<DB: database::DB>(
+    db: &DB,
+) -> HashMap<[u8; 32], (BlacklistReason, Instant)> {
+    let now = Instant::now();
+    let now_secs = get_current_timestamp();
+    let res = db.update(|t| {
+        let mut blacklist = HashMap::new();
+        for (hash, reason, expiry) in t.blacklisted_blocks()? {
+            let reason = BlacklistReason::from_u8(reason);
+            match reason {
+                Some(reason) if expiry > now_secs => {
+                    let ttl = Duration::from_secs(expiry - now_secs);
+                    blacklist.insert(hash, (reason, now + ttl));
+                }
+                _ => t.delete_blacklisted_block(&hash)?,
+            }
+        }
+        Ok(blacklist)
+    });
+
+    res.unwrap_or_else(|err| {
+        warn!(event = "persisted blacklist not loaded", ?err);
+        HashMap::new()
+    })
+}
+
+/// Returns the reason `hash` is blacklisted for, unless its entry has expired
+/// at `now`. An expired entry is removed.
+async fn blacklist_reason(
+    blacklisted_blocks: &SharedBlacklist,
+    hash: &[u8; 32],
+    now: Instant,
+) -> Option<BlacklistReason> {
+    let (reason, expiry) = *blacklisted_blocks.read().await.get(hash)?;
+    if expiry <= now {
+        blacklisted_blocks.write().await.remove(hash);
+        return None;
+    }
+    Some(reason)
+}
 
 /// `PresyncInfo` holds information about the presync process, which is used to
 /// verify if a peer has valid block successors before switching the system into
@@ -102,6 +231,23 @@ impl PresyncInfo {
     fn start_height(&self) -> u64 {
         self.tip_height
     }
+
+    /// Returns true if the presync target can extend the chain ending with
+    /// `accepted`.
+    ///
+    /// The target must be above the accepted block, no pooled block may be at
+    /// or below it, and a pooled direct successor must link to its hash.
+    fn chains_onto(&self, accepted: &Header) -> bool {
+        if self.remote_height <= accepted.height {
+            return false;
+        }
+
+        self.pool.iter().map(|b| b.header()).all(|h| {
+            h.height > accepted.height
+                && (h.height != accepted.height + 1
+                    || h.prev_block_hash == accepted.hash)
+        })
+    }
 }
 
 enum State<N: Network, DB: database::DB, VM: vm::VMExecution> {
@@ -109,32 +255,87 @@ enum State<N: Network, DB: database::DB, VM: vm::VMExecution> {
     OutOfSync(OutOfSyncImpl<DB, VM, N>),
 }
 
+/// Synchronization status of the node
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum SyncStatus {
+    InSync,
+    OutOfSync {
+        /// Heights still to be accepted, up to the sync target
+        range: (u64, u64),
+        /// Number of blocks within the range received but not yet accepted
+        pooled: usize,
+    },
+}
+
+impl SyncStatus {
+    /// Returns the height the node is syncing to, if out of sync
+    pub fn target(&self) -> Option<u64> {
+        match self {
+            Self::InSync => None,
+            Self::OutOfSync { range, .. } => Some(range.1),
+        }
+    }
+
+    /// Returns the number of blocks still to be accepted to reach the sync
+    /// target
+    pub fn remaining(&self) -> u64 {
+        match self {
+            Self::InSync => 0,
+            Self::OutOfSync {
+                range: (from, to), ..
+            } => to.saturating_add(1).saturating_sub(*from),
+        }
+    }
+}
+
 /// Implements a finite-state-machine to manage InSync and OutOfSync
 pub(crate) struct SimpleFSM<N: Network, DB: database::DB, VM: vm::VMExecution> {
     curr: State<N, DB, VM>,
     acc: Arc<RwLock<Acceptor<N, DB, VM>>>,
     network: Arc<RwLock<N>>,
 
-    blacklisted_blocks: SharedHashSet,
+    blacklisted_blocks: SharedBlacklist,
 
     /// Attestations cached from received Quorum messages
-    attestations_cache: HashMap<[u8; 32], (Attestation, Instant)>,
+    attestations_cache: AttestationCache,
 
     /// State machine to detect a stalled state of the chain
     stalled_sm: StalledChainFSM<DB, N, VM>,
+
+    /// Candidates recently requested to the network
+    recent_requests: RecentRequests,
+
+    clock_skew: ClockSkewDetector,
+
+    /// Coalesces the heartbeats fired too frequently
+    heartbeats: HeartbeatCoalescer,
+
+    conf: Params,
 }
 
 impl<N: Network, DB: database::DB, VM: vm::VMExecution> SimpleFSM<N, DB, VM> {
     pub async fn new(
         acc: Arc<RwLock<Acceptor<N, DB, VM>>>,
         network: Arc<RwLock<N>>,
+        conf: Params,
     ) -> Self {
-        let blacklisted_blocks = Arc::new(RwLock::new(HashSet::new()));
+        let blacklisted_blocks = if conf.persist_blacklist {
+            let db = acc.read().await.db.clone();
+            let blacklist = load_blacklist(&*db.read().await);
+            if !blacklist.is_empty() {
+                info!(event = "blacklist restored", len = blacklist.len());
+            }
+            blacklist
+        } else {
+            HashMap::new()
+        };
+        let blacklisted_blocks = Arc::new(RwLock::new(blacklisted_blocks));
         let stalled_sm = StalledChainFSM::new_with_acc(acc.clone()).await;
         let curr = State::InSync(InSyncImpl::<DB, VM, N>::new(
             acc.clone(),
             network.clone(),
             blacklisted_blocks.clone(),
+            conf,
         ));
 
         Self {
@@ -142,11 +343,49 @@ impl<N: Network, DB: database::DB, VM: vm::VMExecution> SimpleFSM<N, DB, VM> {
             acc,
             network: network.clone(),
             blacklisted_blocks,
-            attestations_cache: Default::default(),
+            attestations_cache: AttestationCache::new(
+                conf.att_cache_capacity,
+                conf.att_cache_full_policy,
+            ),
             stalled_sm,
+            recent_requests: RecentRequests::new(conf.flood_request_window),
+            clock_skew: ClockSkewDetector::new(conf.clock_skew_threshold),
+            heartbeats: HeartbeatCoalescer::new(conf.heartbeat_interval),
+            conf,
+        }
+    }
+
+    /// Returns the heights present in the sync pool and the missing ones,
+    /// within the current sync range.
+    ///
+    /// Returns `None` if the node is not out of sync.
+    pub fn pool_status(&self) -> Option<(Vec<u64>, Vec<u64>)> {
+        match &self.curr {
+            State::OutOfSync(oos) => Some(oos.pool_status()),
+            State::InSync(_) => None,
+        }
+    }
+
+    /// Returns the current synchronization status
+    pub fn sync_status(&self) -> SyncStatus {
+        match &self.curr {
+            State::OutOfSync(oos) => oos.sync_status(),
+            State::InSync(_) => SyncStatus::InSync,
         }
     }
 
+    /// Returns the blacklisted blocks along with the reason they have been
+    /// blacklisted for.
+    pub async fn blacklisted_blocks(&self) -> Vec<([u8; 32], BlacklistReason)> {
+        let now = Instant::now();
+        let blacklisted = self.blacklisted_blocks.read().await;
+        blacklisted
+            .iter()
+            .filter(|(_, (_, expiry))| *expiry > now)
+            .map(|(hash, (reason, _))| (*hash, *reason))
+            .collect()
+    }
+
     pub async fn on_failed_consensus(&mut self) {
         self.acc.write().await.restart_consensus().await;
     }
@@ -178,10 +417,17 @@ impl<N: Network, DB: database::DB, VM: vm::VMExecution> SimpleFSM<N, DB, VM> {
 
         // Filter out blocks that have already been marked as
         // blacklisted upon successful fallback execution.
-        if self.blacklisted_blocks.read().await.contains(block_hash) {
+        if let Some(reason) = blacklist_reason(
+            &self.blacklisted_blocks,
+            block_hash,
+            Instant::now(),
+        )
+        .await
+        {
             info!(
                 event = "block discarded",
                 reason = "blacklisted",
+                blacklist_reason = ?reason,
                 hash = to_str(&blk.header().hash),
                 height = blk.header().height,
                 iter = blk.header().iteration,
@@ -204,6 +450,19 @@ impl<N: Network, DB: database::DB, VM: vm::VMExecution> SimpleFSM<N, DB, VM> {
 
         let fsm_res = match &mut self.curr {
             State::InSync(ref mut curr) => {
+                // A block for the next height, received from the network,
+                // has been produced just now
+                if metadata.is_some() {
+                    let tip_height =
+                        self.acc.read().await.get_curr_height().await;
+                    if blk.header().height == tip_height + 1 {
+                        self.clock_skew.check(
+                            blk.header().timestamp,
+                            blk.header().iteration,
+                        );
+                    }
+                }
+
                 if let Some(presync) =
                     curr.on_block_event(&blk, metadata).await?
                 {
@@ -214,6 +473,9 @@ impl<N: Network, DB: database::DB, VM: vm::VMExecution> SimpleFSM<N, DB, VM> {
                     let mut next = OutOfSyncImpl::new(
                         self.acc.clone(),
                         self.network.clone(),
+                        self.conf.max_blocks_to_request,
+                        self.conf.sync_failure_policy,
+                        self.conf.max_sync_pool_bytes,
                     )
                     .await;
                     next.on_entering(presync).await;
@@ -222,7 +484,8 @@ impl<N: Network, DB: database::DB, VM: vm::VMExecution> SimpleFSM<N, DB, VM> {
                 anyhow::Ok(())
             }
             State::OutOfSync(ref mut curr) => {
-                if curr.on_block_event(&blk).await? {
+                let src_addr = metadata.as_ref().map(|m| m.src_addr);
+                if curr.on_block_event(&blk, src_addr).await? {
                     // Transition from OutOfSync to InSync state
                     curr.on_exiting().await;
 
@@ -231,6 +494,7 @@ impl<N: Network, DB: database::DB, VM: vm::VMExecution> SimpleFSM<N, DB, VM> {
                         self.acc.clone(),
                         self.network.clone(),
                         self.blacklisted_blocks.clone(),
+                        self.conf,
                     );
                     next.on_entering(&blk).await.map_err(|e| {
                         error!("Unable to enter in_sync state: {e}");
@@ -242,6 +506,9 @@ impl<N: Network, DB: database::DB, VM: vm::VMExecution> SimpleFSM<N, DB, VM> {
             }
         };
 
+        let remaining = self.sync_status().remaining();
+        gauge!("dusk_sync_remaining").set(remaining as f64);
+
         // Try to detect a stalled chain
         // Generally speaking, if a node is receiving future blocks from the
         // network but it cannot accept a new block for long time, then
@@ -274,8 +541,9 @@ impl<N: Network, DB: database::DB, VM: vm::VMExecution> SimpleFSM<N, DB, VM> {
                     .try_revert(RevertTarget::Commit(prev_local_state_root))
                     .await
                 {
-                    Ok(_) => {
+                    Ok(replaced) => {
                         counter!("dusk_revert_count").increment(1);
+                        acc.consensus_metrics.on_revert();
                         info!(event = "reverted to last finalized");
 
                         info!(
@@ -284,14 +552,37 @@ impl<N: Network, DB: database::DB, VM: vm::VMExecution> SimpleFSM<N, DB, VM> {
                             hash = to_str(&remote_blk.header().hash),
                         );
 
-                        acc.try_accept_block(&remote_blk, true).await?;
+                        let outcome = acc
+                            .try_accept_replacing(&remote_blk, replaced, true)
+                            .await?;
+                        info!(
+                            event = "fork recovered",
+                            height = remote_blk.header().height,
+                            replaced = ?outcome
+                                .replaced
+                                .iter()
+                                .map(|hash| to_str(hash))
+                                .collect::<Vec<_>>(),
+                        );
 
                         // Black list the block hash to avoid accepting it
                         // again due to fallback execution
-                        self.blacklisted_blocks
-                            .write()
-                            .await
-                            .insert(local_hash_at_fork);
+                        blacklist(
+                            &self.blacklisted_blocks,
+                            local_hash_at_fork,
+                            BlacklistReason::ForkRecovery,
+                            self.conf.blacklist_ttl,
+                        )
+                        .await;
+                        if self.conf.persist_blacklist {
+                            persist_blacklisted(
+                                &acc.db,
+                                local_hash_at_fork,
+                                BlacklistReason::ForkRecovery,
+                                self.conf.blacklist_ttl,
+                            )
+                            .await;
+                        }
 
                         // Try to reset the stalled chain FSM to `running`
                         // state
@@ -311,7 +602,13 @@ impl<N: Network, DB: database::DB, VM: vm::VMExecution> SimpleFSM<N, DB, VM> {
                 }
             }
             stalled::State::Stalled(_) => {
-                self.blacklisted_blocks.write().await.clear();
+                let db = self.acc.read().await.db.clone();
+                clear_blacklist(
+                    &self.blacklisted_blocks,
+                    &db,
+                    self.conf.persist_blacklist,
+                )
+                .await;
             }
             _ => {}
         }
@@ -323,15 +620,32 @@ impl<N: Network, DB: database::DB, VM: vm::VMExecution> SimpleFSM<N, DB, VM> {
     }
 
     async fn flood_request_block(&mut self, hash: [u8; 32], att: Attestation) {
-        if self.attestations_cache.contains_key(&hash) {
+        if self.attestations_cache.contains(&hash) {
             return;
         }
 
         // Save attestation in case only candidate block is received
-        let expiry = Instant::now()
-            .checked_add(DEFAULT_ATT_CACHE_EXPIRY)
-            .unwrap();
-        self.attestations_cache.insert(hash, (att, expiry));
+        let now = Instant::now();
+        let expiry = now.checked_add(DEFAULT_ATT_CACHE_EXPIRY).unwrap();
+        if !self.attestations_cache.insert(hash, att, expiry, now) {
+            counter!("dusk_att_cache_rejected").increment(1);
+            warn!(
+                event = "Candidate request skipped",
+                reason = "attestation cache full",
+                hash = to_str(&hash),
+            );
+            return;
+        }
+
+        // Suppress rapid re-requests of the same candidate, even if its
+        // attestation has already expired
+        if !self.recent_requests.try_request(hash, Instant::now()) {
+            debug!(
+                event = "Candidate request suppressed",
+                hash = to_str(&hash),
+            );
+            return;
+        }
 
         let mut inv = Inv::new(1);
         inv.add_candidate_from_hash(hash);
@@ -371,6 +685,21 @@ impl<N: Network, DB: database::DB, VM: vm::VMExecution> SimpleFSM<N, DB, VM> {
                 }
             };
 
+            if exceeds_lookahead(
+                tip_height,
+                quorum_height,
+                self.conf.max_quorum_lookahead,
+            ) {
+                counter!("dusk_future_quorum_ignored").increment(1);
+                debug!(
+                    event = "Quorum ignored",
+                    reason = "too far in the future",
+                    tip_height,
+                    height = quorum_height,
+                );
+                return;
+            }
+
             let quorum_blk = if quorum_height > tip_height + 1 {
                 // Quorum from future
 
@@ -395,18 +724,27 @@ impl<N: Network, DB: database::DB, VM: vm::VMExecution> SimpleFSM<N, DB, VM> {
 
             let attestation = qmsg.att;
 
-            if let Some(mut blk) = quorum_blk {
+            if let Some(candidate_blk) = quorum_blk {
                 // Candidate found. We can build the "full" block
                 info!(
                     event = "New block",
                     src = "Quorum msg",
-                    height = blk.header().height,
-                    iter = blk.header().iteration,
-                    hash = to_str(&blk.header().hash)
+                    height = candidate_blk.header().height,
+                    iter = candidate_blk.header().iteration,
+                    hash = to_str(&candidate_blk.header().hash)
                 );
 
                 // Attach the Attestation to the block
-                blk.set_attestation(attestation);
+                let blk = match Block::from_candidate_and_attestation(
+                    candidate_blk,
+                    attestation,
+                ) {
+                    Ok(blk) => blk,
+                    Err(e) => {
+                        error!("Invalid Quorum for candidate: {e}");
+                        return;
+                    }
+                };
 
                 // Handle the new block
                 let res = self.on_block_event(blk, metadata).await;
@@ -434,6 +772,11 @@ impl<N: Network, DB: database::DB, VM: vm::VMExecution> SimpleFSM<N, DB, VM> {
     }
 
     pub(crate) async fn on_heartbeat_event(&mut self) -> anyhow::Result<()> {
+        if !self.heartbeats.try_handle(Instant::now()) {
+            trace!(event = "heartbeat coalesced");
+            return Ok(());
+        }
+
         self.stalled_sm.on_heartbeat_event().await;
 
         match &mut self.curr {
@@ -446,6 +789,9 @@ impl<N: Network, DB: database::DB, VM: vm::VMExecution> SimpleFSM<N, DB, VM> {
                     let next = OutOfSyncImpl::new(
                         self.acc.clone(),
                         self.network.clone(),
+                        self.conf.max_blocks_to_request,
+                        self.conf.sync_failure_policy,
+                        self.conf.max_sync_pool_bytes,
                     )
                     .await;
                     self.curr = State::OutOfSync(next);
@@ -461,6 +807,7 @@ impl<N: Network, DB: database::DB, VM: vm::VMExecution> SimpleFSM<N, DB, VM> {
                         self.acc.clone(),
                         self.network.clone(),
                         self.blacklisted_blocks.clone(),
+                        self.conf,
                     );
                     self.curr = State::InSync(next);
                 }
@@ -482,7 +829,7 @@ impl<N: Network, DB: database::DB, VM: vm::VMExecution> SimpleFSM<N, DB, VM> {
         let block_hash = blk.header().hash;
 
         // Check if we have the block Attestation in our cache
-        if let Some((att, _)) = self.attestations_cache.get(&block_hash) {
+        if let Some(att) = self.attestations_cache.get(&block_hash) {
             blk.set_attestation(*att);
         } else {
             // warn!("Attestation not found for {}", hex::encode(block_hash));
@@ -500,9 +847,7 @@ impl<N: Network, DB: database::DB, VM: vm::VMExecution> SimpleFSM<N, DB, VM> {
     }
 
     fn clean_att_cache(&mut self) {
-        let now = Instant::now();
-        self.attestations_cache
-            .retain(|_, (_, expiry)| *expiry > now);
+        self.attestations_cache.clean(Instant::now());
     }
 }
 
@@ -520,3 +865,449 @@ async fn flood_request<N: Network>(network: &Arc<RwLock<N>>, inv: &Inv) {
         warn!("could not request block {err}")
     };
 }
+
+/// Attestations cached from received Quorum messages, waiting for their
+/// candidates
+struct AttestationCache {
+    entries: HashMap<[u8; 32], (Attestation, Instant)>,
+    capacity: usize,
+    policy: AttCacheFullPolicy,
+}
+
+impl AttestationCache {
+    fn new(capacity: usize, policy: AttCacheFullPolicy) -> Self {
+        Self {
+            entries: HashMap::new(),
+            capacity,
+            policy,
+        }
+    }
+
+    fn contains(&self, hash: &[u8; 32]) -> bool {
+        self.entries.contains_key(hash)
+    }
+
+    fn get(&self, hash: &[u8; 32]) -> Option<&Attestation> {
+        self.entries.get(hash).map(|(att, _)| att)
+    }
+
+    fn remove(&mut self, hash: &[u8; 32]) {
+        self.entries.remove(hash);
+    }
+
+    /// Removes the attestations expired at `now`
+    fn clean(&mut self, now: Instant) {
+        self.entries.retain(|_, (_, expiry)| *expiry > now);
+    }
+
+    /// Caches `att` until `expiry`.
+    ///
+    /// Returns false if the cache is full and the policy rejects new
+    /// attestations.
+    fn insert(
+        &mut self,
+        hash: [u8; 32],
+        att: Attestation,
+        expiry: Instant,
+        now: Instant,
+    ) -> bool {
+        if self.entries.len() >= self.capacity {
+            self.clean(now);
+        }
+
+        if self.entries.len() >= self.capacity {
+            match self.policy {
+                AttCacheFullPolicy::RejectNew => return false,
+                AttCacheFullPolicy::EvictOldest => {
+                    // All the entries share the same lifetime, so the oldest
+                    // is the one expiring first
+                    let oldest = self
+                        .entries
+                        .iter()
+                        .min_by_key(|(_, (_, expiry))| *expiry)
+                        .map(|(hash, _)| *hash);
+                    if let Some(oldest) = oldest {
+                        self.entries.remove(&oldest);
+                    }
+                }
+            }
+        }
+
+        self.entries.insert(hash, (att, expiry));
+        true
+    }
+}
+
+/// Coalesces the heartbeats fired within a minimum interval, so that the
+/// heartbeat checks run at most once per interval
+struct HeartbeatCoalescer {
+    min_interval: Duration,
+    last_handled: Option<Instant>,
+}
+
+impl HeartbeatCoalescer {
+    /// Creates a coalescer for heartbeats fired every `heartbeat_interval`.
+    ///
+    /// The minimum interval is a fraction of the heartbeat one, so that
+    /// regular heartbeats are never coalesced, whatever their interval.
+    fn new(heartbeat_interval: Duration) -> Self {
+        Self {
+            min_interval: heartbeat_interval / HEARTBEAT_COALESCING_DIVISOR,
+            last_handled: None,
+        }
+    }
+
+    /// Returns true if a heartbeat fired at `now` must be handled. In that
+    /// case, it's recorded as the last handled one.
+    fn try_handle(&mut self, now: Instant) -> bool {
+        if let Some(last) = self.last_handled {
+            if now.duration_since(last) < self.min_interval {
+                return false;
+            }
+        }
+        self.last_handled = Some(now);
+        true
+    }
+}
+
+/// Tracks the candidates recently requested to the network
+struct RecentRequests {
+    window: Duration,
+    requested: HashMap<[u8; 32], Instant>,
+}
+
+impl RecentRequests {
+    fn new(window: Duration) -> Self {
+        Self {
+            window,
+            requested: HashMap::new(),
+        }
+    }
+
+    /// Returns true if `hash` can be requested at `now`, i.e. it has not been
+    /// requested within the window. In that case, the request is recorded.
+    fn try_request(&mut self, hash: [u8; 32], now: Instant) -> bool {
+        let window = self.window;
+        self.requested.retain(|_, requested_at| {
+            now.duration_since(*requested_at) < window
+        });
+
+        if self.requested.contains_key(&hash) {
+            return false;
+        }
+        self.requested.insert(hash, now);
+        true
+    }
+}
+
+/// Returns true if a Quorum at `quorum_height` is more than `max_lookahead`
+/// blocks ahead of the local tip.
+fn exceeds_lookahead(
+    tip_height: u64,
+    quorum_height: u64,
+    max_lookahead: u64,
+) -> bool {
+    quorum_height > tip_height.saturating_add(max_lookahead)
+}
+
+#[cfg(test)]
+mod tests {
+    use node_data::ledger::{BlockWithLabel, Label};
+
+    use super::*;
+    use crate::chain::acceptor::tests::{
+        acceptor, attested_block, genesis, provisioners, store_block,
+    };
+    use crate::database::rocksdb::Backend;
+    use crate::database::{DatabaseOptions, DB};
+
+    #[test]
+    fn test_future_quorum_lookahead() {
+        let lookahead = Params::default().max_quorum_lookahead;
+        let tip = 1_000;
+
+        assert!(!exceeds_lookahead(tip, tip + 1, lookahead));
+        assert!(!exceeds_lookahead(tip, tip + lookahead, lookahead));
+        assert!(exceeds_lookahead(tip, tip + lookahead + 1, lookahead));
+
+        // A quorum thousands of blocks ahead is ignored
+        assert!(exceeds_lookahead(tip, tip + 5_000, lookahead));
+        assert!(!exceeds_lookahead(u64::MAX - 1, u64::MAX, lookahead));
+    }
+
+    #[test]
+    fn test_recent_requests() {
+        let window = Params::default().flood_request_window;
+        assert!(window > DEFAULT_ATT_CACHE_EXPIRY);
+        let mut requests = RecentRequests::new(window);
+        let now = Instant::now();
+        let hash = [1u8; 32];
+
+        assert!(requests.try_request(hash, now));
+        assert!(!requests.try_request(hash, now));
+
+        // The attestation has expired, but the candidate is still not
+        // requested again within the window
+        let after_expiry =
+            now + DEFAULT_ATT_CACHE_EXPIRY + Duration::from_secs(1);
+        assert!(!requests.try_request(hash, after_expiry));
+        assert!(requests.try_request([2u8; 32], after_expiry));
+
+        // Once the window has elapsed, it can be requested again
+        assert!(requests.try_request(hash, now + window));
+    }
+
+    #[test]
+    fn test_att_cache_full_policy() {
+        let now = Instant::now();
+        let fill = |policy| {
+            let mut cache = AttestationCache::new(2, policy);
+            for i in 0..2u8 {
+                let expiry = now + Duration::from_secs(i as u64 + 1);
+                assert!(cache.insert(
+                    [i; 32],
+                    Attestation::default(),
+                    expiry,
+                    now
+                ));
+            }
+            cache
+        };
+        let expiry = now + DEFAULT_ATT_CACHE_EXPIRY;
+
+        let mut cache = fill(AttCacheFullPolicy::EvictOldest);
+        assert!(cache.insert([9; 32], Attestation::default(), expiry, now));
+        assert!(!cache.contains(&[0; 32]));
+        assert!(cache.contains(&[1; 32]));
+        assert!(cache.contains(&[9; 32]));
+
+        let mut cache = fill(AttCacheFullPolicy::RejectNew);
+        assert!(!cache.insert([9; 32], Attestation::default(), expiry, now));
+        assert!(cache.contains(&[0; 32]));
+        assert!(cache.contains(&[1; 32]));
+        assert!(!cache.contains(&[9; 32]));
+
+        // Expired attestations make room regardless of the policy
+        let later = now + Duration::from_secs(1);
+        assert!(cache.insert([9; 32], Attestation::default(), expiry, later));
+        assert!(!cache.contains(&[0; 32]));
+    }
+
+    #[tokio::test]
+    async fn test_blacklist_reason() {
+        let blacklisted_blocks: SharedBlacklist = Default::default();
+        let loser = [1u8; 32];
+        let reverted = [2u8; 32];
+
+        let ttl = Params::default().blacklist_ttl;
+        let now = Instant::now();
+
+        // As done on a successful fallback and on a fork recovery
+        blacklist(
+            &blacklisted_blocks,
+            loser,
+            BlacklistReason::FallbackLoser,
+            ttl,
+        )
+        .await;
+        blacklist(
+            &blacklisted_blocks,
+            reverted,
+            BlacklistReason::ForkRecovery,
+            ttl,
+        )
+        .await;
+
+        assert_eq!(
+            blacklist_reason(&blacklisted_blocks, &loser, now).await,
+            Some(BlacklistReason::FallbackLoser)
+        );
+        assert_eq!(
+            blacklist_reason(&blacklisted_blocks, &reverted, now).await,
+            Some(BlacklistReason::ForkRecovery)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_fallback_blacklist_reason() {
+        let dir = tempdir::TempDir::new("test_fallback_blacklist_reason")
+            .expect("Temp directory to be created");
+        let conf = Params::default();
+        let (provisioners, keys) = provisioners(1);
+        let genesis = genesis();
+        let local =
+            attested_block(genesis.header(), 1, [1; 32], &provisioners, &keys);
+        let remote =
+            attested_block(genesis.header(), 0, [2; 32], &provisioners, &keys);
+        let local_hash = local.header().hash;
+
+        let db =
+            Backend::create_or_open(dir.path(), DatabaseOptions::default());
+        store_block(&db, &genesis, Label::Final(0));
+        store_block(&db, &local, Label::Accepted(1));
+        let tip = BlockWithLabel::new_with_label(local, Label::Accepted(1));
+        let acc = Arc::new(RwLock::new(acceptor(db, provisioners, tip, conf)));
+        let network = acc.read().await.network.clone();
+        let mut fsm = SimpleFSM::new(acc.clone(), network, conf).await;
+
+        // The remote block, produced at a lower iteration, wins the fallback.
+        // Its acceptance is then refused by the VM for its state root.
+        assert!(fsm.on_block_event(remote, None).await.is_err());
+        assert_eq!(acc.read().await.get_curr_height().await, 0);
+
+        // The local block is blacklisted as the loser of the fallback
+        assert_eq!(
+            fsm.blacklisted_blocks().await,
+            vec![(local_hash, BlacklistReason::FallbackLoser)]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_blacklist_ttl() {
+        let blacklisted_blocks: SharedBlacklist = Default::default();
+        let hash = [1u8; 32];
+        let ttl = Duration::from_secs(10);
+        let reason = BlacklistReason::FallbackLoser;
+
+        blacklist(&blacklisted_blocks, hash, reason, ttl).await;
+
+        let now = Instant::now();
+        assert_eq!(
+            blacklist_reason(&blacklisted_blocks, &hash, now).await,
+            Some(reason)
+        );
+
+        // Once the TTL has elapsed, the block can be accepted again
+        let after_ttl = now + ttl + Duration::from_secs(1);
+        assert_eq!(
+            blacklist_reason(&blacklisted_blocks, &hash, after_ttl).await,
+            None
+        );
+        assert!(blacklisted_blocks.read().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_blacklist_persistence() {
+        let dir = tempdir::TempDir::new("test_blacklist_persistence")
+            .expect("Temp directory to be created");
+        let conf = Params {
+            persist_blacklist: true,
+            ..Default::default()
+        };
+        let (provisioners, keys) = provisioners(1);
+        let genesis = genesis();
+        let blk =
+            attested_block(genesis.header(), 0, [9; 32], &provisioners, &keys);
+        let hash = blk.header().hash;
+        let expired = [2u8; 32];
+
+        {
+            let db = RwLock::new(Backend::create_or_open(
+                dir.path(),
+                DatabaseOptions::default(),
+            ));
+            let reason = BlacklistReason::ForkRecovery;
+            persist_blacklisted(&db, hash, reason, conf.blacklist_ttl).await;
+            let reason = BlacklistReason::FallbackLoser;
+            persist_blacklisted(&db, expired, reason, Duration::ZERO).await;
+        }
+
+        // A node restarted from the database still discards the block
+        let db =
+            Backend::create_or_open(dir.path(), DatabaseOptions::default());
+        store_block(&db, &genesis, Label::Final(0));
+        let tip = BlockWithLabel::new_with_label(genesis, Label::Final(0));
+        let acc = acceptor(db, provisioners, tip, conf);
+        let db = acc.db.clone();
+        let network = acc.network.clone();
+        let mut fsm =
+            SimpleFSM::new(Arc::new(RwLock::new(acc)), network, conf).await;
+
+        assert_eq!(
+            fsm.blacklisted_blocks().await,
+            vec![(hash, BlacklistReason::ForkRecovery)]
+        );
+        let res = fsm.on_block_event(blk.clone(), None).await;
+        assert!(matches!(res, Ok(None)));
+
+        // The expired entry is not restored, and is deleted from the database
+        let persisted = db
+            .read()
+            .await
+            .view(|t| t.blacklisted_blocks())
+            .expect("blacklist to be read");
+        assert_eq!(persisted.len(), 1);
+        assert_eq!(persisted[0].0, hash);
+
+        // Once cleared, the block is no longer blacklisted across restarts and
+        // reaches the acceptance, which the VM refuses for its state root
+        clear_blacklist(&fsm.blacklisted_blocks, &db, true).await;
+        assert!(load_blacklist(&*db.read().await).is_empty());
+        assert!(fsm.on_block_event(blk, None).await.is_err());
+    }
+
+    #[test]
+    fn test_heartbeat_coalescing() {
+        let interval = Duration::from_secs(1);
+        let mut heartbeats = HeartbeatCoalescer::new(interval);
+        let start = Instant::now();
+
+        // Heartbeats fired rapidly are handled once per half interval
+        let handled = (0..100)
+            .map(|i| start + Duration::from_millis(i * 10))
+            .filter(|now| heartbeats.try_handle(*now))
+            .count();
+        assert_eq!(handled, 2);
+
+        let next = start + interval;
+        assert!(heartbeats.try_handle(next));
+        assert!(!heartbeats.try_handle(next + Duration::from_millis(1)));
+
+        // Regular heartbeats are never coalesced, even with a short interval
+        let interval = Duration::from_millis(100);
+        let mut heartbeats = HeartbeatCoalescer::new(interval);
+        let handled = (0..10)
+            .map(|i| start + interval * i)
+            .filter(|now| heartbeats.try_handle(*now))
+            .count();
+        assert_eq!(handled, 10);
+        assert!(!heartbeats.try_handle(start + interval * 9 + interval / 4));
+    }
+
+    fn block(height: u64, prev_block_hash: [u8; 32]) -> Block {
+        let header = Header {
+            height,
+            prev_block_hash,
+            ..Default::default()
+        };
+        Block::new(header, vec![], vec![]).expect("block to be created")
+    }
+
+    #[test]
+    fn test_presync_continuity() {
+        let peer_addr = "127.0.0.1:9000".parse().unwrap();
+        let accepted = block(11, [1u8; 32]);
+        let accepted = accepted.header();
+
+        // A target linking to the accepted block allows the transition
+        let successor = block(12, accepted.hash);
+        let presync = PresyncInfo::from_block(peer_addr, successor, 10);
+        assert!(presync.chains_onto(accepted));
+
+        // A successor built on a different block does not
+        let forked = block(12, [2u8; 32]);
+        let presync = PresyncInfo::from_block(peer_addr, forked, 10);
+        assert!(!presync.chains_onto(accepted));
+
+        // Neither does a target that is not ahead of the accepted block
+        let presync = PresyncInfo::from_height(peer_addr, 11, 10);
+        assert!(!presync.chains_onto(accepted));
+
+        // A target further ahead is accepted as long as its pool is coherent
+        let presync = PresyncInfo::from_height(peer_addr, 20, 10);
+        assert!(presync.chains_onto(accepted));
+        let stale = block(11, [1u8; 32]);
+        let presync = PresyncInfo::from_block(peer_addr, stale, 10);
+        assert!(!presync.chains_onto(accepted));
+    }
+}
@@ -5,12 +5,14 @@
 // Copyright (c) DUSK NETWORK. All rights reserved.
 
 use std::cmp::Ordering;
+use std::collections::{BTreeMap, VecDeque};
 use std::ops::Deref;
 
 use node_data::message::payload::{GetResource, Inv, Quorum};
-use node_data::message::Message;
+use node_data::message::{Message, Version};
 
 use super::*;
+use crate::chain::conf::TieBreakPolicy;
 use crate::chain::fallback;
 
 pub(super) struct InSyncImpl<DB: database::DB, VM: vm::VMExecution, N: Network>
@@ -18,21 +20,30 @@ pub(super) struct InSyncImpl<DB: database::DB, VM: vm::VMExecution, N: Network>
     acc: Arc<RwLock<Acceptor<N, DB, VM>>>,
     network: Arc<RwLock<N>>,
 
-    blacklisted_blocks: SharedHashSet,
+    blacklisted_blocks: SharedBlacklist,
     presync: Option<PresyncInfo>,
+    catchup: CatchUpPool,
+
+    conf: Params,
 }
 
 impl<DB: database::DB, VM: vm::VMExecution, N: Network> InSyncImpl<DB, VM, N> {
     pub fn new(
         acc: Arc<RwLock<Acceptor<N, DB, VM>>>,
         network: Arc<RwLock<N>>,
-        blacklisted_blocks: SharedHashSet,
+        blacklisted_blocks: SharedBlacklist,
+        conf: Params,
     ) -> Self {
         Self {
             acc,
             network,
             blacklisted_blocks,
             presync: None,
+            catchup: CatchUpPool::new(
+                conf.catchup_window,
+                conf.max_catchup_blocks,
+            ),
+            conf,
         }
     }
 
@@ -52,7 +63,8 @@ impl<DB: database::DB, VM: vm::VMExecution, N: Network> InSyncImpl<DB, VM, N> {
 
     /// performed when exiting the state
     pub async fn on_exiting(&mut self) {
-        self.presync = None
+        self.presync = None;
+        self.catchup.clear();
     }
 
     pub async fn on_quorum(
@@ -70,6 +82,15 @@ impl<DB: database::DB, VM: vm::VMExecution, N: Network> InSyncImpl<DB, VM, N> {
                 // We use the quorum's previous block, to be sure that network
                 // already have the full block available
                 let remote_height = remote_quorum.header.round - 1;
+                // If we are only a few blocks behind, we request the missing
+                // blocks and accept them inline
+                if self.catchup.in_window(tip_height, remote_height) {
+                    let missing =
+                        self.catchup.missing(tip_height, remote_height + 1);
+                    self.request_blocks(&missing, peer_addr).await;
+                    return;
+                }
+
                 // Don't compare with `= tip + 1` because that's supposed to be
                 // handled by the InSync
                 if remote_height > tip_height + 1 {
@@ -169,93 +190,155 @@ impl<DB: database::DB, VM: vm::VMExecution, N: Network> InSyncImpl<DB, VM, N> {
             let local_header = local_blk.header();
             let local_height = local_header.height;
 
-            match remote_header.iteration.cmp(&local_header.iteration) {
-                Ordering::Less => {
-                    // If remote_blk.iteration < local_blk.iteration, then we
-                    // fallback to prev_blk and accept remote_blk
-                    info!(
-                        event = "entering fallback",
-                        height = local_height,
-                        iter = local_header.iteration,
-                        new_iter = remote_header.iteration,
-                    );
-
-                    // Retrieve prev_block state
-                    let prev_state = acc
-                        .db
-                        .read()
-                        .await
-                        .view(|t| {
-                            let res = t
-                                .block_header(&remote_header.prev_block_hash)?
-                                .map(|prev| prev.state_hash);
-
-                            anyhow::Ok(res)
-                        })?
-                        .ok_or_else(|| {
-                            anyhow::anyhow!("could not retrieve state_hash")
-                        })?;
-
-                    match fallback::WithContext::new(acc.deref())
-                        .try_revert(
-                            local_header,
-                            remote_header,
-                            RevertTarget::Commit(prev_state),
-                        )
-                        .await
-                    {
-                        Ok(_) => {
-                            // Successfully fallbacked to prev_blk
-                            counter!("dusk_fallback_count").increment(1);
-
-                            // Blacklist the local_blk so we discard it if
-                            // we receive it again
-                            self.blacklisted_blocks
-                                .write()
-                                .await
-                                .insert(local_header.hash);
-
-                            // After reverting we can accept `remote_blk` as the
-                            // new tip
-                            acc.try_accept_block(remote_blk, true).await?;
-                            return Ok(None);
-                        }
-                        Err(e) => {
-                            error!(
-                                event = "fallback failed",
-                                height = local_height,
-                                remote_height,
-                                err = format!("{:?}", e)
-                            );
-                            return Ok(None);
-                        }
-                    }
-                }
-
+            let replace_local = match remote_header
+                .iteration
+                .cmp(&local_header.iteration)
+            {
+                // If remote_blk.iteration < local_blk.iteration, then we
+                // fallback to prev_blk and accept remote_blk
+                Ordering::Less => true,
                 Ordering::Greater => {
                     // If remote_blk.iteration > local_blk.iteration, we send
-                    // the sender our local block. This
-                    // behavior is intended to make the peer
-                    // switch to our higher-priority block.
-                    if let Some(meta) = metadata {
-                        let remote_source = meta.src_addr;
-
-                        debug!("sending our lower-iteration block at height {local_height} to {remote_source}");
-
-                        let msg = Message::from(local_blk);
-                        let net = self.network.read().await;
-                        let send = net.send_to_peer(msg, remote_source);
-                        if let Err(e) = send.await {
-                            warn!("Unable to send_to_peer {e}")
-                        };
-                    }
+                    // our local block out. This behavior is intended to make
+                    // the peers switch to our higher-priority block.
+                    self.share_local_block(
+                        local_blk.clone(),
+                        metadata.as_ref(),
+                    )
+                    .await;
+                    false
                 }
                 Ordering::Equal => {
-                    // If remote_blk and local_blk have the same iteration, it
-                    // means two conflicting candidates have been generated
+                    // If remote_blk and local_blk have the same
+                    // iteration, it means two conflicting candidates
+                    // have been generated
                     let local_hash = to_str(&local_header.hash);
                     let remote_hash = to_str(&remote_header.hash);
                     warn!("Double candidate detected. Local block: {local_hash}, remote block {remote_hash}");
+
+                    let prefer_remote = prefer_remote(
+                        self.conf.tie_break,
+                        &local_header.hash,
+                        &remote_header.hash,
+                    );
+                    if !prefer_remote {
+                        debug!(
+                            event = "keeping local block",
+                            height = local_height,
+                            policy = ?self.conf.tie_break,
+                        );
+
+                        // If the tie-break is deterministic, the peers switch
+                        // to our block once they receive it
+                        if self.conf.tie_break
+                            == TieBreakPolicy::PreferLowerHash
+                        {
+                            self.share_local_block(
+                                local_blk.clone(),
+                                metadata.as_ref(),
+                            )
+                            .await;
+                        }
+                    }
+                    prefer_remote
+                }
+            };
+
+            if replace_local {
+                let depth = fork_depth(tip_height, remote_height);
+                if depth > self.conf.max_fork_depth {
+                    counter!("dusk_fallback_refused").increment(1);
+                    error!(
+                        event = "fallback refused",
+                        reason = "fork too deep",
+                        height = local_height,
+                        depth,
+                        max_depth = self.conf.max_fork_depth,
+                    );
+                    return Ok(None);
+                }
+
+                info!(
+                    event = "entering fallback",
+                    height = local_height,
+                    iter = local_header.iteration,
+                    new_iter = remote_header.iteration,
+                );
+
+                // Retrieve prev_block state
+                let prev_state = acc
+                    .db
+                    .read()
+                    .await
+                    .view(|t| {
+                        let res = t
+                            .block_header(&remote_header.prev_block_hash)?
+                            .map(|prev| prev.state_hash);
+
+                        anyhow::Ok(res)
+                    })?
+                    .ok_or_else(|| {
+                        anyhow::anyhow!("could not retrieve state_hash")
+                    })?;
+
+                match fallback::WithContext::new(acc.deref())
+                    .try_revert(
+                        local_header,
+                        remote_header,
+                        RevertTarget::Commit(prev_state),
+                        remote_header.iteration == local_header.iteration,
+                    )
+                    .await
+                {
+                    Ok(replaced) => {
+                        // Successfully fallbacked to prev_blk
+                        counter!("dusk_fallback_count").increment(1);
+                        acc.consensus_metrics.on_fallback();
+
+                        // Blacklist the local_blk so we discard it if
+                        // we receive it again
+                        blacklist(
+                            &self.blacklisted_blocks,
+                            local_header.hash,
+                            BlacklistReason::FallbackLoser,
+                            self.conf.blacklist_ttl,
+                        )
+                        .await;
+                        if self.conf.persist_blacklist {
+                            persist_blacklisted(
+                                &acc.db,
+                                local_header.hash,
+                                BlacklistReason::FallbackLoser,
+                                self.conf.blacklist_ttl,
+                            )
+                            .await;
+                        }
+
+                        // After reverting we can accept `remote_blk` as the
+                        // new tip
+                        let outcome = acc
+                            .try_accept_replacing(remote_blk, replaced, true)
+                            .await?;
+                        info!(
+                            event = "fallback completed",
+                            height = remote_height,
+                            replaced = ?outcome
+                                .replaced
+                                .iter()
+                                .map(|hash| to_str(hash))
+                                .collect::<Vec<_>>(),
+                        );
+                        return Ok(None);
+                    }
+                    Err(e) => {
+                        error!(
+                            event = "fallback failed",
+                            height = local_height,
+                            remote_height,
+                            err = format!("{:?}", e)
+                        );
+                        return Ok(None);
+                    }
                 }
             }
 
@@ -264,12 +347,53 @@ impl<DB: database::DB, VM: vm::VMExecution, N: Network> InSyncImpl<DB, VM, N> {
 
         // If remote_blk is a successor of our tip, we try to accept it
         if remote_height == tip_height + 1 {
-            let finalized = acc.try_accept_block(remote_blk, true).await?;
+            let outcome = acc.try_accept_block(remote_blk, true).await?;
 
             // On first final block accepted while we're inSync, clear
             // blacklisted blocks
-            if finalized {
-                self.blacklisted_blocks.write().await.clear();
+            if outcome.finalized {
+                clear_blacklist(
+                    &self.blacklisted_blocks,
+                    &acc.db,
+                    self.conf.persist_blacklist,
+                )
+                .await;
+            }
+
+            // Accept the blocks received ahead of the tip while catching up
+            let mut tip_height = remote_height;
+            let mut rejected = None;
+            while let Some(blk) = self.catchup.take_next(tip_height) {
+                match acc.try_accept_block(&blk, true).await {
+                    Ok(outcome) => {
+                        if outcome.finalized {
+                            clear_blacklist(
+                                &self.blacklisted_blocks,
+                                &acc.db,
+                                self.conf.persist_blacklist,
+                            )
+                            .await;
+                        }
+                        tip_height = blk.header().height;
+                    }
+                    Err(e) => {
+                        warn!(
+                            event = "catch-up block not accepted",
+                            height = blk.header().height,
+                            err = ?e,
+                        );
+                        // Only the rejected block is dropped, the ones above
+                        // it are kept until a valid replacement is received
+                        rejected = Some(blk.header().height);
+                        break;
+                    }
+                }
+            }
+
+            // Request the rejected block again from the peer that provided
+            // the last accepted one
+            if let (Some(height), Some(metadata)) = (rejected, &metadata) {
+                self.request_block(height, metadata.src_addr).await;
             }
 
             // If the accepted block is the one requested to presync peer,
@@ -284,13 +408,60 @@ impl<DB: database::DB, VM: vm::VMExecution, N: Network> InSyncImpl<DB, VM, N> {
                     })
                     .unwrap_or_default();
                 if same {
-                    return Ok(self.presync.take());
+                    let presync = self.presync.take();
+                    if presync
+                        .as_ref()
+                        .is_some_and(|p| p.chains_onto(remote_header))
+                    {
+                        return Ok(presync);
+                    }
+
+                    warn!(
+                        event = "presync reset",
+                        reason = "target does not chain onto accepted block",
+                        peer = ?metadata.src_addr,
+                        remote_height,
+                    );
                 }
             }
 
             return Ok(None);
         }
 
+        // Do not sync from peers running an incompatible protocol version
+        if let Some(metadata) = &metadata {
+            if !peer_version_allowed(self.conf.min_peer_version, metadata) {
+                counter!("dusk_sync_peer_refused").increment(1);
+                debug!(
+                    event = "sync refused",
+                    reason = "peer version below minimum",
+                    peer = ?metadata.src_addr,
+                    version = %metadata.version,
+                    remote_height,
+                );
+                return Ok(None);
+            }
+        }
+
+        // If remote_blk is only a few blocks ahead of our tip, we keep it
+        // and request the missing blocks instead of switching to outOfSync
+        // mode
+        if self.presync.is_none()
+            && self.catchup.in_window(tip_height, remote_height)
+        {
+            let missing = self.catchup.insert(tip_height, remote_blk.clone());
+            debug!(
+                event = "catching up",
+                tip_height,
+                remote_height,
+                missing = missing.len(),
+            );
+            if let Some(peer_addr) = metadata.map(|m| m.src_addr) {
+                self.request_blocks(&missing, peer_addr).await;
+            }
+            return Ok(None);
+        }
+
         // If remote_blk.height > tip.height+1, we might be out of sync.
         // Before switching to outOfSync mode and download missing blocks,
         // we ensure that the peer has a valid successor of tip
@@ -321,11 +492,53 @@ impl<DB: database::DB, VM: vm::VMExecution, N: Network> InSyncImpl<DB, VM, N> {
         Ok(None)
     }
 
+    /// Sends out `local_blk`, which has priority over a conflicting block
+    /// received from the network, so that the peers switch to it
+    async fn share_local_block(
+        &self,
+        local_blk: Block,
+        metadata: Option<&Metadata>,
+    ) {
+        let local_height = local_blk.header().height;
+        let action = conflict_action(metadata, self.conf.broadcast_on_conflict);
+        let msg = Message::from(local_blk);
+        let net = self.network.read().await;
+        match action {
+            ConflictAction::SendToPeer(remote_source) => {
+                debug!("sending our higher-priority block at height {local_height} to {remote_source}");
+
+                let send = net.send_to_peer(msg, remote_source);
+                if let Err(e) = send.await {
+                    warn!("Unable to send_to_peer {e}")
+                };
+            }
+            ConflictAction::Broadcast => {
+                debug!("broadcasting our higher-priority block at height {local_height}");
+
+                if let Err(e) = net.broadcast(&msg).await {
+                    warn!("Unable to broadcast {e}")
+                };
+            }
+            ConflictAction::Ignore => {}
+        }
+    }
+
     /// Requests a block by height from a `peer_addr`
     async fn request_block(&self, height: u64, peer_addr: SocketAddr) {
+        self.request_blocks(&[height], peer_addr).await
+    }
+
+    /// Requests a list of blocks by height from a `peer_addr`
+    async fn request_blocks(&self, heights: &[u64], peer_addr: SocketAddr) {
+        if heights.is_empty() {
+            return;
+        }
+
         let network = self.network.read().await;
         let mut inv = Inv::new(1);
-        inv.add_block_from_height(height);
+        for height in heights {
+            inv.add_block_from_height(*height);
+        }
         let this_peer = *network.public_addr();
         let req = GetResource::new(inv, Some(this_peer), u64::MAX, 1);
         debug!(event = "request block by height", ?req, ?peer_addr);
@@ -346,3 +559,356 @@ impl<DB: database::DB, VM: vm::VMExecution, N: Network> InSyncImpl<DB, VM, N> {
         Ok(false)
     }
 }
+
+/// Pool of blocks slightly ahead of the local tip.
+///
+/// When the node is only a few blocks behind the network, the missing blocks
+/// are requested and accepted inline, without switching to OutOfSync mode.
+/// The blocks received ahead of the tip are kept here until all the blocks in
+/// between are accepted. Once `capacity` blocks are pooled, the oldest one is
+/// evicted to make room for a new one.
+struct CatchUpPool {
+    /// Maximum distance from the tip of a block that can be caught up inline.
+    /// A value lower than 2 disables the inline catch-up.
+    window: u64,
+    capacity: usize,
+    blocks: BTreeMap<u64, Block>,
+    /// Heights of the pooled blocks, in order of arrival
+    arrivals: VecDeque<u64>,
+}
+
+impl CatchUpPool {
+    fn new(window: u64, capacity: usize) -> Self {
+        Self {
+            window,
+            capacity: capacity.max(1),
+            blocks: BTreeMap::new(),
+            arrivals: VecDeque::new(),
+        }
+    }
+
+    /// Returns `true` if a block at `height` is not a direct successor of
+    /// `tip` but is within the catch-up window
+    fn in_window(&self, tip: u64, height: u64) -> bool {
+        height > tip + 1 && height - tip <= self.window
+    }
+
+    /// Stores a block ahead of `tip`, returning the heights of the blocks
+    /// still missing in between.
+    ///
+    /// Pooled blocks are not verified yet, so the first block received for a
+    /// height is kept and any later one at the same height is ignored.
+    fn insert(&mut self, tip: u64, blk: Block) -> Vec<u64> {
+        let height = blk.header().height;
+        self.drop_stale(tip);
+        if !self.blocks.contains_key(&height) {
+            while self.blocks.len() >= self.capacity {
+                self.evict_oldest();
+            }
+            self.arrivals.push_back(height);
+            self.blocks.insert(height, blk);
+        }
+        self.missing(tip, height)
+    }
+
+    /// Removes the block that has been pooled first
+    fn evict_oldest(&mut self) {
+        if let Some(height) = self.arrivals.pop_front() {
+            self.blocks.remove(&height);
+            debug!(event = "catch-up block evicted", height);
+        }
+    }
+
+    /// Removes the blocks at or below `tip`
+    fn drop_stale(&mut self, tip: u64) {
+        self.blocks.retain(|h, _| *h > tip);
+        self.arrivals.retain(|h| *h > tip);
+    }
+
+    /// Returns the heights between `tip` and `height` that are not in the
+    /// pool
+    fn missing(&self, tip: u64, height: u64) -> Vec<u64> {
+        (tip + 1..height)
+            .filter(|h| !self.blocks.contains_key(h))
+            .collect()
+    }
+
+    /// Removes and returns the pooled successor of `tip`, if any
+    fn take_next(&mut self, tip: u64) -> Option<Block> {
+        self.drop_stale(tip);
+        let next = tip + 1;
+        self.arrivals.retain(|h| *h != next);
+        self.blocks.remove(&next)
+    }
+
+    fn clear(&mut self) {
+        self.blocks.clear();
+        self.arrivals.clear();
+    }
+}
+
+/// Action taken when a block with lower priority than our tip is received
+#[derive(Debug, PartialEq, Eq)]
+enum ConflictAction {
+    /// Send our block back to the peer the conflicting block came from
+    SendToPeer(SocketAddr),
+    /// Broadcast our block to the network
+    Broadcast,
+    Ignore,
+}
+
+/// Returns the number of blocks to revert in order to replace the block at
+/// `remote_height` in a chain whose tip is at `tip_height`
+fn fork_depth(tip_height: u64, remote_height: u64) -> u64 {
+    (tip_height + 1).saturating_sub(remote_height)
+}
+
+/// Returns true if the remote block must replace the local one, when both have
+/// the same height and iteration
+fn prefer_remote(
+    policy: TieBreakPolicy,
+    local_hash: &[u8; 32],
+    remote_hash: &[u8; 32],
+) -> bool {
+    match policy {
+        TieBreakPolicy::PreferLocal => false,
+        TieBreakPolicy::PreferNetwork => true,
+        TieBreakPolicy::PreferLowerHash => remote_hash < local_hash,
+    }
+}
+
+fn conflict_action(
+    metadata: Option<&Metadata>,
+    broadcast_on_conflict: bool,
+) -> ConflictAction {
+    match metadata {
+        Some(meta) => ConflictAction::SendToPeer(meta.src_addr),
+        None if broadcast_on_conflict => ConflictAction::Broadcast,
+        None => ConflictAction::Ignore,
+    }
+}
+
+/// Returns true if the node can sync from the sender of a message, given the
+/// minimum protocol version required
+fn peer_version_allowed(
+    min_version: Option<Version>,
+    metadata: &Metadata,
+) -> bool {
+    min_version.map_or(true, |min| metadata.version >= min)
+}
+
+#[cfg(test)]
+mod tests {
+    use node_data::ledger::Header;
+
+    use super::*;
+
+    fn block(height: u64) -> Block {
+        let header = Header {
+            height,
+            ..Default::default()
+        };
+        Block::new(header, vec![], vec![]).expect("block to be created")
+    }
+
+    #[test]
+    fn test_catchup_two_blocks_behind() {
+        let tip = 10;
+        let mut pool = CatchUpPool::new(3, 16);
+
+        // A node 2 blocks behind catches up inline
+        assert!(pool.in_window(tip, 12));
+        let missing = pool.insert(tip, block(12));
+        assert_eq!(missing, vec![11]);
+
+        // Nothing to accept until the missing block is received
+        assert!(pool.take_next(tip).is_none());
+
+        // Once the missing block is accepted, the pooled one follows
+        let next = pool.take_next(11).expect("block 12 to be pooled");
+        assert_eq!(next.header().height, 12);
+        assert!(pool.take_next(12).is_none());
+    }
+
+    #[test]
+    fn test_catchup_window() {
+        let tip = 10;
+        let pool = CatchUpPool::new(3, 16);
+
+        // The direct successor is handled as usual
+        assert!(!pool.in_window(tip, 11));
+        assert!(pool.in_window(tip, 13));
+
+        // Blocks beyond the window require a full sync
+        assert!(!pool.in_window(tip, 14));
+
+        // A window lower than 2 disables the inline catch-up
+        assert!(!CatchUpPool::new(1, 16).in_window(tip, 12));
+    }
+
+    #[test]
+    fn test_catchup_discards_stale_blocks() {
+        let mut pool = CatchUpPool::new(3, 16);
+        pool.insert(10, block(12));
+        pool.insert(10, block(13));
+
+        // The tip advanced through other means
+        assert!(pool.take_next(12).is_some_and(|b| b.header().height == 13));
+        assert!(pool.blocks.is_empty());
+        assert!(pool.arrivals.is_empty());
+    }
+
+    #[test]
+    fn test_catchup_keeps_first_block() {
+        let tip = 10;
+        let mut pool = CatchUpPool::new(3, 16);
+        pool.insert(tip, block(12));
+        pool.insert(tip, block(13));
+
+        // A different block at an already pooled height does not replace the
+        // first one
+        let honest = pool.blocks[&12].header().hash;
+        let header = Header {
+            height: 12,
+            timestamp: 1,
+            ..Default::default()
+        };
+        let junk = Block::new(header, vec![], vec![]).expect("valid block");
+        assert_ne!(junk.header().hash, honest);
+        pool.insert(tip, junk);
+        assert_eq!(pool.blocks[&12].header().hash, honest);
+
+        // If the pooled block at a height is rejected, only that height is
+        // missing again
+        let rejected = pool.take_next(11).expect("block 12 to be pooled");
+        assert_eq!(rejected.header().height, 12);
+        assert_eq!(pool.missing(11, 14), vec![12]);
+        assert!(pool.blocks.contains_key(&13));
+    }
+
+    #[test]
+    fn test_catchup_pool_capacity() {
+        let tip = 10;
+        let capacity = 4;
+        let mut pool = CatchUpPool::new(100, capacity);
+
+        // Blocks delivered out of order never exceed the capacity
+        let heights = [50, 20, 40, 30, 60, 15, 70, 25];
+        for height in heights {
+            pool.insert(tip, block(height));
+            assert!(pool.blocks.len() <= capacity);
+            assert_eq!(pool.blocks.len(), pool.arrivals.len());
+        }
+
+        // The oldest blocks have been evicted first
+        let pooled: Vec<_> = pool.blocks.keys().copied().collect();
+        assert_eq!(pooled, vec![15, 25, 60, 70]);
+
+        // Pooling the same height again does not evict any block
+        pool.insert(tip, block(60));
+        assert_eq!(pool.blocks.len(), capacity);
+        assert!(pool.blocks.contains_key(&15));
+    }
+
+    #[test]
+    fn test_fork_depth_limit() {
+        let max_depth = Params::default().max_fork_depth;
+        let tip = 1_000;
+
+        // Replacing the tip reverts a single block
+        assert_eq!(fork_depth(tip, tip), 1);
+        assert!(fork_depth(tip, tip + 1 - max_depth) <= max_depth);
+
+        // A fork deeper than the limit is refused
+        assert!(fork_depth(tip, tip - max_depth) > max_depth);
+    }
+
+    #[test]
+    fn test_tie_break_policy() {
+        let lower = [1u8; 32];
+        let higher = [2u8; 32];
+
+        assert!(!prefer_remote(TieBreakPolicy::PreferLocal, &higher, &lower));
+        assert!(prefer_remote(
+            TieBreakPolicy::PreferNetwork,
+            &lower,
+            &higher
+        ));
+
+        let policy = TieBreakPolicy::PreferLowerHash;
+        assert!(prefer_remote(policy, &higher, &lower));
+        assert!(!prefer_remote(policy, &lower, &higher));
+        assert_eq!(Params::default().tie_break, policy);
+    }
+
+    #[test]
+    fn test_tie_break_convergence() {
+        // Two conflicting blocks of the same height and iteration
+        let a = block(10);
+        let b = Block::new(
+            Header {
+                height: 10,
+                timestamp: 1,
+                ..Default::default()
+            },
+            vec![],
+            vec![],
+        )
+        .expect("block to be created");
+        let (a, b) = (a.header().hash, b.header().hash);
+        assert_ne!(a, b);
+
+        let kept = |local: [u8; 32], remote: [u8; 32]| {
+            let policy = TieBreakPolicy::PreferLowerHash;
+            if prefer_remote(policy, &local, &remote) {
+                remote
+            } else {
+                local
+            }
+        };
+
+        // Nodes converge on the same block regardless of the arrival order
+        assert_eq!(kept(a, b), kept(b, a));
+        assert_eq!(kept(a, b), a.min(b));
+    }
+
+    #[test]
+    fn test_conflict_action() {
+        let src_addr = "127.0.0.1:9000".parse().unwrap();
+        let meta = Metadata {
+            height: 0,
+            src_addr,
+            ray_id: String::new(),
+            version: Version::default(),
+        };
+
+        // Our block is sent back to the known sender
+        assert_eq!(
+            conflict_action(Some(&meta), false),
+            ConflictAction::SendToPeer(src_addr)
+        );
+
+        // Without metadata, our block is broadcast if enabled
+        assert_eq!(conflict_action(None, true), ConflictAction::Broadcast);
+        assert_eq!(conflict_action(None, false), ConflictAction::Ignore);
+    }
+
+    #[test]
+    fn test_peer_version_allowed() {
+        let meta = |version| Metadata {
+            height: 0,
+            src_addr: "127.0.0.1:9000".parse().unwrap(),
+            ray_id: String::new(),
+            version,
+        };
+        let min = Some(Version(1, 2, 0));
+
+        // A below-minimum peer offering a future block is not synced from
+        assert!(!peer_version_allowed(min, &meta(Version(1, 1, 9))));
+        assert!(!peer_version_allowed(min, &meta(Version(0, 9, 0))));
+
+        assert!(peer_version_allowed(min, &meta(Version(1, 2, 0))));
+        assert!(peer_version_allowed(min, &meta(Version(2, 0, 0))));
+        assert!(peer_version_allowed(None, &meta(Version(0, 0, 1))));
+    }
+}
@@ -4,7 +4,7 @@
 //
 // Copyright (c) DUSK NETWORK. All rights reserved.
 
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashMap};
 use std::net::{Ipv4Addr, SocketAddr, SocketAddrV4};
 use std::sync::Arc;
 use std::time::{Duration, SystemTime};
@@ -12,15 +12,16 @@ use std::time::{Duration, SystemTime};
 use node_data::ledger::Block;
 use node_data::message::payload::{GetResource, Inv, Quorum};
 use tokio::sync::RwLock;
-use tracing::{debug, info, warn};
+use tracing::{debug, info, trace, warn};
 
-use super::PresyncInfo;
+use super::{PresyncInfo, SyncStatus};
 use crate::chain::acceptor::Acceptor;
+use crate::chain::conf::SyncFailurePolicy;
 use crate::{database, vm, Network};
 
 const MAX_POOL_BLOCKS_SIZE: usize = 1000;
-const MAX_BLOCKS_TO_REQUEST: u64 = 100;
 const SYNC_TIMEOUT: Duration = Duration::from_secs(5);
+const MAX_SYNC_TIMEOUT: Duration = Duration::from_secs(20);
 
 /// The `OutOfSyncImpl` struct manages the synchronization state of a node
 /// that is out of sync with the network. It handles the detection of missing
@@ -50,14 +51,15 @@ const SYNC_TIMEOUT: Duration = Duration::from_secs(5);
 ///   sufficient blocks, the node may retry synchronization or restart its
 ///   consensus process.
 ///
-/// * `pool: BTreeMap<u64, Block>` - A rolling pool of blocks received from the
-///   network but not yet processed or accepted. The key is the block height,
-///   and the value is the `Block` itself. This pool is used to temporarily hold
-///   blocks until they can be processed sequentially. When a block is accepted,
-///   the pool is drained of consecutive blocks in order, helping to maintain
-///   efficient synchronization. The pool has a maximum size to prevent memory
-///   overflow, and blocks are prioritized based on their proximity to the
-///   current height.
+/// * `pool: BTreeMap<u64, PooledBlock>` - A rolling pool of blocks received
+///   from the network but not yet processed or accepted. The key is the block
+///   height, and the value is the `Block` itself along with its serialized
+///   size. This pool is used to temporarily hold blocks until they can be
+///   processed sequentially. When a block is accepted, the pool is drained of
+///   consecutive blocks in order, helping to maintain efficient
+///   synchronization. The pool has a maximum size, both in blocks and in bytes,
+///   to prevent memory overflow, and blocks are prioritized based on their
+///   proximity to the current height.
 ///
 /// * `remote_peer: SocketAddr` - The address of the peer from which blocks are
 ///   being requested. This peer is responsible for helping the node synchronize
@@ -70,6 +72,13 @@ const SYNC_TIMEOUT: Duration = Duration::from_secs(5);
 ///   reaches zero, the node will stop retrying and may transition back to an
 ///   in-sync state as a fallback.
 ///
+/// * `peers: SyncPeers` - The number of blocks accepted from each peer. On
+///   retry, missing blocks are requested to the peer that delivered the most.
+///
+/// * `timeout: Duration` - The current sync timeout. It is doubled on each
+///   retry, up to `MAX_SYNC_TIMEOUT`, so that slow peers are given more time,
+///   and reset to `SYNC_TIMEOUT` whenever a valid block is received.
+///
 /// * `acc: Arc<RwLock<Acceptor<N, DB, VM>>>` - A thread-safe reference to the
 ///   `Acceptor`, which is responsible for handling incoming blocks and managing
 ///   the consensus process during synchronization. The `Acceptor` is also used
@@ -87,6 +96,13 @@ const SYNC_TIMEOUT: Duration = Duration::from_secs(5);
 ///   included in block requests so that peers know where to send the requested
 ///   block data.
 ///
+/// * `max_blocks_to_request: u64` - The maximum number of blocks requested at
+///   once, i.e. the width of the rolling window described below.
+///
+/// * `sync_failure_policy: SyncFailurePolicy` - The behavior when a block fails
+///   acceptance: either abort the sync-up, or drop the block and request it
+///   again.
+///
 /// # Rolling Pool Mechanism
 ///
 /// The rolling pool is designed to efficiently handle block receipt and
@@ -105,7 +121,8 @@ const SYNC_TIMEOUT: Duration = Duration::from_secs(5);
 ///   (those with greater heights) may be removed to make space for more
 ///   relevant blocks. This ensures that the pool remains efficient and only
 ///   stores blocks that are close to the current height and are likely to be
-///   processed soon.
+///   processed soon. The same applies when the cumulative size of the pooled
+///   blocks would exceed the configured byte budget.
 ///
 /// - **Triggering Requests for Missing Blocks**: The node periodically checks
 ///   the pool to identify any missing blocks that have not yet been received.
@@ -116,18 +133,18 @@ const SYNC_TIMEOUT: Duration = Duration::from_secs(5);
 ///
 /// - **Rolling Window for Block Requests**: Block requests are made in chunks,
 ///   with the maximum number of blocks requested defined by
-///   `MAX_BLOCKS_TO_REQUEST`. As the node accepts blocks and its local height
+///   `max_blocks_to_request`. As the node accepts blocks and its local height
 ///   advances, it dynamically triggers new requests for any remaining missing
 ///   blocks within the sync range, creating a "rolling window" of requested
 ///   blocks. When the number of blocks requested drops below one-third of
-///   `MAX_BLOCKS_TO_REQUEST`, the node triggers new requests to maintain
+///   `max_blocks_to_request`, the node triggers new requests to maintain
 ///   consistent synchronization progress.
 ///
 /// - **Timeout and Retry Logic**: The sync process uses a timeout mechanism
 ///   (`SYNC_TIMEOUT`) to ensure that the node does not wait indefinitely for
 ///   blocks. If the timeout expires and progress is insufficient, the node
-///   retries the block requests or transitions back to the consensus process as
-///   a fallback.
+///   retries the block requests, with an exponentially increasing timeout, or
+///   transitions back to the consensus process as a fallback.
 ///
 /// This rolling pool mechanism allows the node to synchronize with the
 /// network efficiently, ensuring that blocks are processed in the correct
@@ -142,14 +159,34 @@ pub(super) struct OutOfSyncImpl<
     range: (u64, u64),
     last_request: u64,
     start_time: SystemTime,
-    pool: BTreeMap<u64, Block>,
+    pool: BTreeMap<u64, PooledBlock>,
     remote_peer: SocketAddr,
     attempts: u8,
+    timeout: Duration,
+    peers: SyncPeers,
 
     acc: Arc<RwLock<Acceptor<N, DB, VM>>>,
     network: Arc<RwLock<N>>,
 
     local_peer: SocketAddr,
+
+    max_blocks_to_request: u64,
+    sync_failure_policy: SyncFailurePolicy,
+    max_pool_bytes: usize,
+}
+
+/// A block held in the sync pool, along with its serialized size
+#[derive(Clone)]
+struct PooledBlock {
+    blk: Block,
+    size: usize,
+}
+
+impl PooledBlock {
+    fn new(blk: Block) -> Self {
+        let size = blk.size().unwrap_or_default();
+        Self { blk, size }
+    }
 }
 
 impl<DB: database::DB, VM: vm::VMExecution, N: Network>
@@ -158,6 +195,9 @@ impl<DB: database::DB, VM: vm::VMExecution, N: Network>
     pub async fn new(
         acc: Arc<RwLock<Acceptor<N, DB, VM>>>,
         network: Arc<RwLock<N>>,
+        max_blocks_to_request: u64,
+        sync_failure_policy: SyncFailurePolicy,
+        max_pool_bytes: usize,
     ) -> Self {
         let this_peer = *network.read().await.public_addr();
         Self {
@@ -173,6 +213,11 @@ impl<DB: database::DB, VM: vm::VMExecution, N: Network>
                 8000,
             )),
             attempts: 3,
+            timeout: SYNC_TIMEOUT,
+            peers: SyncPeers::default(),
+            max_blocks_to_request: request_window(max_blocks_to_request),
+            sync_failure_policy,
+            max_pool_bytes,
         }
     }
 
@@ -190,8 +235,8 @@ impl<DB: database::DB, VM: vm::VMExecution, N: Network>
 
         // add target_block to the pool
         self.drain_pool().await;
-        for b in &pool {
-            self.pool.insert(b.header().height, b.clone());
+        for b in pool {
+            self.pool.insert(b.header().height, PooledBlock::new(b));
         }
         self.remote_peer = peer_addr;
 
@@ -202,7 +247,7 @@ impl<DB: database::DB, VM: vm::VMExecution, N: Network>
         let (from, to) = &self.range;
         info!(event = "entering", from, to, ?peer_addr);
         for (_, b) in self.pool.clone() {
-            let _ = self.on_block_event(&b).await;
+            let _ = self.on_block_event(&b.blk, Some(peer_addr)).await;
         }
     }
 
@@ -236,10 +281,13 @@ impl<DB: database::DB, VM: vm::VMExecution, N: Network>
     /// Handles consecutive block acceptance, pool draining, and state
     /// transition checks.
     ///
+    /// `src_addr` is the peer the block has been received from, if any.
+    ///
     /// Returns `true` if the node should transition back to the in-sync state.
     pub async fn on_block_event(
         &mut self,
         blk: &Block,
+        src_addr: Option<SocketAddr>,
     ) -> anyhow::Result<bool> {
         let mut acc = self.acc.write().await;
         let block_height = blk.header().height;
@@ -253,6 +301,10 @@ impl<DB: database::DB, VM: vm::VMExecution, N: Network>
 
         let current_height = acc.get_curr_height().await;
         if block_height <= current_height {
+            // Our own blocks echoed back by peers are already in the ledger
+            if acc.is_self_authored(blk.header()).await {
+                trace!(event = "self-authored block echoed", block_height);
+            }
             return Ok(false);
         }
 
@@ -267,9 +319,18 @@ impl<DB: database::DB, VM: vm::VMExecution, N: Network>
 
         // Try accepting consecutive block
         if block_height == current_height + 1 {
-            acc.try_accept_block(blk, false).await?;
+            if let Err(err) = acc.try_accept_block(blk, false).await {
+                let policy = self.sync_failure_policy;
+                skip_failed_block(policy, &mut self.pool, block_height, err)?;
+                self.request_missing_block(block_height).await;
+                return Ok(false);
+            }
             // reset expiry_time only if we receive a valid block
             self.start_time = SystemTime::now();
+            self.timeout = SYNC_TIMEOUT;
+            if let Some(src_addr) = src_addr {
+                self.peers.record(src_addr);
+            }
             debug!(
                 event = "accepted block",
                 block_height,
@@ -280,10 +341,17 @@ impl<DB: database::DB, VM: vm::VMExecution, N: Network>
             // Try to accept other consecutive blocks from the pool, if
             // available
             for height in self.range.0..=self.range.1 {
-                if let Some(blk) = self.pool.get(&height) {
-                    acc.try_accept_block(blk, false).await?;
+                if let Some(pooled) = self.pool.get(&height) {
+                    let res = acc.try_accept_block(&pooled.blk, false).await;
+                    if let Err(err) = res {
+                        let policy = self.sync_failure_policy;
+                        skip_failed_block(policy, &mut self.pool, height, err)?;
+                        self.request_missing_block(height).await;
+                        break;
+                    }
                     // reset expiry_time only if we receive a valid block
                     self.start_time = SystemTime::now();
+                    self.timeout = SYNC_TIMEOUT;
                     self.range.0 += 1;
                     debug!(
                         event = "accepted next block",
@@ -324,7 +392,7 @@ impl<DB: database::DB, VM: vm::VMExecution, N: Network>
                 self.pool.clear();
 
                 // Block sync-up procedure manages to download all requested
-                acc.restart_consensus().await;
+                acc.restart_consensus_after_sync().await;
 
                 // Transit to InSync mode
                 return Ok(true);
@@ -345,7 +413,8 @@ impl<DB: database::DB, VM: vm::VMExecution, N: Network>
         }
 
         // If we almost dequeued all requested blocks (2/3)
-        if self.last_request < current_height + (MAX_BLOCKS_TO_REQUEST / 3) {
+        if self.last_request < current_height + (self.max_blocks_to_request / 3)
+        {
             if let Some(last_request) = self.request_pool_missing_blocks().await
             {
                 self.last_request = last_request
@@ -369,8 +438,28 @@ impl<DB: database::DB, VM: vm::VMExecution, N: Network>
             }
         }
 
+        // Make room for the block within the byte budget, evicting the
+        // pooled blocks with greater heights if needed
+        let pooled = PooledBlock::new(blk.clone());
+        if !make_room(
+            &mut self.pool,
+            |b| b.size,
+            block_height,
+            pooled.size,
+            self.max_pool_bytes,
+        ) {
+            info!(
+                event = "block skipped",
+                reason = "pool byte budget exceeded",
+                block_height,
+                size = pooled.size,
+                budget = self.max_pool_bytes,
+            );
+            return Ok(false);
+        }
+
         // add block to the pool
-        self.pool.insert(block_height, blk.clone());
+        self.pool.insert(block_height, pooled);
 
         debug!(
             event = "block saved",
@@ -381,8 +470,18 @@ impl<DB: database::DB, VM: vm::VMExecution, N: Network>
         Ok(false)
     }
 
+    /// Returns the heights within the current sync range that are present
+    /// in the pool and the ones that are still missing.
+    pub fn pool_status(&self) -> (Vec<u64>, Vec<u64>) {
+        pool_status(self.range, &self.pool)
+    }
+
+    pub fn sync_status(&self) -> SyncStatus {
+        sync_status(self.range, &self.pool)
+    }
+
     fn is_timeout_expired(&self) -> bool {
-        self.start_time.checked_add(SYNC_TIMEOUT).unwrap() <= SystemTime::now()
+        self.start_time.checked_add(self.timeout).unwrap() <= SystemTime::now()
     }
 
     pub async fn on_heartbeat(&mut self) -> anyhow::Result<bool> {
@@ -397,6 +496,11 @@ impl<DB: database::DB, VM: vm::VMExecution, N: Network>
                 return Ok(true);
             }
 
+            // Prefer the peer that delivered most of the blocks so far
+            if let Some(peer) = self.peers.best() {
+                self.remote_peer = peer;
+            }
+
             // Request missing from local_pool blocks
             if let Some(last_request) = self.request_pool_missing_blocks().await
             {
@@ -404,7 +508,9 @@ impl<DB: database::DB, VM: vm::VMExecution, N: Network>
             }
 
             self.start_time = SystemTime::now();
+            self.timeout = next_sync_timeout(self.timeout);
             self.attempts -= 1;
+            debug!(event = "sync retry", attempts = self.attempts, timeout = ?self.timeout);
         }
 
         Ok(false)
@@ -433,21 +539,16 @@ impl<DB: database::DB, VM: vm::VMExecution, N: Network>
     ///
     /// Returns the height of the last block requested, if any.
     async fn request_pool_missing_blocks(&self) -> Option<u64> {
-        let mut last_request = None;
-        let mut inv = Inv::new(0);
+        let heights = heights_to_request(
+            self.range,
+            &self.pool,
+            self.max_blocks_to_request,
+        );
+        let last_request = heights.last().copied();
 
-        let mut inv_count = 0;
-        for height in self.range.0..=self.range.1 {
-            if self.pool.contains_key(&height) {
-                // already received
-                continue;
-            }
+        let mut inv = Inv::new(0);
+        for height in heights {
             inv.add_block_from_height(height);
-            inv_count += 1;
-            last_request = Some(height);
-            if inv_count >= MAX_BLOCKS_TO_REQUEST {
-                break;
-            }
         }
 
         if !inv.inv_list.is_empty() {
@@ -474,3 +575,364 @@ impl<DB: database::DB, VM: vm::VMExecution, N: Network>
         last_request
     }
 }
+
+/// Number of blocks accepted from each peer during a sync-up
+#[derive(Default)]
+struct SyncPeers {
+    scores: HashMap<SocketAddr, u64>,
+}
+
+impl SyncPeers {
+    fn record(&mut self, peer: SocketAddr) {
+        *self.scores.entry(peer).or_default() += 1;
+    }
+
+    /// Returns the peer that delivered the most blocks, if any
+    fn best(&self) -> Option<SocketAddr> {
+        self.scores
+            .iter()
+            .max_by_key(|(peer, score)| (**score, *peer))
+            .map(|(peer, _)| *peer)
+    }
+}
+
+/// Splits the heights of `range` into the ones present in `pool` and the
+/// missing ones.
+fn pool_status<B>(
+    (from, to): (u64, u64),
+    pool: &BTreeMap<u64, B>,
+) -> (Vec<u64>, Vec<u64>) {
+    (from..=to).partition(|h| pool.contains_key(h))
+}
+
+/// Handles a block failing acceptance during a sync-up, according to `policy`.
+///
+/// Returns the acceptance error if the sync-up must be aborted, otherwise
+/// drops the block from the pool so that it can be requested again.
+fn skip_failed_block<B>(
+    policy: SyncFailurePolicy,
+    pool: &mut BTreeMap<u64, B>,
+    height: u64,
+    err: anyhow::Error,
+) -> anyhow::Result<()> {
+    match policy {
+        SyncFailurePolicy::Abort => Err(err),
+        SyncFailurePolicy::Skip => {
+            warn!(
+                event = "block skipped",
+                reason = "not accepted",
+                height,
+                ?err
+            );
+            pool.remove(&height);
+            Ok(())
+        }
+    }
+}
+
+/// Makes room in `pool` for a block at `height` of `size` bytes, so that the
+/// cumulative size of the pooled blocks stays within `budget`. The pooled
+/// blocks with greater heights are evicted first, as the lower ones are
+/// processed sooner.
+///
+/// Returns false, leaving `pool` untouched, if the block cannot fit.
+fn make_room<B>(
+    pool: &mut BTreeMap<u64, B>,
+    size_of: impl Fn(&B) -> usize,
+    height: u64,
+    size: usize,
+    budget: usize,
+) -> bool {
+    let mut used: usize = pool.values().map(&size_of).sum();
+    let evictable: usize =
+        pool.range(height + 1..).map(|(_, b)| size_of(b)).sum();
+    if used.saturating_sub(evictable).saturating_add(size) > budget {
+        return false;
+    }
+
+    while used.saturating_add(size) > budget {
+        match pool.last_entry() {
+            Some(entry) => {
+                debug!(
+                    event = "block removed",
+                    reason = "pool byte budget exceeded",
+                    block_height = height,
+                    stored_height = *entry.key(),
+                );
+                used -= size_of(&entry.remove());
+            }
+            None => break,
+        }
+    }
+
+    true
+}
+
+/// Returns the status of a sync-up over `range`, given the blocks in `pool`
+fn sync_status<B>(range: (u64, u64), pool: &BTreeMap<u64, B>) -> SyncStatus {
+    let (from, to) = range;
+    let pooled = pool.keys().filter(|h| (from..=to).contains(*h)).count();
+    SyncStatus::OutOfSync { range, pooled }
+}
+
+/// Returns the sync timeout to apply after a retry: the current one doubled,
+/// capped to `MAX_SYNC_TIMEOUT`
+fn next_sync_timeout(timeout: Duration) -> Duration {
+    timeout.saturating_mul(2).min(MAX_SYNC_TIMEOUT)
+}
+
+/// Returns the heights of `range` missing from `pool`, up to `window` of them
+fn heights_to_request<B>(
+    (from, to): (u64, u64),
+    pool: &BTreeMap<u64, B>,
+    window: u64,
+) -> Vec<u64> {
+    (from..=to)
+        .filter(|h| !pool.contains_key(h))
+        .take(window as usize)
+        .collect()
+}
+
+/// Bounds the configured request window between 1 and the size of the pool, as
+/// requesting more blocks than the pool can hold is pointless
+fn request_window(max_blocks_to_request: u64) -> u64 {
+    max_blocks_to_request.clamp(1, MAX_POOL_BLOCKS_SIZE as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::Ordering;
+
+    use node_data::ledger::{BlockWithLabel, Label};
+
+    use super::*;
+    use crate::chain::acceptor::tests::{
+        acceptor_with_keys, attested_block, genesis, provisioners, store_block,
+    };
+    use crate::chain::conf::Params;
+    use crate::database::rocksdb::Backend;
+    use crate::database::{DatabaseOptions, DB};
+
+    #[test]
+    fn test_pool_status() {
+        let pool: BTreeMap<u64, ()> =
+            [10, 11, 14, 20].into_iter().map(|h| (h, ())).collect();
+
+        // Heights outside the range are ignored
+        let (present, missing) = pool_status((11, 15), &pool);
+        assert_eq!(present, vec![11, 14]);
+        assert_eq!(missing, vec![12, 13, 15]);
+
+        let (present, missing) = pool_status((16, 18), &pool);
+        assert!(present.is_empty());
+        assert_eq!(missing, vec![16, 17, 18]);
+    }
+
+    #[test]
+    fn test_request_window() {
+        let pool: BTreeMap<u64, ()> =
+            [12, 13].into_iter().map(|h| (h, ())).collect();
+
+        // Heights already in the pool do not count towards the window
+        let window = request_window(5);
+        let heights = heights_to_request((10, 100), &pool, window);
+        assert_eq!(heights, vec![10, 11, 14, 15, 16]);
+
+        // The window never exceeds the sync range
+        let heights = heights_to_request((10, 15), &pool, request_window(50));
+        assert_eq!(heights, vec![10, 11, 14, 15]);
+
+        let heights =
+            heights_to_request((1, 10_000), &pool, request_window(20));
+        assert_eq!(heights.len(), 20);
+
+        // Invalid windows are bounded
+        assert_eq!(request_window(0), 1);
+        assert_eq!(request_window(u64::MAX), MAX_POOL_BLOCKS_SIZE as u64);
+    }
+
+    #[test]
+    fn test_sync_failure_policy() {
+        // Simulate the acceptance of the pooled blocks, `false` marking an
+        // unacceptable block
+        fn accept_pool(
+            policy: SyncFailurePolicy,
+            tip: &mut u64,
+            pool: &mut BTreeMap<u64, bool>,
+        ) -> anyhow::Result<()> {
+            while let Some(&valid) = pool.get(&(*tip + 1)) {
+                let height = *tip + 1;
+                if !valid {
+                    let err = anyhow::anyhow!("invalid block {height}");
+                    skip_failed_block(policy, pool, height, err)?;
+                    break;
+                }
+                pool.remove(&height);
+                *tip = height;
+            }
+            Ok(())
+        }
+
+        let blocks = [(11, true), (12, true), (13, false), (14, true)];
+
+        let mut tip = 10;
+        let mut pool: BTreeMap<u64, bool> = blocks.into_iter().collect();
+        assert!(
+            accept_pool(SyncFailurePolicy::Abort, &mut tip, &mut pool).is_err()
+        );
+        assert_eq!(tip, 12);
+        assert!(pool.contains_key(&13));
+
+        let mut tip = 10;
+        let mut pool: BTreeMap<u64, bool> = blocks.into_iter().collect();
+        accept_pool(SyncFailurePolicy::Skip, &mut tip, &mut pool).unwrap();
+        assert_eq!(tip, 12);
+        assert!(!pool.contains_key(&13));
+
+        // The block requested again is valid, the sync-up continues past it
+        pool.insert(13, true);
+        accept_pool(SyncFailurePolicy::Skip, &mut tip, &mut pool).unwrap();
+        assert_eq!(tip, 14);
+        assert!(pool.is_empty());
+    }
+
+    #[test]
+    fn test_sync_status() {
+        let mut range = (11, 20);
+        let mut pool: BTreeMap<u64, ()> =
+            [12, 13, 14, 30].into_iter().map(|h| (h, ())).collect();
+
+        let status = sync_status(range, &pool);
+        assert_eq!(status, SyncStatus::OutOfSync { range, pooled: 3 });
+        assert_eq!(status.target(), Some(20));
+        assert_eq!(status.remaining(), 10);
+
+        // Accept the next block and drain the consecutive pooled ones
+        let mut last_remaining = status.remaining();
+        for _ in 11..=14 {
+            range.0 += 1;
+            pool.retain(|h, _| *h >= range.0);
+
+            let remaining = sync_status(range, &pool).remaining();
+            assert!(remaining < last_remaining);
+            last_remaining = remaining;
+        }
+        let status = sync_status(range, &pool);
+        assert_eq!(status, SyncStatus::OutOfSync { range, pooled: 0 });
+        assert_eq!(status.remaining(), 6);
+
+        // Target reached
+        range.0 = 21;
+        assert_eq!(sync_status(range, &pool).remaining(), 0);
+        assert_eq!(SyncStatus::InSync.remaining(), 0);
+        assert_eq!(SyncStatus::InSync.target(), None);
+    }
+
+    #[test]
+    fn test_sync_peers() {
+        let fast: SocketAddr = "127.0.0.1:9000".parse().unwrap();
+        let slow: SocketAddr = "127.0.0.1:9001".parse().unwrap();
+
+        let mut peers = SyncPeers::default();
+        assert_eq!(peers.best(), None);
+
+        // The slow peer delivers the first block, then the fast peer
+        // consistently delivers the following ones
+        peers.record(slow);
+        assert_eq!(peers.best(), Some(slow));
+        for _ in 0..5 {
+            peers.record(fast);
+            if peers.scores[&fast] > 1 {
+                assert_eq!(peers.best(), Some(fast));
+            }
+        }
+        peers.record(slow);
+        assert_eq!(peers.best(), Some(fast));
+    }
+
+    #[test]
+    fn test_sync_timeout_backoff() {
+        // Simulate the heartbeats expiring the timeout on each attempt
+        let mut timeout = SYNC_TIMEOUT;
+        let mut intervals = vec![timeout];
+        for _ in 0..3 {
+            timeout = next_sync_timeout(timeout);
+            intervals.push(timeout);
+        }
+
+        let secs: Vec<_> = intervals.iter().map(|d| d.as_secs()).collect();
+        assert_eq!(secs, vec![5, 10, 20, 20]);
+
+        // The overall sync-up is still bounded
+        let total: Duration = intervals.iter().sum();
+        assert_eq!(total, Duration::from_secs(55));
+    }
+
+    #[test]
+    fn test_pool_byte_budget() {
+        let budget = 10 * 1024;
+        let oversized = 3 * 1024;
+        let mut pool: BTreeMap<u64, usize> = BTreeMap::new();
+
+        // Pool many oversized blocks
+        for height in 20..100 {
+            if make_room(&mut pool, |s| *s, height, oversized, budget) {
+                pool.insert(height, oversized);
+            }
+            assert!(pool.values().sum::<usize>() <= budget);
+        }
+        assert_eq!(pool.keys().copied().collect::<Vec<_>>(), [20, 21, 22]);
+
+        // A block closer to the tip evicts the greatest heights
+        assert!(make_room(&mut pool, |s| *s, 11, 2 * oversized, budget));
+        pool.insert(11, 2 * oversized);
+        assert_eq!(pool.keys().copied().collect::<Vec<_>>(), [11, 20]);
+        assert!(pool.values().sum::<usize>() <= budget);
+
+        // A block that cannot fit is refused
+        assert!(!make_room(&mut pool, |s| *s, 12, budget + 1, budget));
+        assert_eq!(pool.keys().copied().collect::<Vec<_>>(), [11, 20]);
+    }
+
+    #[tokio::test]
+    async fn test_echoed_block_not_accepted() {
+        let dir = tempdir::TempDir::new("test_echoed_block_not_accepted")
+            .expect("Temp directory to be created");
+        let conf = Params::default();
+        let (provisioners, keys) = provisioners(1);
+        let genesis = genesis();
+        let own =
+            attested_block(genesis.header(), 0, [1; 32], &provisioners, &keys);
+
+        // The tip is a block generated by this node
+        let db =
+            Backend::create_or_open(dir.path(), DatabaseOptions::default());
+        store_block(&db, &genesis, Label::Final(0));
+        store_block(&db, &own, Label::Accepted(1));
+        let tip =
+            BlockWithLabel::new_with_label(own.clone(), Label::Accepted(1));
+        let (pk, sk) = keys[0].clone();
+        let acc = acceptor_with_keys(db, provisioners, tip, conf, (sk, pk));
+        let network = acc.network.clone();
+        let vm = acc.vm.clone();
+        let acc = Arc::new(RwLock::new(acc));
+        assert!(acc.read().await.is_self_authored(own.header()).await);
+
+        let mut oos = OutOfSyncImpl::new(
+            acc.clone(),
+            network,
+            conf.max_blocks_to_request,
+            conf.sync_failure_policy,
+            conf.max_sync_pool_bytes,
+        )
+        .await;
+
+        // The block echoed back by a peer is not accepted again
+        let peer = "127.0.0.1:9000".parse().unwrap();
+        let res = oos.on_block_event(&own, Some(peer)).await;
+        assert!(matches!(res, Ok(false)));
+        assert_eq!(vm.read().await.accept_calls.load(Ordering::Relaxed), 0);
+        assert_eq!(acc.read().await.get_curr_height().await, 1);
+        assert!(oos.pool.is_empty());
+    }
+}
@@ -136,7 +136,7 @@ impl<DB: database::DB, N: Network, VM: VMExecution> StalledChainFSM<DB, N, VM> {
 
     /// Handles a running state
     async fn on_running(&mut self) {
-        if self.tip.1 + ACCEPT_TIMEOUT < node_data::get_current_timestamp() {
+        if accept_timed_out(self.tip.1, node_data::get_current_timestamp()) {
             // While we are still receiving blocks, no block
             // has been accepted for a long time (tip has not changed
             // recently)
@@ -286,3 +286,39 @@ impl<DB: database::DB, N: Network, VM: VMExecution> StalledChainFSM<DB, N, VM> {
         Ok((hdr.hash, hdr.height))
     }
 }
+
+/// Returns true if no block has been accepted for more than `ACCEPT_TIMEOUT`
+/// since `tip_timestamp`
+fn accept_timed_out(tip_timestamp: u64, now: u64) -> bool {
+    tip_timestamp + ACCEPT_TIMEOUT < now
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::chain::conf::Params;
+
+    /// Returns the seconds elapsed since the tip when a stall is detected by
+    /// heartbeats ticking at `interval`
+    fn detection_delay(interval: Duration) -> u64 {
+        let tip = 1_000;
+        (1..)
+            .map(|tick| tip + tick * interval.as_secs())
+            .find(|now| accept_timed_out(tip, *now))
+            .unwrap()
+            - tip
+    }
+
+    #[test]
+    fn test_heartbeat_interval_stall_detection() {
+        let default = Params::default().heartbeat_interval;
+
+        assert!(detection_delay(default) > ACCEPT_TIMEOUT);
+        assert_eq!(detection_delay(Duration::from_secs(1)), ACCEPT_TIMEOUT + 1);
+        assert!(
+            detection_delay(Duration::from_secs(1)) < detection_delay(default)
+        );
+    }
+}
@@ -5,13 +5,13 @@
 // Copyright (c) DUSK NETWORK. All rights reserved.
 
 use std::cmp;
-use std::collections::BTreeMap;
 use std::sync::Arc;
 
 use dusk_bytes::Serializable;
 use dusk_consensus::config::{
-    is_emergency_block, is_emergency_iter, CONSENSUS_MAX_ITER,
-    MINIMUM_BLOCK_TIME, MIN_EMERGENCY_BLOCK_TIME, RELAX_ITERATION_THRESHOLD,
+    is_ahead_of_time, is_emergency_block, is_emergency_iter,
+    CONSENSUS_MAX_ITER, MINIMUM_BLOCK_TIME, MIN_EMERGENCY_BLOCK_TIME,
+    RELAX_ITERATION_THRESHOLD,
 };
 use dusk_consensus::errors::{
     AttestationError, FailedIterationError, HeaderError,
@@ -20,7 +20,9 @@ use dusk_consensus::operations::Voter;
 use dusk_consensus::quorum::verifiers;
 use dusk_consensus::quorum::verifiers::QuorumResult;
 use dusk_consensus::user::committee::CommitteeSet;
-use dusk_consensus::user::provisioners::{ContextProvisioners, Provisioners};
+use dusk_consensus::user::provisioners::{
+    verify_fault_culprits, ContextProvisioners, Provisioners,
+};
 use dusk_core::signatures::bls::{
     MultisigPublicKey, MultisigSignature, PublicKey as BlsPublicKey,
 };
@@ -38,8 +40,6 @@ use tracing::{debug, error};
 use crate::database;
 use crate::database::Ledger;
 
-const MARGIN_TIMESTAMP: u64 = 3;
-
 // TODO: Use thiserror instead of anyhow
 
 #[derive(Debug, Error)]
@@ -189,7 +189,7 @@ impl<'a, DB: database::DB> Validator<'a, DB> {
 
         let local_time = get_current_timestamp();
 
-        if candidate_block.timestamp > local_time + MARGIN_TIMESTAMP {
+        if is_ahead_of_time(candidate_block.timestamp, local_time) {
             return Err(HeaderError::BlockTimeHigher(
                 candidate_block.timestamp,
             ));
@@ -367,7 +367,7 @@ impl<'a, DB: database::DB> Validator<'a, DB> {
         )
         .await;
 
-        merge_voters(validation_voters, ratification_voters)
+        verifiers::merge_voters(validation_voters, ratification_voters)
     }
 
     /// Verify faults inside a block.
@@ -376,20 +376,35 @@ impl<'a, DB: database::DB> Validator<'a, DB> {
         current_height: u64,
         faults: &[Fault],
     ) -> Result<(), InvalidFault> {
-        verify_faults(self.db.clone(), current_height, faults).await
+        verify_faults(
+            self.db.clone(),
+            current_height,
+            faults,
+            self.provisioners,
+        )
+        .await
     }
 }
 
+/// Verifies the faults of the block at `current_height`.
+///
+/// `provisioners` are the provisioners eligible at `current_height`. Each fault
+/// must accuse a provisioner of its round, whenever that set is known.
 pub async fn verify_faults<DB: database::DB>(
     db: Arc<RwLock<DB>>,
     current_height: u64,
     faults: &[Fault],
+    provisioners: &ContextProvisioners,
 ) -> Result<(), InvalidFault> {
     for f in faults {
         let fault_header = f.validate(current_height)?;
         if is_emergency_iter(fault_header.iteration) {
             return Err(InvalidFault::EmergencyIteration);
         }
+        verify_fault_culprits(std::slice::from_ref(f), |round| {
+            provisioners.at_round(round, current_height)
+        })
+        .map_err(|_| InvalidFault::NotProvisioner)?;
         db.read()
             .await
             .view(|db| {
@@ -453,46 +468,11 @@ pub async fn verify_att(
         }
     }
 
-    let committee = RwLock::new(CommitteeSet::new(curr_eligible_provisioners));
-    let vote = att.result.vote();
-
-    // Verify validation
-    let (val_result, validation_voters) = verifiers::verify_step_votes(
+    verifiers::verify_attestation(
+        att,
         &consensus_header,
-        vote,
-        &att.validation,
-        &committee,
         curr_seed,
-        StepName::Validation,
+        curr_eligible_provisioners,
     )
     .await
-    .map_err(|s| AttestationError::InvalidVotes(StepName::Validation, s))?;
-
-    // Verify ratification
-    let (rat_result, ratification_voters) = verifiers::verify_step_votes(
-        &consensus_header,
-        vote,
-        &att.ratification,
-        &committee,
-        curr_seed,
-        StepName::Ratification,
-    )
-    .await
-    .map_err(|s| AttestationError::InvalidVotes(StepName::Ratification, s))?;
-
-    let voters = merge_voters(validation_voters, ratification_voters);
-    Ok((val_result, rat_result, voters))
-}
-
-/// Merges two Vec<Voter>, summing up the usize values if the PublicKey is
-/// repeated
-fn merge_voters(v1: Vec<Voter>, v2: Vec<Voter>) -> Vec<Voter> {
-    let mut voter_map = BTreeMap::new();
-
-    for (pk, count) in v1.into_iter().chain(v2.into_iter()) {
-        let counter = voter_map.entry(pk).or_default();
-        *counter += count;
-    }
-
-    voter_map.into_iter().collect()
 }
@@ -8,6 +8,7 @@ use std::collections::VecDeque;
 use std::io;
 use std::io::{Read, Write};
 use std::ops::Div;
+use std::sync::{Arc, RwLock};
 use std::time::Duration;
 
 use node_data::Serializable;
@@ -69,6 +70,56 @@ impl Serializable for AverageElapsedTime {
         Ok(Self(vec))
     }
 }
+
+/// Snapshot of the consensus health, readable without a metrics scrape
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ConsensusMetrics {
+    /// Number of reverts of a stalled chain
+    pub revert_count: u64,
+    /// Number of fallbacks to a lower-iteration block
+    pub fallback_count: u64,
+    /// Duration of the last round, measured between the timestamps of the
+    /// last two accepted blocks
+    pub last_round_duration: Option<Duration>,
+    /// Iteration at which the last accepted block was produced
+    pub current_iteration: u8,
+}
+
+/// Shared tracker of the [ConsensusMetrics]
+#[derive(Clone, Default)]
+pub(crate) struct ConsensusMetricsTracker(Arc<RwLock<ConsensusMetrics>>);
+
+impl ConsensusMetricsTracker {
+    pub fn on_revert(&self) {
+        self.update(|m| m.revert_count += 1);
+    }
+
+    pub fn on_fallback(&self) {
+        self.update(|m| m.fallback_count += 1);
+    }
+
+    pub fn on_block_accepted(&self, round_duration: Duration, iteration: u8) {
+        self.update(|m| {
+            m.last_round_duration = Some(round_duration);
+            m.current_iteration = iteration;
+        });
+    }
+
+    pub fn snapshot(&self) -> ConsensusMetrics {
+        match self.0.read() {
+            Ok(m) => *m,
+            Err(poisoned) => *poisoned.into_inner(),
+        }
+    }
+
+    fn update(&self, f: impl FnOnce(&mut ConsensusMetrics)) {
+        match self.0.write() {
+            Ok(mut m) => f(&mut m),
+            Err(poisoned) => f(&mut poisoned.into_inner()),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -94,4 +145,20 @@ mod tests {
             expected
         );
     }
+
+    #[test]
+    fn test_consensus_metrics_snapshot() {
+        let tracker = ConsensusMetricsTracker::default();
+        assert_eq!(tracker.snapshot(), ConsensusMetrics::default());
+
+        tracker.on_block_accepted(Duration::from_secs(10), 2);
+        tracker.on_fallback();
+
+        // Snapshots are shared among clones
+        let snapshot = tracker.clone().snapshot();
+        assert_eq!(snapshot.fallback_count, 1);
+        assert_eq!(snapshot.revert_count, 0);
+        assert_eq!(snapshot.last_round_duration, Some(Duration::from_secs(10)));
+        assert_eq!(snapshot.current_iteration, 2);
+    }
 }
@@ -0,0 +1,121 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use std::time::Duration;
+
+use dusk_consensus::config::MIN_STEP_TIMEOUT;
+use metrics::gauge;
+use node_data::get_current_timestamp;
+use tracing::warn;
+
+/// Detects a skew between the local clock and the timestamps of the blocks
+/// produced by the network.
+///
+/// A significant skew makes the node mistime the consensus rounds.
+///
+/// A block timestamp is set when the candidate is generated, so its arrival
+/// includes the latency of the Validation and Ratification steps. Only
+/// iteration-0 blocks are sampled, and their expected latency is deducted.
+pub(crate) struct ClockSkewDetector {
+    threshold: Duration,
+    /// Returns the local time, in seconds since the UNIX epoch
+    clock: fn() -> u64,
+}
+
+impl ClockSkewDetector {
+    pub fn new(threshold: Duration) -> Self {
+        Self::with_clock(threshold, get_current_timestamp)
+    }
+
+    fn with_clock(threshold: Duration, clock: fn() -> u64) -> Self {
+        Self { threshold, clock }
+    }
+
+    /// Compares the local time with the timestamp of a block just produced
+    /// by the network at `iteration`.
+    ///
+    /// Returns the skew in seconds (positive if the local clock is ahead) if
+    /// it exceeds the threshold. Blocks of later iterations are not sampled,
+    /// as their timestamp precedes the end of the round by an unknown amount.
+    pub fn check(&self, block_timestamp: u64, iteration: u8) -> Option<i64> {
+        if iteration != 0 {
+            return None;
+        }
+
+        let elapsed = (self.clock)() as i64 - block_timestamp as i64;
+        // Validation and Ratification steps, at their minimum timeout
+        let latency = 2 * MIN_STEP_TIMEOUT.as_secs() as i64;
+        let skew = if elapsed > 0 {
+            elapsed.saturating_sub(latency).max(0)
+        } else {
+            elapsed
+        };
+        gauge!("dusk_clock_skew").set(skew as f64);
+
+        self.exceeds(skew)
+    }
+
+    /// Compares the local time with the timestamp of the local tip.
+    ///
+    /// As the tip can be arbitrarily old, only a local clock behind the tip
+    /// is reported.
+    pub fn check_tip(&self, tip_timestamp: u64) -> Option<i64> {
+        let skew = (self.clock)() as i64 - tip_timestamp as i64;
+        if skew > 0 {
+            return None;
+        }
+        gauge!("dusk_clock_skew").set(skew as f64);
+
+        self.exceeds(skew)
+    }
+
+    fn exceeds(&self, skew: i64) -> Option<i64> {
+        if skew.unsigned_abs() <= self.threshold.as_secs() {
+            return None;
+        }
+
+        warn!(
+            event = "clock skew detected",
+            skew_secs = skew,
+            threshold_secs = self.threshold.as_secs(),
+        );
+        Some(skew)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NOW: u64 = 1_700_000_000;
+
+    #[test]
+    fn test_clock_skew() {
+        let threshold = Duration::from_secs(30);
+        let detector = ClockSkewDetector::with_clock(threshold, || NOW);
+
+        let latency = 2 * MIN_STEP_TIMEOUT.as_secs();
+
+        assert_eq!(detector.check(NOW - 10, 0), None);
+        assert_eq!(detector.check(NOW + 30, 0), None);
+
+        // The latency of the consensus steps is not a skew
+        assert_eq!(detector.check(NOW - latency - 30, 0), None);
+
+        // Local clock ahead of the network
+        assert_eq!(detector.check(NOW - latency - 60, 0), Some(60));
+        // Local clock behind the network
+        assert_eq!(detector.check(NOW + 60, 0), Some(-60));
+
+        // Blocks of later iterations are not sampled
+        assert_eq!(detector.check(NOW - 600, 5), None);
+        assert_eq!(detector.check(NOW - 600, u8::MAX), None);
+
+        // An old tip is not a skew, a tip from the future is
+        assert_eq!(detector.check_tip(NOW - 3600), None);
+        assert_eq!(detector.check_tip(NOW + 60), Some(-60));
+    }
+}
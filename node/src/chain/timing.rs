@@ -0,0 +1,158 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use dusk_consensus::user::provisioners::Provisioners;
+use node_data::bls::PublicKeyBytes;
+use node_data::ledger::Header;
+
+/// Converts between block heights and approximate wall-clock times.
+///
+/// The conversion assumes blocks are produced at a constant interval since
+/// genesis, so it only gives an estimate for heights far from the tip.
+#[derive(Debug, Clone, Copy)]
+pub struct HeightClock {
+    /// Timestamp of the genesis block, in seconds since the UNIX epoch
+    genesis_timestamp: u64,
+    block_interval: Duration,
+}
+
+impl HeightClock {
+    pub fn new(genesis_timestamp: u64, block_interval: Duration) -> Self {
+        Self {
+            genesis_timestamp,
+            block_interval,
+        }
+    }
+
+    /// Returns the approximate time at which the block at `height` is
+    /// produced.
+    pub fn height_to_time(&self, height: u64) -> SystemTime {
+        let elapsed = self.block_interval.as_secs().saturating_mul(height);
+        UNIX_EPOCH
+            + Duration::from_secs(
+                self.genesis_timestamp.saturating_add(elapsed),
+            )
+    }
+
+    /// Returns the approximate height of the block produced at `time`.
+    ///
+    /// Times before genesis map to the genesis height.
+    pub fn time_to_height(&self, time: SystemTime) -> u64 {
+        let timestamp = time
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or_default();
+        let elapsed = timestamp.saturating_sub(self.genesis_timestamp);
+
+        match self.block_interval.as_secs() {
+            0 => 0,
+            interval => elapsed / interval,
+        }
+    }
+}
+
+/// Forecast of the block following the current tip.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BlockForecast {
+    /// Round of the next block
+    pub round: u64,
+    /// Generator of the first iteration of `round`
+    pub generator: PublicKeyBytes,
+    /// Earliest time at which the next block can be produced
+    pub expected_time: SystemTime,
+}
+
+impl BlockForecast {
+    /// Forecasts the block following `tip`, assuming it is produced at the
+    /// first iteration no earlier than `block_interval` after the tip.
+    pub fn next(
+        tip: &Header,
+        provisioners: &Provisioners,
+        block_interval: Duration,
+    ) -> Self {
+        let round = tip.height + 1;
+        let generator = provisioners.get_generator(0, tip.seed, round);
+        let expected_time = UNIX_EPOCH
+            + Duration::from_secs(
+                tip.timestamp.saturating_add(block_interval.as_secs()),
+            );
+
+        Self {
+            round,
+            generator,
+            expected_time,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use dusk_core::signatures::bls::{
+        PublicKey as BlsPublicKey, SecretKey as BlsSecretKey,
+    };
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    use super::*;
+
+    const GENESIS: u64 = 1_700_000_000;
+
+    #[test]
+    fn test_height_clock_round_trip() {
+        let interval = Duration::from_secs(10);
+        let clock = HeightClock::new(GENESIS, interval);
+
+        assert_eq!(
+            clock.height_to_time(0),
+            UNIX_EPOCH + Duration::from_secs(GENESIS)
+        );
+        assert_eq!(clock.time_to_height(UNIX_EPOCH), 0);
+
+        for height in [0, 1, 42, 1_000_000] {
+            let time = clock.height_to_time(height);
+            assert_eq!(clock.time_to_height(time), height);
+
+            // Any time within the block interval maps to the same height
+            let later = time + interval - Duration::from_secs(1);
+            assert_eq!(clock.time_to_height(later), height);
+
+            let back = clock.height_to_time(clock.time_to_height(later));
+            assert!(later.duration_since(back).unwrap() < interval);
+        }
+    }
+
+    #[test]
+    fn test_next_block_forecast() {
+        let rng = &mut StdRng::seed_from_u64(0xbeef);
+        let mut provisioners = Provisioners::empty();
+        for _ in 0..10 {
+            let sk = BlsSecretKey::random(rng);
+            let pk = node_data::bls::PublicKey::new(BlsPublicKey::from(&sk));
+            provisioners.add_member_with_value(pk, 1_000_000_000_000);
+        }
+
+        let tip = Header {
+            height: 41,
+            timestamp: GENESIS,
+            seed: [5; 48].into(),
+            ..Default::default()
+        };
+        let forecast =
+            BlockForecast::next(&tip, &provisioners, Duration::from_secs(10));
+
+        assert_eq!(forecast.round, 42);
+        assert_eq!(
+            forecast.generator,
+            provisioners.get_generator(0, tip.seed, 42)
+        );
+        assert!(
+            forecast.expected_time
+                > UNIX_EPOCH + Duration::from_secs(tip.timestamp)
+        );
+    }
+}
@@ -7,6 +7,7 @@
 use std::collections::HashSet;
 use std::path::Path;
 
+pub mod memory;
 pub mod rocksdb;
 
 use anyhow::Result;
@@ -137,6 +138,24 @@ pub trait ConsensusStorage {
         F: FnOnce([u8; 32]) -> bool + std::marker::Copy;
 
     fn count_validation_results(&self) -> usize;
+
+    /// Blacklisted blocks Storage
+    ///
+    /// `expiry` is the UNIX timestamp, in seconds, the entry expires at.
+    fn store_blacklisted_block(
+        &mut self,
+        hash: &[u8; 32],
+        reason: u8,
+        expiry: u64,
+    ) -> Result<()>;
+
+    /// Returns the hash of the blacklisted blocks along with their reason and
+    /// expiry.
+    fn blacklisted_blocks(&self) -> Result<Vec<([u8; 32], u8, u64)>>;
+
+    fn delete_blacklisted_block(&mut self, hash: &[u8; 32]) -> Result<()>;
+
+    fn clear_blacklisted_blocks(&mut self) -> Result<()>;
 }
 
 pub trait Mempool {
@@ -193,6 +212,15 @@ pub trait Mempool {
 
     /// Number of persisted transactions
     fn mempool_txs_count(&self) -> usize;
+
+    /// Number of transactions per gas price bucket, sorted by gas price (asc).
+    ///
+    /// Each bucket is identified by its lowest gas price, a multiple of
+    /// `bucket_size`. Empty buckets are omitted.
+    fn mempool_gas_histogram(
+        &self,
+        bucket_size: u64,
+    ) -> Result<Vec<(u64, usize)>>;
 }
 
 pub trait Metadata {
@@ -201,6 +229,9 @@ pub trait Metadata {
 
     /// Reads an value of a key from the Metadata CF
     fn op_read(&self, key: &[u8]) -> Result<Option<Vec<u8>>>;
+
+    /// Deletes a key from the Metadata CF
+    fn op_delete(&mut self, key: &[u8]) -> Result<()>;
 }
 
 pub trait Persist:
@@ -211,6 +242,38 @@ pub trait Persist:
     fn clear_database(&mut self) -> Result<()>;
     fn commit(self) -> Result<()>;
     fn rollback(self) -> Result<()>;
+
+    /// Returns the header of the current chain tip, if any.
+    fn tip_header(&self) -> Result<Option<Header>> {
+        match self.op_read(rocksdb::MD_HASH_KEY)? {
+            Some(hash) => self.block_header(&hash),
+            None => Ok(None),
+        }
+    }
+
+    /// Returns the iterations that produced the last `n` accepted blocks,
+    /// from the oldest one to the tip.
+    fn recent_tip_iterations(&self, n: usize) -> Result<Vec<u8>> {
+        let tip = match self.tip_header()? {
+            Some(tip) if n > 0 => tip,
+            _ => return Ok(vec![]),
+        };
+
+        let from = (tip.height + 1).saturating_sub(n as u64);
+        let mut iterations = Vec::with_capacity(n.min(tip.height as usize + 1));
+        for height in from..tip.height {
+            let header = self
+                .block_hash_by_height(height)?
+                .map(|hash| self.block_header(&hash))
+                .transpose()?
+                .flatten()
+                .ok_or_else(|| anyhow::anyhow!("block {height} not found"))?;
+            iterations.push(header.iteration);
+        }
+        iterations.push(tip.iteration);
+
+        Ok(iterations)
+    }
 }
 
 pub fn into_array<const N: usize>(value: &[u8]) -> [u8; N] {
@@ -237,6 +300,23 @@ pub struct DatabaseOptions {
 
     /// Enables a set of flags for collecting DB stats as log data.
     pub enable_debug: bool,
+
+    /// Storage backend of the candidate blocks
+    #[serde(default)]
+    pub candidates_backend: CandidatesBackend,
+}
+
+/// Defines where the candidate blocks are stored
+#[derive(
+    Serialize, Deserialize, Copy, Clone, Debug, Default, PartialEq, Eq,
+)]
+#[serde(rename_all = "kebab-case")]
+pub enum CandidatesBackend {
+    /// Candidates are persisted in the database
+    #[default]
+    Persistent,
+    /// Candidates are kept in memory only, avoiding the disk I/O
+    InMemory,
 }
 
 impl Default for DatabaseOptions {
@@ -246,6 +326,7 @@ impl Default for DatabaseOptions {
             mempool_cf_max_write_buffer_size: 10 * 1024 * 1024, // 10 MiB
             blocks_cf_disable_block_cache: true,
             enable_debug: false,
+            candidates_backend: CandidatesBackend::default(),
         }
     }
 }
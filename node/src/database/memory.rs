@@ -0,0 +1,55 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use std::collections::HashMap;
+
+use node_data::ledger::Block;
+use node_data::message::ConsensusHeader;
+
+/// In-memory storage of the candidate blocks.
+///
+/// Unlike the persistent column families, changes applied here take effect
+/// immediately and are not reverted if the enclosing database transaction is
+/// rolled back. Candidates are ephemeral data that can always be requested
+/// again to the network, so this is acceptable for nodes that prefer to
+/// avoid the disk I/O.
+#[derive(Default, Debug)]
+pub struct MemoryCandidates {
+    blocks: HashMap<[u8; 32], Block>,
+}
+
+impl MemoryCandidates {
+    pub fn store(&mut self, b: Block) {
+        self.blocks.insert(b.header().hash, b);
+    }
+
+    pub fn get(&self, hash: &[u8]) -> Option<Block> {
+        let hash: [u8; 32] = hash.try_into().ok()?;
+        self.blocks.get(&hash).cloned()
+    }
+
+    pub fn get_by_iteration(&self, ch: &ConsensusHeader) -> Option<Block> {
+        self.blocks
+            .values()
+            .find(|b| {
+                b.header().prev_block_hash == ch.prev_block_hash
+                    && b.header().iteration == ch.iteration
+            })
+            .cloned()
+    }
+
+    /// Deletes all candidates whose height satisfies `closure`
+    pub fn delete<F>(&mut self, closure: F)
+    where
+        F: FnOnce(u64) -> bool + std::marker::Copy,
+    {
+        self.blocks.retain(|_, b| !closure(b.header().height));
+    }
+
+    pub fn count(&self) -> usize {
+        self.blocks.len()
+    }
+}
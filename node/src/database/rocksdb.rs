@@ -8,7 +8,7 @@ use std::cell::RefCell;
 use std::collections::HashSet;
 use std::io::{Read, Write};
 use std::path::Path;
-use std::sync::Arc;
+use std::sync::{Arc, RwLock};
 use std::{io, vec};
 
 use anyhow::Result;
@@ -25,9 +25,10 @@ use rocksdb::{
 };
 use tracing::info;
 
+use super::memory::MemoryCandidates;
 use super::{
-    ConsensusStorage, DatabaseOptions, Ledger, LightBlock, Metadata, Persist,
-    DB,
+    into_array, CandidatesBackend, ConsensusStorage, DatabaseOptions, Ledger,
+    LightBlock, Metadata, Persist, DB,
 };
 use crate::database::Mempool;
 
@@ -42,6 +43,7 @@ const CF_MEMPOOL: &str = "cf_mempool";
 const CF_MEMPOOL_SPENDING_ID: &str = "cf_mempool_spending_id";
 const CF_MEMPOOL_FEES: &str = "cf_mempool_fees";
 const CF_METADATA: &str = "cf_metadata";
+const CF_BLACKLIST: &str = "cf_blacklist";
 
 const DB_FOLDER_NAME: &str = "chain.db";
 
@@ -52,10 +54,13 @@ pub const MD_AVG_VALIDATION: &[u8] = b"avg_validation_time";
 pub const MD_AVG_RATIFICATION: &[u8] = b"avg_ratification_time";
 pub const MD_AVG_PROPOSAL: &[u8] = b"avg_proposal_time";
 pub const MD_LAST_ITER: &[u8] = b"consensus_last_iter";
+pub const MD_PENDING_INBOUND: &[u8] = b"consensus_pending_inbound";
 
 #[derive(Clone)]
 pub struct Backend {
     rocksdb: Arc<OptimisticTransactionDB>,
+    /// Candidates storage, if they are not persisted in the database
+    memory_candidates: Option<Arc<RwLock<MemoryCandidates>>>,
 }
 
 impl Backend {
@@ -122,6 +127,11 @@ impl Backend {
             .cf_handle(CF_METADATA)
             .expect("CF_METADATA column family must exist");
 
+        let blacklist_cf = self
+            .rocksdb
+            .cf_handle(CF_BLACKLIST)
+            .expect("CF_BLACKLIST column family must exist");
+
         DBTransaction::<'_, OptimisticTransactionDB> {
             inner,
             candidates_cf,
@@ -135,6 +145,8 @@ impl Backend {
             fees_cf,
             ledger_height_cf,
             metadata_cf,
+            blacklist_cf,
+            memory_candidates: self.memory_candidates.clone(),
             cumulative_inner_size: RefCell::new(0),
         }
     }
@@ -210,6 +222,7 @@ impl DB for Backend {
                 blocks_cf_opts.clone(),
             ),
             ColumnFamilyDescriptor::new(CF_METADATA, blocks_cf_opts.clone()),
+            ColumnFamilyDescriptor::new(CF_BLACKLIST, blocks_cf_opts.clone()),
             ColumnFamilyDescriptor::new(CF_MEMPOOL, mp_opts.clone()),
             ColumnFamilyDescriptor::new(
                 CF_MEMPOOL_SPENDING_ID,
@@ -218,6 +231,13 @@ impl DB for Backend {
             ColumnFamilyDescriptor::new(CF_MEMPOOL_FEES, mp_opts.clone()),
         ];
 
+        let memory_candidates = match db_opts.candidates_backend {
+            CandidatesBackend::Persistent => None,
+            CandidatesBackend::InMemory => {
+                Some(Arc::new(RwLock::new(MemoryCandidates::default())))
+            }
+        };
+
         Self {
             rocksdb: Arc::new(
                 OptimisticTransactionDB::open_cf_descriptors(
@@ -227,6 +247,7 @@ impl DB for Backend {
                 )
                 .expect("should be a valid database in {path}"),
             ),
+            memory_candidates,
         }
     }
 
@@ -283,6 +304,8 @@ pub struct DBTransaction<'db, DB: DBAccess> {
     // Candidates column family
     candidates_cf: &'db ColumnFamily,
     candidates_height_cf: &'db ColumnFamily,
+    // In-memory candidates storage, replacing the candidates column families
+    memory_candidates: Option<Arc<RwLock<MemoryCandidates>>>,
     // ValidationResults column family
     validation_results_cf: &'db ColumnFamily,
 
@@ -298,6 +321,9 @@ pub struct DBTransaction<'db, DB: DBAccess> {
     fees_cf: &'db ColumnFamily,
 
     metadata_cf: &'db ColumnFamily,
+
+    // Blacklisted blocks column family
+    blacklist_cf: &'db ColumnFamily,
 }
 
 impl<'db, DB: DBAccess> Ledger for DBTransaction<'db, DB> {
@@ -581,6 +607,11 @@ impl<'db, DB: DBAccess> ConsensusStorage for DBTransaction<'db, DB> {
     /// Returns `Ok(())` if the block is successfully stored, or an error if the
     /// operation fails.
     fn store_candidate(&mut self, b: Block) -> Result<()> {
+        if let Some(candidates) = &self.memory_candidates {
+            write_lock(candidates)?.store(b);
+            return Ok(());
+        }
+
         let mut serialized = vec![];
         b.write(&mut serialized)?;
 
@@ -605,6 +636,10 @@ impl<'db, DB: DBAccess> ConsensusStorage for DBTransaction<'db, DB> {
     /// Returns `Ok(Some(block))` if the block is found, `Ok(None)` if the block
     /// is not found, or an error if the operation fails.
     fn candidate(&self, hash: &[u8]) -> Result<Option<Block>> {
+        if let Some(candidates) = &self.memory_candidates {
+            return Ok(read_lock(candidates)?.get(hash));
+        }
+
         if let Some(blob) = self.inner.get_cf(self.candidates_cf, hash)? {
             let b = Block::read(&mut &blob[..])?;
             return Ok(Some(b));
@@ -618,6 +653,12 @@ impl<'db, DB: DBAccess> ConsensusStorage for DBTransaction<'db, DB> {
         &self,
         consensus_header: &ConsensusHeader,
     ) -> Result<Option<Block>> {
+        if let Some(candidates) = &self.memory_candidates {
+            return Ok(
+                read_lock(candidates)?.get_by_iteration(consensus_header)
+            );
+        }
+
         let iter = self
             .inner
             .iterator_cf(self.candidates_cf, IteratorMode::Start);
@@ -650,6 +691,11 @@ impl<'db, DB: DBAccess> ConsensusStorage for DBTransaction<'db, DB> {
     where
         F: FnOnce(u64) -> bool + std::marker::Copy,
     {
+        if let Some(candidates) = &self.memory_candidates {
+            write_lock(candidates)?.delete(closure);
+            return Ok(());
+        }
+
         let iter = self
             .inner
             .iterator_cf(self.candidates_height_cf, IteratorMode::Start);
@@ -666,6 +712,10 @@ impl<'db, DB: DBAccess> ConsensusStorage for DBTransaction<'db, DB> {
     }
 
     fn count_candidates(&self) -> usize {
+        if let Some(candidates) = &self.memory_candidates {
+            return candidates.read().map(|c| c.count()).unwrap_or_default();
+        }
+
         let iter = self
             .inner
             .iterator_cf(self.candidates_height_cf, IteratorMode::Start);
@@ -783,6 +833,52 @@ impl<'db, DB: DBAccess> ConsensusStorage for DBTransaction<'db, DB> {
     fn clear_validation_results(&mut self) -> Result<()> {
         self.delete_validation_results(|_| true)
     }
+
+    fn store_blacklisted_block(
+        &mut self,
+        hash: &[u8; 32],
+        reason: u8,
+        expiry: u64,
+    ) -> Result<()> {
+        let mut value = vec![reason];
+        value.extend_from_slice(&expiry.to_le_bytes());
+        self.put_cf(self.blacklist_cf, hash, value)
+    }
+
+    /// Entries stored without an expiry are returned as already expired
+    fn blacklisted_blocks(&self) -> Result<Vec<([u8; 32], u8, u64)>> {
+        self.inner
+            .iterator_cf(self.blacklist_cf, IteratorMode::Start)
+            .map(|entry| -> Result<_> {
+                let (key, value) = entry?;
+                let reason = value.first().copied().unwrap_or_default();
+                let expiry = value
+                    .get(1..9)
+                    .and_then(|b| b.try_into().ok())
+                    .map(u64::from_le_bytes)
+                    .unwrap_or_default();
+                Ok((into_array(&key), reason, expiry))
+            })
+            .collect()
+    }
+
+    fn delete_blacklisted_block(&mut self, hash: &[u8; 32]) -> Result<()> {
+        self.inner.delete_cf(self.blacklist_cf, hash)?;
+        Ok(())
+    }
+
+    /// Deletes all items from the `CF_BLACKLIST` column family.
+    fn clear_blacklisted_blocks(&mut self) -> Result<()> {
+        let iter = self
+            .inner
+            .iterator_cf(self.blacklist_cf, IteratorMode::Start);
+
+        for (key, _) in iter.map(Result::unwrap) {
+            self.inner.delete_cf(self.blacklist_cf, key)?;
+        }
+
+        Ok(())
+    }
 }
 
 impl<'db, DB: DBAccess> Persist for DBTransaction<'db, DB> {
@@ -1021,6 +1117,26 @@ impl<'db, DB: DBAccess> Mempool for DBTransaction<'db, DB> {
             .iterator_cf(self.mempool_cf, IteratorMode::Start)
             .count()
     }
+
+    fn mempool_gas_histogram(
+        &self,
+        bucket_size: u64,
+    ) -> Result<Vec<(u64, usize)>> {
+        let bucket_size = bucket_size.max(1);
+        let mut histogram: Vec<(u64, usize)> = vec![];
+
+        // Only the keys of the fees index are read, the transactions are not
+        // deserialized
+        for (gas_price, _) in self.mempool_txs_ids_sorted_by_low_fee()? {
+            let bucket = gas_price - gas_price % bucket_size;
+            match histogram.last_mut() {
+                Some((last, count)) if *last == bucket => *count += 1,
+                _ => histogram.push((bucket, 1)),
+            }
+        }
+
+        Ok(histogram)
+    }
 }
 
 pub struct MemPoolIterator<'db, DB: DBAccess, M: Mempool> {
@@ -1140,6 +1256,11 @@ impl<'db, DB: DBAccess> Metadata for DBTransaction<'db, DB> {
     fn op_read(&self, key: &[u8]) -> Result<Option<Vec<u8>>> {
         self.inner.get_cf(self.metadata_cf, key).map_err(Into::into)
     }
+
+    fn op_delete(&mut self, key: &[u8]) -> Result<()> {
+        self.inner.delete_cf(self.metadata_cf, key)?;
+        Ok(())
+    }
 }
 
 impl<'db, DB: DBAccess> DBTransaction<'db, DB> {
@@ -1262,8 +1383,24 @@ impl node_data::Serializable for LightBlock {
     }
 }
 
+fn read_lock(
+    candidates: &RwLock<MemoryCandidates>,
+) -> Result<std::sync::RwLockReadGuard<'_, MemoryCandidates>> {
+    candidates
+        .read()
+        .map_err(|_| anyhow::anyhow!("candidates lock poisoned"))
+}
+
+fn write_lock(
+    candidates: &RwLock<MemoryCandidates>,
+) -> Result<std::sync::RwLockWriteGuard<'_, MemoryCandidates>> {
+    candidates
+        .write()
+        .map_err(|_| anyhow::anyhow!("candidates lock poisoned"))
+}
+
 #[cfg(test)]
-mod tests {
+pub(crate) mod tests {
     use fake::{Fake, Faker};
     use node_data::ledger;
 
@@ -1475,6 +1612,33 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_mempool_gas_histogram() {
+        TestWrapper::new("test_mempool_gas_histogram").run(|path| {
+            let db = Backend::create_or_open(path, DatabaseOptions::default());
+
+            db.update(|db| {
+                assert!(db.mempool_gas_histogram(5)?.is_empty());
+                for gas_price in [1, 2, 3, 4, 5, 6, 9, 10, 42] {
+                    let tx = ledger::faker::gen_dummy_tx(gas_price);
+                    db.store_mempool_tx(&tx, 0)?;
+                }
+                Ok(())
+            })
+            .unwrap();
+
+            db.view(|db| {
+                let histogram = db.mempool_gas_histogram(5).unwrap();
+                assert_eq!(histogram, vec![(0, 4), (5, 3), (10, 1), (40, 1)]);
+
+                // Transactions are bucketed by their exact gas price
+                let histogram = db.mempool_gas_histogram(1).unwrap();
+                assert_eq!(histogram.len(), 9);
+                assert!(histogram.iter().all(|(_, count)| *count == 1));
+            });
+        });
+    }
+
     #[test]
     fn test_txs_count() {
         TestWrapper::new("test_txs_count").run(|path| {
@@ -1657,6 +1821,89 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_tip_header() {
+        TestWrapper::new("test_tip_header").run(|path| {
+            let db = Backend::create_or_open(path, DatabaseOptions::default());
+            let b: Block = Faker.fake();
+
+            db.view(|v| {
+                assert!(v.tip_header().expect("should not fail").is_none());
+            });
+
+            // Store a block and mark it as the tip
+            assert!(db
+                .update(|txn| {
+                    txn.store_block(
+                        b.header(),
+                        &to_spent_txs(b.txs()),
+                        b.faults(),
+                        Label::Accepted(b.header().height),
+                    )?;
+                    txn.op_write(MD_HASH_KEY, b.header().hash)?;
+                    Ok(())
+                })
+                .is_ok());
+
+            db.view(|v| {
+                let tip = v
+                    .tip_header()
+                    .expect("should not return error")
+                    .expect("should find the tip");
+                assert_eq!(tip.hash, b.header().hash);
+                assert_eq!(tip.height, b.header().height);
+                assert_eq!(tip.state_hash, b.header().state_hash);
+                assert_eq!(tip.timestamp, b.header().timestamp);
+            });
+        });
+    }
+
+    #[test]
+    fn test_recent_tip_iterations() {
+        TestWrapper::new("test_recent_tip_iterations").run(|path| {
+            let db = Backend::create_or_open(path, DatabaseOptions::default());
+
+            db.view(|v| {
+                assert!(v.recent_tip_iterations(3).unwrap().is_empty());
+            });
+
+            let iterations = [0u8, 2, 0, 5, 1];
+            assert!(db
+                .update(|txn| {
+                    for (height, iteration) in iterations.iter().enumerate() {
+                        let header = Header {
+                            height: height as u64,
+                            iteration: *iteration,
+                            ..Faker.fake()
+                        };
+                        let b = Block::new(header, vec![], vec![])
+                            .expect("block to be created");
+
+                        txn.store_block(
+                            b.header(),
+                            &to_spent_txs(b.txs()),
+                            b.faults(),
+                            Label::Accepted(height as u64),
+                        )?;
+                    }
+                    Ok(())
+                })
+                .is_ok());
+
+            db.view(|v| {
+                assert_eq!(v.recent_tip_iterations(3).unwrap(), vec![0, 5, 1]);
+                assert_eq!(v.recent_tip_iterations(1).unwrap(), vec![1]);
+                assert!(v.recent_tip_iterations(0).unwrap().is_empty());
+
+                // Asking for more blocks than the chain has
+                assert_eq!(
+                    v.recent_tip_iterations(10).unwrap(),
+                    iterations.to_vec()
+                );
+            });
+        });
+    }
+
     #[test]
     fn test_fetch_block_label_by_height() {
         TestWrapper::new("test_fetch_block_hash_by_height").run(|path| {
@@ -1688,6 +1935,62 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_in_memory_candidates() {
+        TestWrapper::new("test_in_memory_candidates").run(|path| {
+            let opts = DatabaseOptions {
+                candidates_backend: CandidatesBackend::InMemory,
+                ..Default::default()
+            };
+            let db = Backend::create_or_open(path, opts);
+            let b: Block = Faker.fake();
+            let hash = b.header().hash;
+            let height = b.header().height;
+
+            db.update(|txn| txn.store_candidate(b.clone()))
+                .expect("candidate to be stored");
+
+            db.view(|txn| {
+                let fetched = txn
+                    .candidate(&hash)
+                    .expect("candidate to be fetched")
+                    .expect("candidate to exist");
+                assert_eq!(fetched.header().hash, hash);
+                assert_eq!(txn.count_candidates(), 1);
+
+                let ch = ConsensusHeader {
+                    prev_block_hash: b.header().prev_block_hash,
+                    round: height,
+                    iteration: b.header().iteration,
+                };
+                let fetched = txn
+                    .candidate_by_iteration(&ch)
+                    .expect("candidate to be fetched")
+                    .expect("candidate to exist");
+                assert_eq!(fetched.header().hash, hash);
+
+                // Nothing is written to the candidates column families
+                let persisted = txn
+                    .inner
+                    .iterator_cf(txn.candidates_cf, IteratorMode::Start)
+                    .count();
+                assert_eq!(persisted, 0);
+            });
+
+            // Eviction by height still applies
+            db.update(|txn| txn.delete_candidate(|h| h < height))
+                .expect("delete to succeed");
+            db.view(|txn| assert_eq!(txn.count_candidates(), 1));
+
+            db.update(|txn| txn.delete_candidate(|h| h <= height))
+                .expect("delete to succeed");
+            db.view(|txn| {
+                assert_eq!(txn.count_candidates(), 0);
+                assert!(txn.candidate(&hash).unwrap().is_none());
+            });
+        });
+    }
+
     #[test]
     /// Ensures delete_block fn removes all keys of a single block
     fn test_delete_block() {
@@ -1741,10 +2044,10 @@ mod tests {
             .for_each(drop);
     }
 
-    struct TestWrapper(tempdir::TempDir);
+    pub(crate) struct TestWrapper(tempdir::TempDir);
 
     impl TestWrapper {
-        fn new(path: &'static str) -> Self {
+        pub(crate) fn new(path: &'static str) -> Self {
             Self(
                 tempdir::TempDir::new(path)
                     .expect("Temp directory to be created"),
@@ -32,6 +32,10 @@ use tracing::{error, info, warn};
 
 pub use rusk_recovery_tools::state::DUSK_CONSENSUS_KEY;
 
+/// Maximum time given to the services handling SIGINT to shut down, before
+/// being aborted
+const SHUTDOWN_GRACE_PERIOD: Duration = Duration::from_secs(2);
+
 /// Filter is used by Network implementor to filter messages before re-routing
 /// them. It's like the middleware in HTTP pipeline.
 ///
@@ -146,6 +150,13 @@ pub trait LongLivedService<N: Network, DB: database::DB, VM: vm::VMExecution>:
 
     /// Returns service name.
     fn name(&self) -> &'static str;
+
+    /// Returns true if the service returns by itself on SIGINT, once shut
+    /// down gracefully. The node then waits for it, up to a grace period,
+    /// before aborting the other services.
+    fn handles_shutdown(&self) -> bool {
+        false
+    }
 }
 
 #[derive(Debug)]
@@ -214,8 +225,14 @@ impl<N: Network, DB: database::DB, VM: vm::VMExecution> Node<N, DB, VM> {
     ) -> anyhow::Result<()> {
         // Spawn all services and join-wait for their termination.
         let mut set = JoinSet::new();
-        set.spawn(async {
+        let graceful = service_list.iter().any(|s| s.handles_shutdown());
+        set.spawn(async move {
             signal(SignalKind::interrupt())?.recv().await;
+            // Give the services handling the signal the time to shut down
+            // gracefully. All the services are aborted as soon as one returns
+            if graceful {
+                tokio::time::sleep(SHUTDOWN_GRACE_PERIOD).await;
+            }
             // TODO: ResultCode
             Ok(2)
         });
@@ -29,6 +29,8 @@ type FilterList<const N: usize> = [Option<BoxedFilter>; N];
 pub struct Listener<const N: usize> {
     routes: Arc<RwLock<RoutesList<N>>>,
     filters: Arc<RwLock<FilterList<N>>>,
+    /// Maximum size of a message received from the network
+    max_message_size: usize,
 }
 
 impl<const N: usize> Listener<N> {
@@ -58,7 +60,7 @@ impl<const N: usize> Listener<N> {
 impl<const N: usize> kadcast::NetworkListen for Listener<N> {
     fn on_message(&self, blob: Vec<u8>, md: MessageInfo) {
         let msg_size = blob.len();
-        match Message::read(&mut &blob.to_vec()[..]) {
+        match Message::from_wire(&blob, self.max_message_size) {
             Ok(mut msg) => {
                 counter!("dusk_bytes_recv").increment(msg_size as u64);
                 counter!(format!("dusk_inbound_{:?}_size", msg.topic()))
@@ -87,6 +89,7 @@ impl<const N: usize> kadcast::NetworkListen for Listener<N> {
                     height: md.height(),
                     src_addr: md.src(),
                     ray_id,
+                    version: *msg.version(),
                 });
 
                 // Allow upper layers to fast-discard a message before queueing
@@ -120,7 +123,12 @@ pub struct Kadcast<const N: usize> {
 }
 
 impl<const N: usize> Kadcast<N> {
-    pub fn new(mut conf: Config) -> Result<Self, AddrParseError> {
+    /// Creates the network layer, discarding any received message larger
+    /// than `max_message_size` before parsing it.
+    pub fn new(
+        mut conf: Config,
+        max_message_size: usize,
+    ) -> Result<Self, AddrParseError> {
         const INIT: Option<AsyncQueue<Message>> = None;
         let routes = Arc::new(RwLock::new([INIT; N]));
 
@@ -134,6 +142,7 @@ impl<const N: usize> Kadcast<N> {
         let listener = Listener {
             routes: routes.clone(),
             filters: filters.clone(),
+            max_message_size,
         };
         conf.version = format!("{PROTOCOL_VERSION}");
         conf.version_match = format!("{PROTOCOL_VERSION}");
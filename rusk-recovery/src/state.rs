@@ -30,7 +30,7 @@ mod http;
 mod zip;
 
 mod snapshot;
-pub use snapshot::{GenesisStake, PhoenixBalance, Snapshot};
+pub use snapshot::{Checkpoint, GenesisStake, PhoenixBalance, Snapshot};
 
 pub mod tar;
 
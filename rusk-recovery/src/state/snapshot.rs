@@ -14,6 +14,8 @@ use serde_derive::{Deserialize, Serialize};
 
 use crate::state;
 
+mod checkpoint;
+pub use checkpoint::Checkpoint;
 mod stake;
 pub use stake::GenesisStake;
 mod wrapper;
@@ -96,6 +98,14 @@ impl Snapshot {
     pub fn base_state(&self) -> Option<&str> {
         self.base_state.as_deref()
     }
+
+    /// Replaces the stakes of this snapshot with the provisioner set of a
+    /// checkpoint.
+    ///
+    /// The checkpoint is expected to be verified (see [`Checkpoint::load`]).
+    pub fn apply_checkpoint(&mut self, checkpoint: Checkpoint) {
+        self.stake = checkpoint.into_stakes();
+    }
 }
 
 #[cfg(test)]
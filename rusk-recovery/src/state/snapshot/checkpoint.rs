@@ -0,0 +1,153 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+
+use dusk_bytes::Serializable;
+use dusk_core::signatures::bls::{
+    PublicKey as BlsPublicKey, SecretKey as BlsSecretKey,
+    Signature as BlsSignature,
+};
+use serde_derive::{Deserialize, Serialize};
+
+use super::wrapper::Wrapper;
+use super::GenesisStake;
+
+/// A provisioner set (keys and stakes) at a given height, signed by a trusted
+/// key.
+///
+/// It allows to bootstrap the stake state of a node without replaying the
+/// chain from genesis.
+#[derive(Serialize, Deserialize, PartialEq, Eq)]
+pub struct Checkpoint {
+    height: u64,
+    signature: Option<Wrapper<BlsSignature, { BlsSignature::SIZE }>>,
+    #[serde(skip_serializing_if = "Vec::is_empty", default = "Vec::new")]
+    stake: Vec<GenesisStake>,
+}
+
+impl Checkpoint {
+    /// Creates an unsigned checkpoint of the given provisioner set
+    pub fn new(height: u64, stake: Vec<GenesisStake>) -> Self {
+        Self {
+            height,
+            signature: None,
+            stake,
+        }
+    }
+
+    /// Reads a checkpoint file, verifying it has been signed by
+    /// `trusted_key`.
+    pub fn load<P: AsRef<Path>>(
+        path: P,
+        trusted_key: &BlsPublicKey,
+    ) -> Result<Self, Box<dyn Error>> {
+        let checkpoint: Self = toml::from_str(&fs::read_to_string(path)?)?;
+        checkpoint.verify(trusted_key)?;
+        Ok(checkpoint)
+    }
+
+    /// Signs the checkpoint with the given secret key
+    pub fn sign(&mut self, sk: &BlsSecretKey) {
+        let signature = sk.sign(&self.signing_message());
+        self.signature = Some(signature.into());
+    }
+
+    /// Verifies the checkpoint has been signed by `trusted_key`
+    pub fn verify(
+        &self,
+        trusted_key: &BlsPublicKey,
+    ) -> Result<(), Box<dyn Error>> {
+        let signature =
+            self.signature.as_ref().ok_or("Checkpoint is not signed")?;
+        trusted_key
+            .verify(signature, &self.signing_message())
+            .map_err(|e| format!("Invalid checkpoint signature: {e:?}"))?;
+        Ok(())
+    }
+
+    /// Returns the height the provisioner set refers to
+    pub fn height(&self) -> u64 {
+        self.height
+    }
+
+    /// Returns an iterator of the stakes included in this checkpoint
+    pub fn stakes(&self) -> impl Iterator<Item = &GenesisStake> {
+        self.stake.iter()
+    }
+
+    pub(crate) fn into_stakes(self) -> Vec<GenesisStake> {
+        self.stake
+    }
+
+    /// Returns the message covered by the signature of the checkpoint
+    fn signing_message(&self) -> Vec<u8> {
+        let mut msg = self.height.to_le_bytes().to_vec();
+        for stake in self.stakes() {
+            let keys = stake.to_stake_keys();
+            msg.extend(keys.account.to_bytes());
+            msg.extend(keys.owner.to_bytes());
+            msg.extend(stake.amount.to_le_bytes());
+            msg.extend(stake.eligibility.unwrap_or_default().to_le_bytes());
+            msg.extend(stake.reward.unwrap_or_default().to_le_bytes());
+        }
+        msg
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    use super::*;
+
+    const CHECKPOINT: &str = "height = 1000
+
+[[stake]]
+address = 'pFPEcfxidLvwmFKRQoifrSyWmmVY9UDEThoRFvGXddXRLZ8hB7xfWDYQYwHhTvZvXeL1p5Ygcnsuuxm1X8nHFJH6tEgK3cS76squcFVFSejaKJGMorYZdTup5uscNq6eDU2'
+amount = 1_000_000_000_000
+";
+
+    #[test]
+    fn checkpoint_signature() -> Result<(), Box<dyn Error>> {
+        let mut rng = StdRng::seed_from_u64(0xc0ffee);
+        let sk = BlsSecretKey::random(&mut rng);
+        let trusted_key = BlsPublicKey::from(&sk);
+
+        // An unsigned checkpoint is rejected
+        let mut checkpoint: Checkpoint = toml::from_str(CHECKPOINT)?;
+        assert!(checkpoint.verify(&trusted_key).is_err());
+
+        checkpoint.sign(&sk);
+        checkpoint.verify(&trusted_key)?;
+
+        // Round-trip through a checkpoint file
+        let dir = tempfile::tempdir()?;
+        let path = dir.path().join("checkpoint.toml");
+        fs::write(&path, toml::to_string(&checkpoint)?)?;
+        let loaded = Checkpoint::load(&path, &trusted_key)?;
+        assert_eq!(loaded.height(), 1000);
+        assert_eq!(loaded.stakes().count(), 1);
+
+        // A tampered signature is rejected
+        let other_sk = BlsSecretKey::random(&mut rng);
+        let mut tampered: Checkpoint =
+            toml::from_str(&fs::read_to_string(&path)?)?;
+        tampered.sign(&other_sk);
+        fs::write(&path, toml::to_string(&tampered)?)?;
+        assert!(Checkpoint::load(&path, &trusted_key).is_err());
+
+        // Tampered content is rejected
+        let mut tampered = loaded;
+        tampered.height += 1;
+        assert!(tampered.verify(&trusted_key).is_err());
+
+        Ok(())
+    }
+}
@@ -8,13 +8,17 @@ use dusk_bytes::Serializable;
 use dusk_core::signatures::bls::PublicKey as BlsPublicKey;
 use dusk_core::stake::StakeKeys;
 use dusk_core::Dusk;
+use serde::{de, Deserialize as _, Deserializer};
 use serde_derive::{Deserialize, Serialize};
 
 use super::wrapper::Wrapper;
 
+type ProvisionerKey = Wrapper<BlsPublicKey, { BlsPublicKey::SIZE }>;
+
 #[derive(Serialize, Deserialize, PartialEq, Eq)]
 pub struct GenesisStake {
-    address: Wrapper<BlsPublicKey, { BlsPublicKey::SIZE }>,
+    #[serde(deserialize_with = "deserialize_provisioner_key")]
+    address: ProvisionerKey,
     owner: Option<Wrapper<BlsPublicKey, { BlsPublicKey::SIZE }>>,
     pub amount: Dusk,
     pub eligibility: Option<u64>,
@@ -31,3 +35,50 @@ impl GenesisStake {
         StakeKeys::new(*self.address(), *owner)
     }
 }
+
+/// Deserializes a provisioner key, rejecting the keys that are structurally
+/// valid but unfit for consensus, such as the identity or points outside the
+/// G2 subgroup.
+fn deserialize_provisioner_key<'de, D>(
+    deserializer: D,
+) -> Result<ProvisionerKey, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let key = ProvisionerKey::deserialize(deserializer)?;
+    if !key.is_valid() {
+        let key = bs58::encode(key.to_bytes()).into_string();
+        let err = format!(
+            "Invalid provisioner key {key}: identity or not in the G2 subgroup"
+        );
+        return Err(de::Error::custom(err));
+    }
+    Ok(key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stake_toml(address: &str) -> String {
+        format!("address = '{address}'\namount = 1_000_000_000_000")
+    }
+
+    #[test]
+    fn provisioner_key_validity() {
+        let valid = "pFPEcfxidLvwmFKRQoifrSyWmmVY9UDEThoRFvGXddXRLZ8hB7xfWDYQYwHhTvZvXeL1p5Ygcnsuuxm1X8nHFJH6tEgK3cS76squcFVFSejaKJGMorYZdTup5uscNq6eDU2";
+        let stake: GenesisStake = toml::from_str(&stake_toml(valid))
+            .expect("valid key should be accepted");
+        assert!(stake.address().is_valid());
+
+        // Compressed encoding of the identity element
+        let mut identity = [0u8; BlsPublicKey::SIZE];
+        identity[0] = 0xc0;
+        let identity = bs58::encode(identity).into_string();
+
+        let err = toml::from_str::<GenesisStake>(&stake_toml(&identity))
+            .err()
+            .expect("identity key should be rejected");
+        assert!(err.to_string().contains("Invalid provisioner key"));
+    }
+}
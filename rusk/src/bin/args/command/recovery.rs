@@ -44,6 +44,20 @@ pub enum RecoveryCommand {
         /// of save the state in the profile path.
         #[clap(short, long, value_parser, num_args(1))]
         output: Option<std::path::PathBuf>,
+
+        /// Replaces the stakes of the init config with the provisioner set of
+        /// this checkpoint file.
+        #[clap(
+            long,
+            value_parser,
+            requires = "checkpoint_key",
+            env = "RUSK_RECOVERY_CHECKPOINT"
+        )]
+        checkpoint: Option<std::path::PathBuf>,
+
+        /// Base58 BLS public key the checkpoint must be signed with.
+        #[clap(long, requires = "checkpoint", env = "RUSK_CHECKPOINT_KEY")]
+        checkpoint_key: Option<String>,
     },
 }
 
@@ -72,7 +86,14 @@ impl RecoveryCommand {
                 force,
                 init,
                 output,
-            } => crate::args::state::recovery_state(init, force, output),
+                checkpoint,
+                checkpoint_key,
+            } => {
+                let checkpoint = checkpoint.zip(checkpoint_key);
+                crate::args::state::recovery_state(
+                    init, force, output, checkpoint,
+                )
+            }
             #[cfg(feature = "recovery-keys")]
             Self::Keys { keep, crs_url } => {
                 rusk_recovery_tools::keys::exec(keep, crs_url)
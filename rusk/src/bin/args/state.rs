@@ -8,23 +8,48 @@ use super::*;
 
 use std::{env, fs, io};
 
-use rusk_recovery_tools::state::{deploy, restore_state, tar};
+use dusk_bytes::DeserializableSlice;
+use dusk_core::signatures::bls::PublicKey as BlsPublicKey;
+use rusk_recovery_tools::state::{
+    deploy, restore_state, tar, Checkpoint, Snapshot,
+};
 use rusk_recovery_tools::Theme;
 use tracing::info;
 
+/// Creates the network state from the `init` config, or the default one.
+///
+/// If a `checkpoint` file is given along with the base58 key it must be signed
+/// with, its provisioner set replaces the stakes of the config.
 pub fn recovery_state(
     init: Option<PathBuf>,
     force: bool,
     output_file: Option<PathBuf>,
+    checkpoint: Option<(PathBuf, String)>,
 ) -> Result<(), Box<dyn std::error::Error>> {
     let config = match &init {
         Some(path) => fs::read_to_string(path)
             .map_err(|_| format!("file {path:?} not found"))?,
         None => rusk_recovery_tools::state::DEFAULT_SNAPSHOT.into(),
     };
-    let init = toml::from_str(&config)?;
+    let mut init: Snapshot = toml::from_str(&config)?;
 
     let theme = Theme::default();
+
+    if let Some((path, trusted_key)) = checkpoint {
+        let trusted_key = bs58::decode(&trusted_key)
+            .into_vec()
+            .map_err(|e| format!("Invalid checkpoint key: {e}"))?;
+        let trusted_key = BlsPublicKey::from_slice(&trusted_key)
+            .map_err(|e| format!("Invalid checkpoint key: {e:?}"))?;
+
+        let checkpoint = Checkpoint::load(&path, &trusted_key)?;
+        info!(
+            "{} checkpoint at height {}",
+            theme.action("Applying"),
+            checkpoint.height()
+        );
+        init.apply_checkpoint(checkpoint);
+    }
     info!("{} Network state", theme.action("Checking"));
 
     let _tmpdir = match output_file.clone() {
@@ -9,6 +9,7 @@ use std::{
     time::{Duration, SystemTime, UNIX_EPOCH},
 };
 
+use node::chain::conf::Params as ChainParams;
 use node::database::DatabaseOptions;
 use serde::{Deserialize, Serialize};
 
@@ -39,6 +40,9 @@ pub(crate) struct ChainConfig {
     #[serde(with = "humantime_serde")]
     #[serde(default)]
     genesis_timestamp: Option<SystemTime>,
+
+    #[serde(flatten)]
+    params: ChainParams,
 }
 
 impl ChainConfig {
@@ -110,6 +114,10 @@ impl ChainConfig {
         self.block_gas_limit.unwrap_or(DEFAULT_BLOCK_GAS_LIMIT)
     }
 
+    pub(crate) fn params(&self) -> ChainParams {
+        self.params
+    }
+
     pub(crate) fn genesis_timestamp(&self) -> u64 {
         self.genesis_timestamp
             .map(|t| {
@@ -24,6 +24,13 @@ pub struct HttpConfig {
         serialize_with = "serialize_feeder_call_gas"
     )]
     pub feeder_call_gas: u64,
+    /// Minimum gas limit of the transactions accepted for propagation
+    #[serde(default)]
+    pub min_propagate_gas_limit: u64,
+    /// Maximum number of feeder contract queries executed concurrently.
+    /// Unlimited if not set
+    #[serde(default)]
+    pub max_concurrent_queries: Option<usize>,
     listen_address: Option<String>,
     #[serde(default = "default_ws_sub_channel_cap")]
     pub ws_sub_channel_cap: usize,
@@ -71,6 +78,8 @@ impl Default for HttpConfig {
             headers: default_http_headers(),
             listen: default_listen(),
             feeder_call_gas: default_feeder_call_gas(),
+            min_propagate_gas_limit: 0,
+            max_concurrent_queries: None,
             listen_address: None,
             ws_sub_channel_cap: default_ws_sub_channel_cap(),
             ws_event_channel_cap: default_ws_event_channel_cap(),
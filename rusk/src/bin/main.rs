@@ -70,6 +70,8 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
         node_builder = node_builder
             .with_feeder_call_gas(config.http.feeder_call_gas)
+            .with_min_propagate_gas_limit(config.http.min_propagate_gas_limit)
+            .with_max_concurrent_queries(config.http.max_concurrent_queries)
             .with_db_path(db_path)
             .with_db_options(config.chain.db_options())
             .with_kadcast(config.kadcast)
@@ -78,6 +80,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             .with_telemetry(config.telemetry.listen_addr())
             .with_chain_queue_size(config.chain.max_queue_size())
             .with_genesis_timestamp(config.chain.genesis_timestamp())
+            .with_chain_params(config.chain.params())
             .with_mempool(config.mempool.into())
             .with_state_dir(state_dir)
             .with_generation_timeout(config.chain.generation_timeout())
@@ -8,6 +8,7 @@ use std::path::PathBuf;
 use std::time::Duration;
 
 use kadcast::config::Config as KadcastConfig;
+use node::chain::conf::Params as ChainParams;
 use node::chain::ChainSrv;
 use node::database::rocksdb;
 use node::database::{DatabaseOptions, DB};
@@ -39,6 +40,7 @@ pub struct RuskNodeBuilder {
     db_options: DatabaseOptions,
     max_chain_queue_size: usize,
     genesis_timestamp: u64,
+    chain_params: ChainParams,
 
     generation_timeout: Option<Duration>,
     gas_per_deploy_byte: Option<u64>,
@@ -47,6 +49,8 @@ pub struct RuskNodeBuilder {
     min_deploy_points: Option<u64>,
     block_gas_limit: u64,
     feeder_call_gas: u64,
+    min_propagate_gas_limit: u64,
+    max_concurrent_queries: Option<usize>,
     state_dir: PathBuf,
 
     http: Option<HttpServerConfig>,
@@ -115,6 +119,11 @@ impl RuskNodeBuilder {
         self
     }
 
+    pub fn with_chain_params(mut self, chain_params: ChainParams) -> Self {
+        self.chain_params = chain_params;
+        self
+    }
+
     pub fn with_generation_timeout(
         mut self,
         generation_timeout: Option<Duration>,
@@ -162,6 +171,19 @@ impl RuskNodeBuilder {
         self
     }
 
+    pub fn with_min_propagate_gas_limit(mut self, gas_limit: u64) -> Self {
+        self.min_propagate_gas_limit = gas_limit;
+        self
+    }
+
+    pub fn with_max_concurrent_queries(
+        mut self,
+        max_concurrent_queries: Option<usize>,
+    ) -> Self {
+        self.max_concurrent_queries = max_concurrent_queries;
+        self
+    }
+
     pub fn with_state_dir(mut self, state_dir: PathBuf) -> Self {
         self.state_dir = state_dir;
         self
@@ -200,7 +222,7 @@ impl RuskNodeBuilder {
         let min_deploy_points =
             self.min_deploy_points.unwrap_or(DEFAULT_MIN_DEPLOY_POINTS);
 
-        let rusk = Rusk::new(
+        let mut rusk = Rusk::new(
             self.state_dir,
             self.kadcast.kadcast_id.unwrap_or_default(),
             self.generation_timeout,
@@ -215,6 +237,9 @@ impl RuskNodeBuilder {
             archive_sender.clone(),
         )
         .map_err(|e| anyhow::anyhow!("Cannot instantiate VM {e}"))?;
+        if let Some(max_concurrent) = self.max_concurrent_queries {
+            rusk = rusk.with_max_concurrent_queries(max_concurrent);
+        }
         info!("Rusk VM loaded");
 
         #[cfg(feature = "archive")]
@@ -225,12 +250,16 @@ impl RuskNodeBuilder {
                 self.db_path.clone(),
                 self.db_options.clone(),
             );
-            let net = Kadcast::new(self.kadcast)?;
+            let net = Kadcast::new(
+                self.kadcast,
+                self.chain_params.max_wire_message_size,
+            )?;
             RuskNode::new(
                 Node::new(net, db, rusk.clone()),
                 #[cfg(feature = "archive")]
                 archive.clone(),
             )
+            .with_min_propagate_gas_limit(self.min_propagate_gas_limit)
         };
 
         let mut chain_srv = ChainSrv::new(
@@ -238,6 +267,7 @@ impl RuskNodeBuilder {
             self.max_chain_queue_size,
             node_sender.clone(),
             self.genesis_timestamp,
+            self.chain_params,
         );
         if self.command_revert {
             chain_srv
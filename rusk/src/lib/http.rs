@@ -299,7 +299,8 @@ async fn handle_stream<H: HandleRequest>(
                         EventResponse {
                             data: e.into(),
                             headers: rsp.headers.clone(),
-                            error: None
+                            error: None,
+                            error_status: None,
                         }
                     });//.await;
                     while let Some(c) = datas.next().await {
@@ -884,10 +885,14 @@ async fn handle_execution<H>(
             EventResponse {
                 data,
                 error: None,
+                error_status: None,
                 headers,
             }
         })
-        .unwrap_or_else(|e| request.to_error(e.to_string()));
+        .unwrap_or_else(|e| EventResponse {
+            error_status: error_status(&e),
+            ..request.to_error(e.to_string())
+        });
 
     rsp.set_header(RUSK_VERSION_HEADER, serde_json::json!(*VERSION));
     let _ = responder.send(rsp);
@@ -909,6 +914,7 @@ async fn handle_execution_rues<H>(
             EventResponse {
                 data,
                 error: None,
+                error_status: None,
                 headers,
             }
         })
@@ -916,12 +922,36 @@ async fn handle_execution_rues<H>(
             headers: event.x_headers(),
             data: DataType::None,
             error: Some(e.to_string()),
+            error_status: error_status(&e),
         });
 
     rsp.set_header(RUSK_VERSION_HEADER, serde_json::json!(*VERSION));
     let _ = responder.send(rsp);
 }
 
+/// The HTTP status reporting the given request handling error, if it differs
+/// from an internal server error
+fn error_status(error: &AnyhowError) -> Option<StatusCode> {
+    error
+        .is::<ResourceExhausted>()
+        .then_some(StatusCode::TOO_MANY_REQUESTS)
+}
+
+/// Error rejecting a request the node has no capacity left to serve.
+///
+/// It is reported with the `429 Too Many Requests` HTTP status, so that
+/// clients can tell it apart from a failure of the request and retry later.
+#[derive(Debug)]
+pub struct ResourceExhausted(pub &'static str);
+
+impl std::fmt::Display for ResourceExhausted {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Resource exhausted: {}", self.0)
+    }
+}
+
+impl std::error::Error for ResourceExhausted {}
+
 #[async_trait]
 pub trait HandleRequest: Send + Sync + 'static {
     fn can_handle(&self, request: &MessageRequest) -> bool;
@@ -991,6 +1021,9 @@ mod tests {
                     });
                     ResponseData::new(rec)
                 }
+                (_, _, "exhausted") => {
+                    return Err(ResourceExhausted("test").into())
+                }
                 _ => ResponseData::new(request.event_data().to_vec()),
             };
             Ok(response)
@@ -1047,6 +1080,52 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn http_resource_exhausted() {
+        let cert_and_key: Option<(String, String)> = None;
+
+        let (_, event_receiver) = broadcast::channel(16);
+        let ws_event_channel_cap = 2;
+
+        let server = HttpServer::bind(
+            TestHandle,
+            event_receiver,
+            ws_event_channel_cap,
+            "localhost:0",
+            HeaderMap::new(),
+            cert_and_key,
+        )
+        .await
+        .expect("Binding the server to the address should succeed");
+
+        let request = |topic: &str| EventRequest {
+            target: Target::None,
+            data: RequestData::Text("data".into()),
+            topic: topic.into(),
+        };
+        let client = reqwest::Client::new();
+        let send = |event: EventRequest| {
+            let request = serde_json::to_vec(&event)
+                .expect("Serializing request should succeed");
+            client
+                .post(format!("http://{}/01/target", server.local_addr))
+                .body(request)
+                .send()
+        };
+
+        let response = send(request("exhausted"))
+            .await
+            .expect("Requesting should succeed");
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+        let body = response.text().await.expect("There should be a body");
+        assert!(body.starts_with("Resource exhausted"));
+
+        let response = send(request("topic"))
+            .await
+            .expect("Requesting should succeed");
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
     #[tokio::test]
     async fn https_query() {
         let cert_path = "tests/assets/cert.pem";
@@ -13,7 +13,7 @@ use std::sync::Arc;
 
 use dusk_core::transfer::Transaction as ProtocolTransaction;
 use node::database::rocksdb::{Backend, DBTransaction};
-use node::database::{Mempool, DB};
+use node::database::{Ledger, Mempool, Persist, DB};
 use node::mempool::MempoolSrv;
 use node::network::Kadcast;
 use node::Network;
@@ -29,11 +29,43 @@ use serde_json::{json, Map, Value};
 use tracing::error;
 
 use super::*;
-use crate::node::RuskNode;
+use crate::node::{DiscardReason, RuskNode, TxStatus};
 use crate::{VERSION, VERSION_BUILD};
 
 const GQL_VAR_PREFIX: &str = "rusk-gqlvar-";
 
+/// Decodes a transaction to be propagated, rejecting it early if its gas
+/// limit is lower than `min_gas_limit`.
+fn decode_tx(bytes: &[u8], min_gas_limit: u64) -> anyhow::Result<Transaction> {
+    let tx = ProtocolTransaction::from_slice(bytes)
+        .map_err(|e| anyhow::anyhow!("Invalid Data {e:?}"))?;
+
+    let gas_limit = tx.gas_limit();
+    if gas_limit < min_gas_limit {
+        anyhow::bail!(
+            "Gas limit too low: {gas_limit}, minimum is {min_gas_limit}"
+        );
+    }
+
+    Ok(tx.into())
+}
+
+/// Resolves the status of a transaction, giving precedence to the ledger over
+/// the mempool, and to the mempool over the `discarded` reason.
+fn resolve_tx_status<T: Ledger + Mempool>(
+    t: &T,
+    tx_id: &[u8; 32],
+    discarded: Option<DiscardReason>,
+) -> anyhow::Result<TxStatus> {
+    if let Some(spent) = t.ledger_tx(tx_id)? {
+        return Ok(TxStatus::Included(spent.block_height));
+    }
+    if t.mempool_tx_exists(*tx_id)? {
+        return Ok(TxStatus::Pending);
+    }
+    Ok(discarded.map_or(TxStatus::NotFound, TxStatus::Discarded))
+}
+
 fn variables_from_headers(headers: &Map<String, Value>) -> Variables {
     let mut var = Variables::default();
     headers
@@ -67,10 +99,13 @@ impl HandleRequest for RuskNode {
             ("graphql", _, "query") => true,
             ("transactions", _, "preverify") => true,
             ("transactions", _, "propagate") => true,
+            ("transactions", _, "validate") => true,
+            ("transactions", _, "status") => true,
             ("network", _, "peers") => true,
             ("network", _, "peers_location") => true,
             ("node", _, "info") => true,
             ("blocks", _, "gas-price") => true,
+            ("blocks", _, "tip") => true,
             _ => false,
         }
     }
@@ -88,6 +123,12 @@ impl HandleRequest for RuskNode {
             ("transactions", _, "propagate") => {
                 self.propagate_tx(request.data.as_bytes()).await
             }
+            ("transactions", _, "validate") => {
+                self.validate_tx(request.data.as_bytes()).await
+            }
+            ("transactions", _, "status") => {
+                self.tx_status(request.data.as_string().trim()).await
+            }
             ("network", _, "peers") => {
                 let amount = request.data.as_string().trim().parse()?;
                 self.alive_nodes(amount).await
@@ -104,6 +145,7 @@ impl HandleRequest for RuskNode {
                     .unwrap_or(usize::MAX);
                 self.get_gas_price(max_transactions).await
             }
+            ("blocks", _, "tip") => self.get_tip().await,
             _ => anyhow::bail!("Unsupported"),
         }
     }
@@ -136,6 +178,7 @@ impl HandleRequest for RuskNode {
                     .unwrap_or(usize::MAX);
                 self.get_gas_price(max_transactions).await
             }
+            ("blocks", _, "tip") => self.get_tip().await,
             _ => anyhow::bail!("Unsupported"),
         }
     }
@@ -196,9 +239,7 @@ impl RuskNode {
     }
 
     async fn propagate_tx(&self, tx: &[u8]) -> anyhow::Result<ResponseData> {
-        let tx: Transaction = ProtocolTransaction::from_slice(tx)
-            .map_err(|e| anyhow::anyhow!("Invalid Data {e:?}"))?
-            .into();
+        let tx = decode_tx(tx, self.min_propagate_gas_limit())?;
         let tx_message = tx.into();
 
         let network = self.network();
@@ -207,6 +248,41 @@ impl RuskNode {
         Ok(ResponseData::new(DataType::None))
     }
 
+    /// Checks whether a transaction would currently be valid, without
+    /// executing it nor adding it to the mempool.
+    ///
+    /// Returns `{ valid, reason }`, rather than an error, for a well-formed
+    /// transaction failing the checks.
+    async fn validate_tx(&self, data: &[u8]) -> anyhow::Result<ResponseData> {
+        let tx = ProtocolTransaction::from_slice(data)
+            .map_err(|e| anyhow::anyhow!("Invalid Data {e:?}"))?;
+        let vm = self.inner().vm_handler();
+        let validity = vm.read().await.validate_tx(&tx.into());
+
+        Ok(ResponseData::new(serde_json::to_value(validity)?))
+    }
+
+    /// Returns the status of the transaction with the given hex-encoded id.
+    ///
+    /// A transaction is reported as `Included` if it's found in the ledger,
+    /// `Pending` if it's found in the mempool and `Discarded` if it has been
+    /// discarded during block generation.
+    async fn tx_status(&self, tx_id: &str) -> anyhow::Result<ResponseData> {
+        let tx_id: [u8; 32] = hex::decode(tx_id)?
+            .try_into()
+            .map_err(|_| anyhow::anyhow!("Invalid transaction id"))?;
+
+        let vm = self.inner().vm_handler();
+        let discarded = vm.read().await.discard_reason(&tx_id);
+        let status = self
+            .db()
+            .read()
+            .await
+            .view(|t| resolve_tx_status(t, &tx_id, discarded))?;
+
+        Ok(ResponseData::new(serde_json::to_value(status)?))
+    }
+
     async fn alive_nodes(&self, amount: usize) -> anyhow::Result<ResponseData> {
         let nodes = self.network().read().await.alive_nodes(amount).await;
         let nodes: Vec<_> = nodes.iter().map(|n| n.to_string()).collect();
@@ -226,6 +302,18 @@ impl RuskNode {
         Ok(ResponseData::new(serde_json::to_value(&info)?))
     }
 
+    /// Returns the header of the current chain tip.
+    async fn get_tip(&self) -> anyhow::Result<ResponseData> {
+        let header = self
+            .db()
+            .read()
+            .await
+            .view(|t| t.tip_header())?
+            .ok_or_else(|| anyhow::anyhow!("Cannot find the tip"))?;
+
+        Ok(ResponseData::new(serde_json::to_value(header)?))
+    }
+
     /// Calculates various statistics for gas prices of transactions in the
     /// mempool.
     ///
@@ -290,3 +378,89 @@ impl RuskNode {
         Ok(ResponseData::new(serde_json::to_value(stats)?))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use dusk_core::signatures::bls::SecretKey as AccountSecretKey;
+    use dusk_core::transfer::data::TransactionData;
+    use node_data::ledger::{Header, Label, SpentTransaction};
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    use super::*;
+
+    fn moonlight_tx_bytes(gas_limit: u64) -> Vec<u8> {
+        let mut rng = StdRng::seed_from_u64(0xbeef);
+        let sk = AccountSecretKey::random(&mut rng);
+        ProtocolTransaction::moonlight(
+            &sk,
+            None,
+            0,
+            0,
+            gas_limit,
+            1,
+            1,
+            0xFA,
+            None::<TransactionData>,
+        )
+        .expect("transaction should be created")
+        .to_var_bytes()
+    }
+
+    #[test]
+    fn test_resolve_tx_status() {
+        let tmp = tempfile::tempdir().expect("temp dir to be created");
+        let db = Backend::create_or_open(tmp.path(), Default::default());
+
+        let included: Transaction =
+            ProtocolTransaction::from_slice(&moonlight_tx_bytes(1_000))
+                .expect("valid transaction")
+                .into();
+        let pending: Transaction =
+            ProtocolTransaction::from_slice(&moonlight_tx_bytes(2_000))
+                .expect("valid transaction")
+                .into();
+        let spent = SpentTransaction {
+            inner: included.clone(),
+            block_height: 7,
+            gas_spent: 1_000,
+            err: None,
+        };
+
+        db.update(|t| {
+            t.store_block(&Header::default(), &[spent], &[], Label::Final(7))?;
+            t.store_mempool_tx(&pending, 0)
+        })
+        .expect("db to be updated");
+
+        let status = |tx_id, discarded| {
+            db.view(|t| resolve_tx_status(t, &tx_id, discarded))
+                .expect("status to be resolved")
+        };
+        let reason = || Some(DiscardReason::InsufficientGas);
+
+        assert_eq!(status(included.id(), None), TxStatus::Included(7));
+        assert_eq!(status(pending.id(), None), TxStatus::Pending);
+        assert_eq!(status([1; 32], None), TxStatus::NotFound);
+        assert_eq!(
+            status([1; 32], reason()),
+            TxStatus::Discarded(DiscardReason::InsufficientGas)
+        );
+
+        // A transaction included after being discarded is reported as such
+        assert_eq!(status(included.id(), reason()), TxStatus::Included(7));
+    }
+
+    #[test]
+    fn test_decode_tx_min_gas_limit() {
+        let tx = moonlight_tx_bytes(1_000);
+
+        // No minimum, the transaction is accepted
+        assert!(decode_tx(&tx, 0).is_ok());
+        assert!(decode_tx(&tx, 1_000).is_ok());
+
+        // A gas limit below the minimum is rejected at decode time
+        let err = decode_tx(&tx, 1_001).expect_err("tx should be rejected");
+        assert!(err.to_string().contains("Gas limit too low"));
+    }
+}
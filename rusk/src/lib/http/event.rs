@@ -16,7 +16,7 @@ use hyper::body::{Buf, Frame};
 use hyper::header::{InvalidHeaderName, InvalidHeaderValue};
 use hyper::{
     body::{Body, Bytes, Incoming},
-    Request, Response,
+    Request, Response, StatusCode,
 };
 use pin_project::pin_project;
 use rand::distributions::{Distribution, Standard};
@@ -66,6 +66,7 @@ impl MessageRequest {
             headers: self.x_headers(),
             data: DataType::None,
             error: Some(err.as_ref().to_string()),
+            error_status: None,
         }
     }
 
@@ -192,6 +193,10 @@ pub struct MessageResponse {
 
     /// A possible error happening during the contract call.
     pub error: Option<String>,
+
+    /// The HTTP status reporting the error. Internal server error if not set.
+    #[serde(skip)]
+    pub error_status: Option<StatusCode>,
 }
 
 impl MessageResponse {
@@ -200,6 +205,7 @@ impl MessageResponse {
             headers: serde_json::Map::default(),
             data: DataType::None,
             error: Some(error),
+            error_status: None,
         }
     }
 
@@ -208,8 +214,11 @@ impl MessageResponse {
         is_binary: bool,
     ) -> anyhow::Result<Response<FullOrStreamBody>> {
         if let Some(error) = &self.error {
+            let status = self
+                .error_status
+                .unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
             return Ok(hyper::Response::builder()
-                .status(hyper::StatusCode::INTERNAL_SERVER_ERROR)
+                .status(status)
                 .body(Full::new(error.to_string().into()).into())?);
         }
 
@@ -110,12 +110,16 @@ impl Rusk {
         let fn_name = topic.to_string();
         let data = data.to_vec();
         if feeder {
+            // Feeder queries stream out whole portions of the state, so they
+            // are the only ones subject to the concurrency limit
+            let permit = self.query_limiter.try_acquire()?;
             let (sender, receiver) = mpsc::channel();
 
             let rusk = self.clone();
 
             thread::spawn(move || {
                 rusk.feeder_query_raw(contract_id, fn_name, data, sender);
+                drop(permit);
             });
             Ok(ResponseData::new(receiver))
         } else {
@@ -4,7 +4,9 @@
 //
 // Copyright (c) DUSK NETWORK. All rights reserved.
 
+mod discarded;
 mod events;
+mod limiter;
 mod rusk;
 mod vm;
 
@@ -22,7 +24,10 @@ use parking_lot::RwLock;
 use tokio::sync::broadcast;
 
 use crate::http::RuesEvent;
+pub use discarded::{DiscardReason, TxStatus, TxValidity};
+pub(crate) use discarded::{DiscardedTxs, NonceOrdering};
 pub(crate) use events::ChainEventStreamer;
+pub(crate) use limiter::QueryLimiter;
 #[cfg(feature = "archive")]
 use {
     node::archive::Archive, node_data::archive::ArchivalData, tokio::sync::mpsc,
@@ -48,6 +53,8 @@ pub struct Rusk {
     pub(crate) feeder_gas_limit: u64,
     pub(crate) block_gas_limit: u64,
     pub(crate) event_sender: broadcast::Sender<RuesEvent>,
+    pub(crate) discarded_txs: Arc<RwLock<DiscardedTxs>>,
+    pub(crate) query_limiter: QueryLimiter,
     #[cfg(feature = "archive")]
     pub(crate) archive_sender: mpsc::Sender<ArchivalData>,
 }
@@ -60,6 +67,7 @@ pub struct RuskNode {
     inner: node::Node<Kadcast<255>, Backend, Rusk>,
     #[cfg(feature = "archive")]
     archive: Archive,
+    min_propagate_gas_limit: u64,
 }
 
 impl RuskNode {
@@ -71,9 +79,16 @@ impl RuskNode {
             inner,
             #[cfg(feature = "archive")]
             archive,
+            min_propagate_gas_limit: 0,
         }
     }
 
+    /// Sets the minimum gas limit a transaction must have to be propagated.
+    pub fn with_min_propagate_gas_limit(mut self, gas_limit: u64) -> Self {
+        self.min_propagate_gas_limit = gas_limit;
+        self
+    }
+
     #[cfg(feature = "archive")]
     pub fn with_archive(mut self, archive: Archive) -> Self {
         self.archive = archive;
@@ -98,6 +113,10 @@ impl RuskNode {
     pub fn inner(&self) -> &node::Node<Kadcast<255>, Backend, Rusk> {
         &self.inner
     }
+
+    pub fn min_propagate_gas_limit(&self) -> u64 {
+        self.min_propagate_gas_limit
+    }
 }
 
 /// Calculates the value that the coinbase notes should contain.
@@ -0,0 +1,178 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use std::collections::{HashMap, VecDeque};
+
+use dusk_bytes::Serializable;
+use dusk_core::signatures::bls::PublicKey as AccountPublicKey;
+use dusk_core::transfer::Transaction as ProtocolTransaction;
+use dusk_vm::Error as VMError;
+use serde::Serialize;
+
+/// Maximum number of discarded transactions whose reason is remembered.
+const MAX_DISCARDED_TXS: usize = 10_000;
+
+/// Reason why a transaction has been discarded during block generation.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub enum DiscardReason {
+    /// The gas limit was not enough to spend the transaction
+    InsufficientGas,
+    /// The transaction could not be spent for any other reason
+    Unspendable(String),
+    /// The transaction comes after a transaction of the same sender with a
+    /// higher or equal nonce
+    OutOfOrder,
+}
+
+impl From<&VMError> for DiscardReason {
+    fn from(err: &VMError) -> Self {
+        match err {
+            VMError::OutOfGas => DiscardReason::InsufficientGas,
+            e => DiscardReason::Unspendable(format!("{e}")),
+        }
+    }
+}
+
+/// Status of a transaction as known by the node.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub enum TxStatus {
+    /// The transaction is waiting in the mempool
+    Pending,
+    /// The transaction has been included in the block at the given height
+    Included(u64),
+    /// The transaction has been discarded
+    Discarded(DiscardReason),
+    /// The transaction is unknown to the node
+    NotFound,
+}
+
+/// Outcome of the pre-execution validity checks of a transaction against the
+/// current state.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TxValidity {
+    pub valid: bool,
+    /// Why the transaction is invalid
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+/// Bounded registry of the transactions discarded during block generation.
+///
+/// When the registry is full, the oldest entry is evicted.
+#[derive(Default)]
+pub(crate) struct DiscardedTxs {
+    reasons: HashMap<[u8; 32], DiscardReason>,
+    order: VecDeque<[u8; 32]>,
+}
+
+impl DiscardedTxs {
+    pub fn insert(&mut self, tx_id: [u8; 32], reason: DiscardReason) {
+        if self.reasons.insert(tx_id, reason).is_some() {
+            return;
+        }
+
+        self.order.push_back(tx_id);
+        if self.order.len() > MAX_DISCARDED_TXS {
+            if let Some(oldest) = self.order.pop_front() {
+                self.reasons.remove(&oldest);
+            }
+        }
+    }
+
+    pub fn get(&self, tx_id: &[u8; 32]) -> Option<&DiscardReason> {
+        self.reasons.get(tx_id)
+    }
+}
+
+/// Tracks the nonces of the account transactions included in a block, to
+/// detect the transactions of the same sender coming in the wrong order.
+#[derive(Default)]
+pub(crate) struct NonceOrdering {
+    last_nonces: HashMap<[u8; AccountPublicKey::SIZE], u64>,
+}
+
+impl NonceOrdering {
+    /// Returns false if a transaction of the same sender with a higher or
+    /// equal nonce has already been included.
+    pub fn in_order(&self, tx: &ProtocolTransaction) -> bool {
+        match Self::sender_nonce(tx) {
+            Some((sender, nonce)) => self
+                .last_nonces
+                .get(&sender)
+                .map_or(true, |last| nonce > *last),
+            None => true,
+        }
+    }
+
+    /// Records a transaction included in the block
+    pub fn include(&mut self, tx: &ProtocolTransaction) {
+        if let Some((sender, nonce)) = Self::sender_nonce(tx) {
+            self.last_nonces.insert(sender, nonce);
+        }
+    }
+
+    fn sender_nonce(
+        tx: &ProtocolTransaction,
+    ) -> Option<([u8; AccountPublicKey::SIZE], u64)> {
+        match tx {
+            ProtocolTransaction::Phoenix(_) => None,
+            ProtocolTransaction::Moonlight(tx) => {
+                Some((tx.sender().to_bytes(), tx.nonce()))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use dusk_core::signatures::bls::SecretKey as AccountSecretKey;
+    use dusk_core::transfer::data::TransactionData;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    use super::*;
+
+    fn moonlight_tx(sk: &AccountSecretKey, nonce: u64) -> ProtocolTransaction {
+        ProtocolTransaction::moonlight(
+            sk,
+            None,
+            0,
+            0,
+            1_000_000,
+            1,
+            nonce,
+            0xFA,
+            None::<TransactionData>,
+        )
+        .expect("transaction should be created")
+    }
+
+    #[test]
+    fn test_nonce_ordering() {
+        let mut rng = StdRng::seed_from_u64(0xbeef);
+        let sk = AccountSecretKey::random(&mut rng);
+        let other_sk = AccountSecretKey::random(&mut rng);
+
+        let first = moonlight_tx(&sk, 1);
+        let second = moonlight_tx(&sk, 2);
+
+        // Two transactions of the same sender in the wrong order: the later
+        // one is out of order
+        let mut ordering = NonceOrdering::default();
+        assert!(ordering.in_order(&second));
+        ordering.include(&second);
+        assert!(!ordering.in_order(&first));
+        assert!(!ordering.in_order(&second));
+
+        // Other senders are not affected
+        assert!(ordering.in_order(&moonlight_tx(&other_sk, 1)));
+
+        // In the right order, both are included
+        let mut ordering = NonceOrdering::default();
+        ordering.include(&first);
+        assert!(ordering.in_order(&second));
+    }
+}
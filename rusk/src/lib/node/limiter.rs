@@ -0,0 +1,67 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use std::sync::Arc;
+
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+use crate::http::ResourceExhausted;
+
+/// Caps the number of expensive contract queries executed concurrently
+/// against the state, so that they cannot starve block production.
+#[derive(Clone)]
+pub(crate) struct QueryLimiter {
+    permits: Arc<Semaphore>,
+}
+
+impl Default for QueryLimiter {
+    fn default() -> Self {
+        Self::new(Semaphore::MAX_PERMITS)
+    }
+}
+
+impl QueryLimiter {
+    pub fn new(max_concurrent: usize) -> Self {
+        Self {
+            permits: Arc::new(Semaphore::new(max_concurrent)),
+        }
+    }
+
+    /// Acquires a permit to run a query, held until dropped.
+    ///
+    /// Fails immediately, without waiting, if the limit is reached.
+    pub fn try_acquire(
+        &self,
+    ) -> Result<OwnedSemaphorePermit, ResourceExhausted> {
+        self.permits
+            .clone()
+            .try_acquire_owned()
+            .map_err(|_| ResourceExhausted("too many concurrent queries"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_query_limiter_saturation() {
+        let limiter = QueryLimiter::new(2);
+
+        let first = limiter.try_acquire().expect("permit to be available");
+        let _second = limiter.try_acquire().expect("permit to be available");
+
+        // Excess queries are rejected without waiting
+        let err = limiter.try_acquire().expect_err("limiter to be saturated");
+        assert!(err.to_string().starts_with("Resource exhausted"));
+
+        // The limiter is shared among clones
+        assert!(limiter.clone().try_acquire().is_err());
+
+        drop(first);
+        assert!(limiter.try_acquire().is_ok());
+    }
+}
@@ -12,7 +12,7 @@ use std::{fs, io};
 use dusk_bytes::Serializable;
 use dusk_consensus::config::{
     ratification_extra, ratification_quorum, validation_extra,
-    validation_quorum, MAX_NUMBER_OF_TRANSACTIONS,
+    validation_quorum, ConsensusConfig, MAX_NUMBER_OF_TRANSACTIONS,
     RATIFICATION_COMMITTEE_CREDITS, VALIDATION_COMMITTEE_CREDITS,
 };
 use dusk_consensus::operations::{CallParams, VerificationOutput, Voter};
@@ -38,7 +38,10 @@ use {node_data::archive::ArchivalData, tokio::sync::mpsc::Sender};
 
 use crate::bloom::Bloom;
 use crate::http::RuesEvent;
-use crate::node::{coinbase_value, Rusk, RuskTip};
+use crate::node::{
+    coinbase_value, DiscardReason, DiscardedTxs, NonceOrdering, QueryLimiter,
+    Rusk, RuskTip,
+};
 use crate::Error::InvalidCreditsCount;
 use crate::{Error, Result};
 
@@ -95,12 +98,24 @@ impl Rusk {
             min_deploy_points,
             feeder_gas_limit,
             event_sender,
+            discarded_txs: Arc::new(RwLock::new(DiscardedTxs::default())),
+            query_limiter: QueryLimiter::default(),
             #[cfg(feature = "archive")]
             archive_sender,
             block_gas_limit,
         })
     }
 
+    /// Sets the maximum number of feeder contract queries executed
+    /// concurrently. Queries in excess are rejected.
+    pub fn with_max_concurrent_queries(
+        mut self,
+        max_concurrent: usize,
+    ) -> Self {
+        self.query_limiter = QueryLimiter::new(max_concurrent);
+        self
+    }
+
     pub fn execute_transactions<I: Iterator<Item = Transaction>>(
         &self,
         params: &CallParams,
@@ -124,6 +139,7 @@ impl Rusk {
 
         let mut spent_txs = Vec::<SpentTransaction>::new();
         let mut discarded_txs = vec![];
+        let mut nonces = NonceOrdering::default();
 
         let mut dusk_spent = 0;
 
@@ -159,6 +175,17 @@ impl Rusk {
                 continue;
             }
 
+            // A transaction following one of the same sender with a higher
+            // nonce can't be included in this block
+            if !nonces.in_order(&unspent_tx.inner) {
+                info!("discard tx {tx_id_hex} due to out of order nonce");
+                self.discarded_txs
+                    .write()
+                    .insert(unspent_tx.id(), DiscardReason::OutOfOrder);
+                discarded_txs.push(unspent_tx);
+                continue;
+            }
+
             match execute(
                 &mut session,
                 &unspent_tx.inner,
@@ -203,6 +230,7 @@ impl Rusk {
                     block_gas_left -= gas_spent;
                     let gas_price = unspent_tx.inner.gas_price();
                     dusk_spent += gas_spent * gas_price;
+                    nonces.include(&unspent_tx.inner);
                     spent_txs.push(SpentTransaction {
                         inner: unspent_tx,
                         gas_spent,
@@ -221,6 +249,9 @@ impl Rusk {
                 Err(e) => {
                     info!("discard tx {tx_id_hex} due to {e:?}");
                     // An unspendable transaction should be discarded
+                    self.discarded_txs
+                        .write()
+                        .insert(unspent_tx.id(), DiscardReason::from(&e));
                     discarded_txs.push(unspent_tx);
                     continue;
                 }
@@ -392,6 +423,21 @@ impl Rusk {
         self.tip.read().current
     }
 
+    /// Returns the reason why the transaction with the given id has been
+    /// discarded during block generation, if any.
+    pub fn discard_reason(&self, tx_id: &[u8; 32]) -> Option<DiscardReason> {
+        self.discarded_txs.read().get(tx_id).cloned()
+    }
+
+    /// Returns the consensus parameters in effect, including the ones
+    /// overridden through environment variables.
+    ///
+    /// Comparing the output of two nodes allows to detect a configuration
+    /// divergence.
+    pub fn consensus_config(&self) -> ConsensusConfig {
+        ConsensusConfig::current()
+    }
+
     /// Returns the nullifiers that already exist from a list of given
     /// `nullifiers`.
     pub fn existing_nullifiers(
@@ -18,11 +18,13 @@ use dusk_core::{
     signatures::bls::PublicKey as BlsPublicKey, stake::StakeData,
     transfer::Transaction as ProtocolTransaction,
 };
+use node::chain::AcceptError;
 use node::vm::{PreverificationResult, VMExecution};
 use node_data::bls::PublicKey;
 use node_data::ledger::{Block, Slash, SpentTransaction, Transaction};
 
-use super::Rusk;
+use super::{Rusk, TxValidity};
+use crate::Error;
 
 impl VMExecution for Rusk {
     fn execute_state_transition<I: Iterator<Item = Transaction>>(
@@ -112,7 +114,18 @@ impl VMExecution for Rusk {
                 slashing,
                 voters,
             )
-            .map_err(|inner| anyhow::anyhow!("Cannot accept txs: {inner}!!"))?;
+            .map_err(|inner| match inner {
+                Error::InconsistentState(computed)
+                    if computed.state_root != blk.header().state_hash =>
+                {
+                    AcceptError::StateRootMismatch {
+                        expected: blk.header().state_hash,
+                        computed: computed.state_root,
+                    }
+                    .into()
+                }
+                inner => anyhow::anyhow!("Cannot accept txs: {inner}!!"),
+            })?;
 
         Ok((txs, verification_output, stake_events))
     }
@@ -295,6 +308,22 @@ where
 }
 
 impl Rusk {
+    /// Checks whether a transaction would currently be valid, running the
+    /// pre-execution checks (unspent nullifiers, valid proof, sufficient
+    /// balance and nonce) against the current state, without executing it.
+    pub fn validate_tx(&self, tx: &Transaction) -> TxValidity {
+        match self.preverify(tx) {
+            Ok(_) => TxValidity {
+                valid: true,
+                reason: None,
+            },
+            Err(e) => TxValidity {
+                valid: false,
+                reason: Some(e.to_string()),
+            },
+        }
+    }
+
     fn query_provisioners(
         &self,
         base_commit: Option<[u8; 32]>,
@@ -0,0 +1,64 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+#![feature(lazy_cell)]
+pub mod common;
+
+use std::env;
+use std::path::Path;
+
+use dusk_consensus::config::{
+    CONSENSUS_MAX_ITER, MIN_STEP_TIMEOUT, VALIDATION_COMMITTEE_CREDITS,
+};
+use rusk::{Result, Rusk};
+use tempfile::tempdir;
+
+use crate::common::logger;
+use crate::common::state::new_state;
+
+const BLOCK_GAS_LIMIT: u64 = 100_000_000_000;
+
+// Creates the Rusk initial state for the test below
+fn initial_state<P: AsRef<Path>>(dir: P) -> Result<Rusk> {
+    let snapshot = toml::from_str(include_str!("./config/transfer.toml"))
+        .expect("Cannot deserialize config");
+
+    new_state(dir, &snapshot, BLOCK_GAS_LIMIT)
+}
+
+// The consensus parameters are read once per process, so this test runs in
+// its own binary to make sure the overrides below are the first values read
+#[tokio::test(flavor = "multi_thread")]
+pub async fn consensus_config() -> Result<()> {
+    // Setup the logger
+    logger();
+
+    // Override some of the parameters before they are first read
+    env::set_var("RUSK_MAX_CANDIDATES_PER_ROUND", "150");
+    env::set_var("RUSK_MIN_BLOCK_INTERVAL", "2");
+
+    let tmp = tempdir().expect("Should be able to create temporary directory");
+    let rusk = initial_state(&tmp)?;
+
+    let config = rusk.consensus_config();
+    assert_eq!(config.max_candidates_per_round, 150);
+    assert_eq!(config.min_block_interval, 2);
+
+    // Parameters not overridden keep their default value
+    assert_eq!(config.max_iterations, CONSENSUS_MAX_ITER);
+    assert_eq!(
+        config.validation_committee_credits,
+        VALIDATION_COMMITTEE_CREDITS
+    );
+    assert_eq!(config.min_step_timeout, MIN_STEP_TIMEOUT);
+
+    config.validate().expect("config to be valid");
+
+    // The configuration is stable across calls
+    assert_eq!(rusk.consensus_config(), config);
+
+    Ok(())
+}
@@ -13,7 +13,9 @@ pub mod gas_behavior;
 pub mod mainnet;
 pub mod moonlight_stake;
 pub mod multi_transfer;
+pub mod nonce_ordering;
 pub mod owner_calls;
 pub mod phoenix_stake;
 pub mod transfer;
 pub mod unspendable;
+pub mod validate_tx;
@@ -0,0 +1,108 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::{Arc, RwLock};
+
+use dusk_bytes::Serializable;
+use dusk_core::transfer::data::TransactionData;
+use dusk_core::transfer::moonlight::Transaction as MoonlightTransaction;
+use dusk_core::transfer::Transaction;
+use rusk::node::DiscardReason;
+use rusk::{Result, Rusk};
+use tempfile::tempdir;
+use test_wallet::{self as wallet};
+
+use crate::common::logger;
+use crate::common::state::{generator_procedure, new_state, ExecuteResult};
+use crate::common::wallet::{TestStateClient, TestStore};
+
+const BLOCK_GAS_LIMIT: u64 = 100_000_000_000;
+const BLOCK_HEIGHT: u64 = 1;
+const GAS_LIMIT: u64 = 1_000_000_000;
+
+// Creates the Rusk initial state for the tests below
+fn initial_state<P: AsRef<Path>>(dir: P) -> Result<Rusk> {
+    let snapshot = toml::from_str(include_str!("../config/transfer.toml"))
+        .expect("Cannot deserialize config");
+
+    new_state(dir, &snapshot, BLOCK_GAS_LIMIT)
+}
+
+#[tokio::test(flavor = "multi_thread")]
+pub async fn out_of_order_nonce() -> Result<()> {
+    // Setup the logger
+    logger();
+
+    let tmp = tempdir().expect("Should be able to create temporary directory");
+    let rusk = initial_state(&tmp)?;
+
+    let cache = Arc::new(RwLock::new(HashMap::new()));
+
+    let wallet = wallet::Wallet::new(
+        TestStore,
+        TestStateClient {
+            rusk: rusk.clone(),
+            cache,
+        },
+    );
+
+    let sender_sk = wallet
+        .account_secret_key(0)
+        .expect("Failed to get secret key");
+    let receiver_pk = wallet
+        .account_public_key(1)
+        .expect("Failed to get public key");
+    let chain_id = rusk.chain_id()?;
+
+    let transfer = |value, gas_price, nonce| -> Transaction {
+        MoonlightTransaction::new(
+            &sender_sk,
+            Some(receiver_pk),
+            value,
+            0,
+            GAS_LIMIT,
+            gas_price,
+            nonce,
+            chain_id,
+            None::<TransactionData>,
+        )
+        .expect("Failed to create transaction")
+        .into()
+    };
+
+    // Transactions are passed sorted by fee, as the mempool would. The one
+    // with nonce 2 pays more than the second transaction with nonce 1, so it
+    // is included first and makes the latter come out of order.
+    let first = transfer(1_000, 3, 1);
+    let higher_nonce = transfer(1_000, 2, 2);
+    let lower_nonce = transfer(2_000, 1, 1);
+
+    let spent_txs = generator_procedure(
+        &rusk,
+        &[first.clone(), higher_nonce.clone(), lower_nonce.clone()],
+        BLOCK_HEIGHT,
+        BLOCK_GAS_LIMIT,
+        vec![],
+        Some(ExecuteResult {
+            executed: 2,
+            discarded: 1,
+        }),
+    )
+    .expect("generator procedure to succeed");
+
+    let spent_ids: Vec<_> =
+        spent_txs.iter().map(|tx| tx.inner.inner.hash()).collect();
+    assert_eq!(spent_ids, vec![first.hash(), higher_nonce.hash()]);
+
+    assert_eq!(
+        rusk.discard_reason(&lower_nonce.hash().to_bytes()),
+        Some(DiscardReason::OutOfOrder),
+    );
+
+    Ok(())
+}
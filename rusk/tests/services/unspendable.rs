@@ -8,12 +8,14 @@ use std::collections::HashMap;
 use std::path::Path;
 use std::sync::{Arc, RwLock};
 
+use dusk_bytes::Serializable;
 use dusk_core::transfer::{
     data::{ContractCall, TransactionData},
     TRANSFER_CONTRACT,
 };
 use rand::prelude::*;
 use rand::rngs::StdRng;
+use rusk::node::DiscardReason;
 use rusk::{Result, Rusk};
 use tempfile::tempdir;
 use test_wallet::{self as wallet};
@@ -127,6 +129,8 @@ fn make_transactions(
         )
         .expect("Making the transaction should succeed");
 
+    let tx_1_id = tx_1.hash().to_bytes();
+
     let expected = ExecuteResult {
         discarded: 1,
         executed: 2,
@@ -150,6 +154,12 @@ fn make_transactions(
         .next()
         .expect("There should be two spent transactions");
 
+    assert_eq!(
+        rusk.discard_reason(&tx_1_id),
+        Some(DiscardReason::InsufficientGas),
+        "The second transaction should be discarded for insufficient gas"
+    );
+
     assert!(tx_0.err.is_some(), "The first transaction should error");
     assert!(tx_2.err.is_none(), "The second transaction should succeed");
     assert_eq!(
@@ -0,0 +1,87 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) DUSK NETWORK. All rights reserved.
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::{Arc, RwLock};
+
+use node_data::ledger::Transaction;
+use rand::prelude::*;
+use rand::rngs::StdRng;
+use rusk::{Result, Rusk};
+use tempfile::tempdir;
+use test_wallet::{self as wallet};
+
+use crate::common::logger;
+use crate::common::state::{generator_procedure, new_state};
+use crate::common::wallet::{TestStateClient, TestStore};
+
+const BLOCK_GAS_LIMIT: u64 = 100_000_000_000;
+const BLOCK_HEIGHT: u64 = 2;
+
+// Creates the Rusk initial state for the tests below
+fn initial_state<P: AsRef<Path>>(dir: P) -> Result<Rusk> {
+    let snapshot = toml::from_str(include_str!("../config/transfer.toml"))
+        .expect("Cannot deserialize config");
+
+    new_state(dir, &snapshot, BLOCK_GAS_LIMIT)
+}
+
+#[tokio::test(flavor = "multi_thread")]
+pub async fn validate_tx() -> Result<()> {
+    // Setup the logger
+    logger();
+
+    let tmp = tempdir().expect("Should be able to create temporary directory");
+    let rusk = initial_state(&tmp)?;
+
+    let cache = Arc::new(RwLock::new(HashMap::new()));
+
+    let wallet = wallet::Wallet::new(
+        TestStore,
+        TestStateClient {
+            rusk: rusk.clone(),
+            cache,
+        },
+    );
+
+    let receiver_pk = wallet
+        .phoenix_public_key(1)
+        .expect("Failed to get public key");
+
+    let mut rng = StdRng::seed_from_u64(0xdead);
+    let tx = wallet
+        .phoenix_transfer(&mut rng, 0, &receiver_pk, 1_000, 1_000_000_000, 2)
+        .expect("Failed to transfer");
+
+    // A transaction spending unspent notes is valid
+    let validity = rusk.validate_tx(&Transaction::from(tx.clone()));
+    assert!(validity.valid, "Transaction should be valid: {validity:?}");
+    assert_eq!(validity.reason, None);
+
+    // Validating the transaction doesn't execute it
+    let root_before = rusk.state_root();
+    rusk.validate_tx(&Transaction::from(tx.clone()));
+    assert_eq!(root_before, rusk.state_root());
+
+    generator_procedure(
+        &rusk,
+        &[tx.clone()],
+        BLOCK_HEIGHT,
+        BLOCK_GAS_LIMIT,
+        vec![],
+        None,
+    )
+    .expect("generator procedure to succeed");
+
+    // Once spent, its nullifiers make the same transaction invalid
+    let validity = rusk.validate_tx(&Transaction::from(tx));
+    assert!(!validity.valid, "Transaction should be invalid");
+    let reason = validity.reason.expect("reason to be set");
+    assert!(reason.contains("Nullifiers"), "Unexpected reason: {reason}");
+
+    Ok(())
+}